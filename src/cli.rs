@@ -1,6 +1,8 @@
+use crate::commands::{ConfigEdit, ConfigKey, SyncStrategy};
 use crate::meta::ForestMode;
 use crate::paths::DisposableRootEntry;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[cfg_attr(
@@ -42,18 +44,21 @@ pub enum Command {
         /// Base directory for worktrees
         #[arg(long, default_value = "~/worktrees")]
         worktree_base: String,
-        /// Default base branch for repos
-        #[arg(long, default_value = "dev")]
-        base_branch: String,
+        /// Default base branch for repos (default: detected from <remote>/HEAD, else dev)
+        #[arg(long)]
+        base_branch: Option<String>,
         /// Feature branch naming template (must contain {name}, e.g. "yourname/{name}")
         #[arg(long)]
         feature_branch_template: Option<String>,
-        /// Git repo paths to manage (repeatable)
+        /// Git repo paths or urls to manage, optionally with a custom name (format: path[:name], repeatable; urls are cloned if missing)
         #[arg(long = "repo")]
         repos: Vec<String>,
         /// Per-repo base branch override (format: repo-name=branch, repeatable)
         #[arg(long = "repo-base-branch")]
         repo_base_branches: Vec<String>,
+        /// Per-repo remote (format: repo-name=remote, repeatable; default: detected from upstream)
+        #[arg(long = "repo-remote")]
+        repo_remotes: Vec<String>,
         /// Forest-root entry that ordinary removal may discard (repeatable)
         #[arg(long = "disposable-root-entry")]
         disposable_root_entries: Vec<DisposableRootEntry>,
@@ -64,13 +69,22 @@ pub enum Command {
         #[arg(long)]
         show_path: bool,
     },
+    /// Show, edit, or upgrade the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
     /// Create a new forest
     New {
-        /// Forest name (e.g., "java-84/refactor-auth")
-        name: String,
+        /// Forest name (e.g., "java-84/refactor-auth"; default with --from-manifest: the manifest's name)
+        #[arg(required_unless_present = "from_manifest")]
+        name: Option<String>,
         /// Mode: feature or review
-        #[arg(long)]
-        mode: ForestMode,
+        #[arg(long, required_unless_present = "from_manifest")]
+        mode: Option<ForestMode>,
+        /// Recreate a forest from a manifest written by `git forest export`
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["mode", "branch", "repo_branches", "pull_requests"])]
+        from_manifest: Option<PathBuf>,
         /// Template to use (default: from config's default_template)
         #[arg(long)]
         template: Option<String>,
@@ -80,12 +94,100 @@ pub enum Command {
         /// Per-repo branch override (format: repo-name=branch, repeatable)
         #[arg(long = "repo-branch")]
         repo_branches: Vec<String>,
+        /// Review mode: check out a pull/merge request fetched from the repo's remote (format: repo-name=number, repeatable)
+        #[arg(long = "pr", value_name = "REPO=NUMBER")]
+        pull_requests: Vec<String>,
         /// Skip fetching remotes before creating
         #[arg(long)]
         no_fetch: bool,
         /// Show plan without executing
         #[arg(long)]
         dry_run: bool,
+        /// Maximum number of repos to fetch and check out concurrently
+        #[arg(long, default_value_t = crate::parallel::DEFAULT_JOBS)]
+        jobs: usize,
+        /// Skip post_create hooks
+        #[arg(long)]
+        no_hooks: bool,
+        /// Run post_create hooks across repos concurrently (up to --jobs)
+        #[arg(long, conflicts_with = "no_hooks")]
+        parallel_hooks: bool,
+    },
+    /// Rename a forest, moving its directory and worktrees
+    Rename {
+        /// Current forest name
+        name: String,
+        /// New forest name
+        new_name: String,
+        /// Feature mode: rename forest-created branches to match the feature branch template
+        #[arg(long)]
+        rename_branches: bool,
+        /// Template whose feature branch template to apply (default: from config's default_template)
+        #[arg(long, requires = "rename_branches")]
+        template: Option<String>,
+        /// Show what would be renamed without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create a forest from existing worktrees made with `git worktree add`
+    Adopt {
+        /// Forest name
+        name: String,
+        /// Existing worktree to move into the forest (format: repo-name=path, repeatable)
+        #[arg(long = "worktree", value_name = "REPO=PATH", required = true)]
+        worktrees: Vec<String>,
+        /// Mode: feature or review
+        #[arg(long, default_value_t = ForestMode::Feature)]
+        mode: ForestMode,
+        /// Template whose repos the worktrees belong to (default: from config's default_template)
+        #[arg(long)]
+        template: Option<String>,
+        /// Show what would be adopted without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add repos to an existing forest
+    AddRepo {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Repo to add: a name from the template, or a path to a git repo (repeatable)
+        #[arg(long = "repo", required = true)]
+        repos: Vec<String>,
+        /// Template to look up repo names and the feature branch template (default: from config's default_template)
+        #[arg(long)]
+        template: Option<String>,
+        /// Override branch for the added repos
+        #[arg(long)]
+        branch: Option<String>,
+        /// Skip fetching remotes before adding
+        #[arg(long)]
+        no_fetch: bool,
+        /// Show plan without executing
+        #[arg(long)]
+        dry_run: bool,
+        /// Maximum number of repos to fetch and check out concurrently
+        #[arg(long, default_value_t = crate::parallel::DEFAULT_JOBS)]
+        jobs: usize,
+        /// Skip post_create hooks
+        #[arg(long)]
+        no_hooks: bool,
+        /// Run post_create hooks across repos concurrently (up to --jobs)
+        #[arg(long, conflicts_with = "no_hooks")]
+        parallel_hooks: bool,
+    },
+    /// Remove one repo from a forest (worktree, forest-created branch, metadata entry)
+    DropRepo {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Repo to remove, by its name in the forest
+        #[arg(long)]
+        repo: String,
+        /// Force removal of a dirty worktree and unmerged branch
+        #[arg(long)]
+        force: bool,
+        /// Show what would be removed without executing
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Remove a forest
     Rm {
@@ -103,18 +205,137 @@ pub enum Command {
         /// Show what would be removed without executing
         #[arg(long)]
         dry_run: bool,
+        #[command(flatten)]
+        filter: RepoFilterArgs,
     },
     /// List all forests
     Ls,
+    /// Check forests against git's worktree registry and repair inconsistencies
+    Doctor {
+        /// Apply the suggested repairs
+        #[arg(long)]
+        fix: bool,
+    },
     /// Show status of repos in a forest
     Status {
         /// Forest name (or auto-detect from cwd)
         name: Option<String>,
+        #[command(flatten)]
+        filter: RepoFilterArgs,
+    },
+    /// Show each repo's changes against its base branch
+    Diff {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        #[command(flatten)]
+        view: ChangeViewArgs,
+    },
+    /// Show each repo's commits that are not on its base branch
+    Log {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        #[command(flatten)]
+        view: ChangeViewArgs,
+    },
+    /// Update each forest branch from its base branch
+    Sync {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Rebase onto or merge in `<remote>/<base_branch>`
+        #[arg(long, value_enum, default_value_t = SyncStrategy::Rebase)]
+        strategy: SyncStrategy,
+        /// Stash uncommitted changes around the update instead of skipping dirty repos
+        #[arg(long)]
+        autostash: bool,
+        /// Skip fetching remotes before syncing
+        #[arg(long)]
+        no_fetch: bool,
+        /// Show what would be updated without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Commit in every repo of a forest with one message
+    Commit {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Commit message
+        #[arg(short, long)]
+        message: String,
+        /// Commit all modified tracked files, not just staged changes
+        #[arg(short, long)]
+        all: bool,
+        /// Append a "Forest: <name>" trailer to the message
+        #[arg(long)]
+        forest_trailer: bool,
+        /// Show which repos would be committed without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Push each forest branch that has commits beyond its base
+    Push {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Also push review-mode scratch branches (forest/<name>)
+        #[arg(long)]
+        allow_scratch: bool,
+        /// Show what would be pushed without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Record every repo's checked-out commit as a named checkpoint
+    Pin {
+        /// Checkpoint name
+        checkpoint: String,
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Replace an existing checkpoint with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check every repo out at the commits recorded by `pin`
+    CheckoutPin {
+        /// Checkpoint name
+        checkpoint: String,
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Move each forest branch to its pinned commit instead of detaching HEAD
+        #[arg(long)]
+        on_branch: bool,
+        /// With --on-branch, move branches even if they have commits not in the checkpoint
+        #[arg(long, requires = "on_branch")]
+        force: bool,
+        /// Show what would be checked out without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Switch worktrees that drifted off their recorded branch back to it
+    RepairDrift {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Record each worktree's checked-out branch in metadata instead of switching back
+        #[arg(long)]
+        adopt: bool,
+        /// Show what would be repaired without executing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write a manifest that reproduces a forest elsewhere
+    Export {
+        /// Forest name (or auto-detect from cwd)
+        name: Option<String>,
+        /// Write the manifest to this file instead of stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
     },
     /// Run a command in each repo of a forest
     Exec {
         /// Forest name
         name: String,
+        /// Run in up to N repos at once (default: 4), prefixing output lines with the repo name
+        #[arg(long, value_name = "N", num_args = 0..=1)]
+        parallel: Option<Option<usize>>,
+        #[command(flatten)]
+        filter: RepoFilterArgs,
         /// Command and arguments to run
         #[arg(last = true)]
         cmd: Vec<String>,
@@ -142,3 +363,181 @@ pub enum Command {
     /// Update git-forest to the latest version
     Update,
 }
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Show the resolved config, including any project config found from cwd
+    Show {
+        /// Only this template
+        #[arg(long)]
+        template: Option<String>,
+        /// Explain which file and section set each value
+        #[arg(long)]
+        origin: bool,
+    },
+    /// Add a repo to a template
+    AddRepo {
+        /// Path to the git repo
+        path: String,
+        /// Template to edit (default: default_template)
+        #[arg(long)]
+        template: Option<String>,
+        /// Repo name (default: last path segment)
+        #[arg(long)]
+        name: Option<String>,
+        /// Base branch override for this repo
+        #[arg(long)]
+        base_branch: Option<String>,
+        /// Remote to fetch and push (default: origin)
+        #[arg(long)]
+        remote: Option<String>,
+    },
+    /// Remove a repo from a template
+    RemoveRepo {
+        /// Repo name
+        name: String,
+        /// Template to edit (default: default_template)
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Set a template setting, or a per-repo override with --repo
+    Set {
+        /// Setting to change
+        key: ConfigKey,
+        /// New value
+        value: String,
+        /// Template to edit (default: default_template)
+        #[arg(long)]
+        template: Option<String>,
+        /// Only set this repo's override (base_branch and remote only)
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Rename a template
+    RenameTemplate {
+        /// Current template name
+        from: String,
+        /// New template name
+        to: String,
+    },
+    /// Delete a template (not the default one)
+    DeleteTemplate {
+        /// Template name
+        name: String,
+    },
+    /// Change the default template
+    SetDefault {
+        /// Template name
+        name: String,
+    },
+    /// Let a project's .git-forest.toml run hooks, set env, and pick the default template
+    Trust {
+        /// Project config file or its directory (default: found from cwd)
+        path: Option<PathBuf>,
+    },
+    /// Upgrade the config and forest metadata to the current format
+    Migrate {
+        /// Show the changes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl From<ConfigCommand> for ConfigEdit {
+    fn from(command: ConfigCommand) -> Self {
+        match command {
+            ConfigCommand::AddRepo {
+                path,
+                template,
+                name,
+                base_branch,
+                remote,
+            } => Self::AddRepo {
+                template,
+                path,
+                name,
+                base_branch,
+                remote,
+            },
+            ConfigCommand::RemoveRepo { name, template } => Self::RemoveRepo { template, name },
+            ConfigCommand::Set {
+                key,
+                value,
+                template,
+                repo,
+            } => Self::Set {
+                template,
+                repo,
+                key,
+                value,
+            },
+            ConfigCommand::RenameTemplate { from, to } => Self::RenameTemplate { from, to },
+            ConfigCommand::DeleteTemplate { name } => Self::DeleteTemplate { name },
+            ConfigCommand::SetDefault { name } => Self::SetDefault { name },
+            ConfigCommand::Show { .. }
+            | ConfigCommand::Trust { .. }
+            | ConfigCommand::Migrate { .. } => {
+                unreachable!("config show, trust, and migrate are not edits")
+            }
+        }
+    }
+}
+
+/// Repo selection shared by `exec`, `status`, and `rm`.
+#[derive(Args)]
+pub struct RepoFilterArgs {
+    /// Only this repo (repeatable)
+    #[arg(long = "repo", value_name = "NAME")]
+    pub repos: Vec<String>,
+    /// Skip this repo (repeatable)
+    #[arg(long = "exclude-repo", value_name = "NAME")]
+    pub exclude_repos: Vec<String>,
+    /// Only repos with uncommitted changes
+    #[arg(long)]
+    pub dirty: bool,
+    /// Only repos whose branch was created by the forest
+    #[arg(long)]
+    pub branch_created: bool,
+    /// Only repos checked out on a different branch than recorded
+    #[arg(long)]
+    pub drifted: bool,
+    /// Only repos whose worktree directory is missing
+    #[arg(long)]
+    pub missing: bool,
+}
+
+/// Output detail shared by `diff` and `log`.
+#[derive(Args)]
+pub struct ChangeViewArgs {
+    /// Show a diffstat per repo
+    #[arg(long, conflicts_with = "name_only")]
+    pub stat: bool,
+    /// Show only the names of changed files
+    #[arg(long)]
+    pub name_only: bool,
+}
+
+impl From<ChangeViewArgs> for crate::commands::ChangeView {
+    fn from(args: ChangeViewArgs) -> Self {
+        if args.stat {
+            Self::Stat
+        } else if args.name_only {
+            Self::NameOnly
+        } else {
+            Self::Full
+        }
+    }
+}
+
+impl From<RepoFilterArgs> for crate::commands::RepoFilter {
+    fn from(args: RepoFilterArgs) -> Self {
+        Self {
+            repos: args.repos,
+            exclude_repos: args.exclude_repos,
+            dirty: args.dirty,
+            branch_created: args.branch_created,
+            drifted: args.drifted,
+            missing: args.missing,
+        }
+    }
+}
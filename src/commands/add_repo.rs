@@ -0,0 +1,521 @@
+use anyhow::{bail, ensure, Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::branch_state::path_exists_or_symlink;
+use super::init::detect_remote;
+use super::new::{
+    clone_missing_repos, compute_target_branch, create_worktrees, ensure_source_repo,
+    fetch_remotes, format_clone_line, format_new_repo_line, format_post_create_lines, plan_repo,
+    record_clones, repo_plan_result, run_post_create_hooks, NewRepoResult, RepoPlan,
+};
+use crate::config::{ResolvedRepo, ResolvedTemplate};
+use crate::meta::{ForestMeta, ForestMode, META_FILENAME};
+use crate::paths::{
+    expand_tilde, forest_root_entry_comparison_key, AbsolutePath, BranchName, ForestName, RepoName,
+};
+
+pub struct AddRepoInputs {
+    /// Template repo names or source repo paths.
+    pub repos: Vec<String>,
+    pub branch_override: Option<String>,
+    pub no_fetch: bool,
+    pub dry_run: bool,
+    pub jobs: usize,
+    pub no_hooks: bool,
+    pub parallel_hooks: bool,
+}
+
+#[derive(Debug)]
+pub struct AddRepoPlan {
+    pub forest_name: ForestName,
+    pub forest_dir: AbsolutePath,
+    pub mode: ForestMode,
+    pub repo_plans: Vec<RepoPlan>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddRepoResult {
+    pub forest_name: ForestName,
+    pub forest_dir: AbsolutePath,
+    pub mode: ForestMode,
+    pub dry_run: bool,
+    pub repos: Vec<NewRepoResult>,
+}
+
+/// Resolve one `--repo` value: a repo name from the template, or a path to a
+/// git repository that takes the template's base branch and its detected remote.
+fn resolve_repo_spec(spec: &str, tmpl: &ResolvedTemplate) -> Result<ResolvedRepo> {
+    if let Some(repo) = tmpl.repos.iter().find(|r| r.name.as_str() == spec) {
+        return Ok(repo.clone());
+    }
+
+    if !spec.contains('/') && !spec.contains('\\') && !spec.starts_with('~') {
+        let known: Vec<&str> = tmpl.repos.iter().map(|r| r.name.as_str()).collect();
+        bail!(
+            "unknown repo: {}\n  hint: known repos: {}; or pass an absolute path to a git repository",
+            spec,
+            known.join(", ")
+        );
+    }
+
+    let path = expand_tilde(spec)?;
+    ensure!(
+        path.is_dir(),
+        "repo path does not exist: {}\n  hint: provide an absolute path to a git repository",
+        path.display()
+    );
+    if crate::git::git(&path, &["rev-parse", "--git-dir"]).is_err() {
+        bail!(
+            "not a git repository: {}\n  hint: provide a path to a git repository",
+            path.display()
+        );
+    }
+
+    let name_str = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = RepoName::new(name_str)
+        .with_context(|| format!("repo has empty name (path: {})", path.display()))?;
+
+    let remote = detect_remote(&path).unwrap_or_else(|| "origin".to_string());
+    Ok(ResolvedRepo {
+        path,
+        url: None,
+        name,
+        base_branch: tmpl.base_branch.clone(),
+        remote,
+        post_create: tmpl.post_create.clone(),
+        pre_remove: tmpl.pre_remove.clone(),
+    })
+}
+
+fn resolve_repo_specs(
+    inputs: &AddRepoInputs,
+    tmpl: &ResolvedTemplate,
+) -> Result<Vec<ResolvedRepo>> {
+    ensure!(
+        !inputs.repos.is_empty(),
+        "at least one --repo is required\n  hint: git forest add-repo <forest> --repo <name-or-path>"
+    );
+    inputs
+        .repos
+        .iter()
+        .map(|spec| resolve_repo_spec(spec, tmpl))
+        .collect()
+}
+
+/// Branch for repos added without `--branch`: the one every repo already in
+/// the forest is on, else the mode's default naming if some repo uses it.
+fn forest_branch(meta: &ForestMeta, tmpl: &ResolvedTemplate) -> Result<String> {
+    let default = compute_target_branch(
+        "",
+        meta.name.as_str(),
+        &meta.mode,
+        &tmpl.feature_branch_template,
+        &None,
+        &[],
+    );
+    let mut branches: Vec<&str> = meta.repos.iter().map(|r| r.branch.as_str()).collect();
+    branches.sort();
+    branches.dedup();
+    match branches.as_slice() {
+        [] => Ok(default),
+        [only] => Ok(only.to_string()),
+        _ if branches.contains(&default.as_str()) => Ok(default),
+        _ => bail!(
+            "repos in forest {:?} are on different branches ({})\n  hint: pass --branch to choose the branch for the added repos",
+            meta.name.as_str(),
+            branches.join(", ")
+        ),
+    }
+}
+
+pub fn plan_add_repo(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    repos: &[ResolvedRepo],
+    inputs: &AddRepoInputs,
+    tmpl: &ResolvedTemplate,
+) -> Result<AddRepoPlan> {
+    let forest_dir = AbsolutePath::new(forest_dir.to_path_buf()).ok_or_else(|| {
+        anyhow::anyhow!("forest directory is not absolute: {}", forest_dir.display())
+    })?;
+
+    // Names already occupying the forest root: existing repos, the metadata
+    // file, and snapshotted disposable entries (compared case-insensitively).
+    let mut taken: HashSet<String> = meta
+        .repos
+        .iter()
+        .map(|r| forest_root_entry_comparison_key(r.name.as_str()))
+        .collect();
+    let reserved: HashSet<String> = meta
+        .disposable_root_entries
+        .iter()
+        .map(|e| forest_root_entry_comparison_key(e.as_str()))
+        .chain(std::iter::once(forest_root_entry_comparison_key(
+            META_FILENAME,
+        )))
+        .collect();
+
+    let branch_str = match &inputs.branch_override {
+        Some(branch) => branch.clone(),
+        None => forest_branch(meta, tmpl)?,
+    };
+    let mut repo_plans = Vec::new();
+    for repo in repos {
+        let key = forest_root_entry_comparison_key(repo.name.as_str());
+        if meta.repos.iter().any(|r| r.name == repo.name) {
+            bail!(
+                "repo {} is already in forest {:?}\n  hint: use `git forest status {}` to inspect it",
+                repo.name,
+                meta.name.as_str(),
+                meta.name.as_str()
+            );
+        }
+        if let Some(existing) = meta.repos.iter().find(|r| *r.source == *repo.path) {
+            bail!(
+                "source {} is already in forest {:?} as {}\n  hint: each source repo can appear in a forest only once",
+                repo.path.display(),
+                meta.name.as_str(),
+                existing.name
+            );
+        }
+        ensure!(
+            !reserved.contains(&key),
+            "repo name {} conflicts with a reserved forest-root entry\n  hint: forest metadata and disposable root entries cannot be used as repo names",
+            repo.name
+        );
+        ensure!(
+            taken.insert(key),
+            "duplicate repo: {}\n  hint: specify each repo at most once",
+            repo.name
+        );
+        ensure_source_repo(repo)?;
+
+        let dest = forest_dir.join(repo.name.as_str());
+        ensure!(
+            !path_exists_or_symlink(&dest),
+            "{} already exists in forest {:?}\n  hint: move or remove it before adding the repo",
+            dest.display(),
+            meta.name.as_str()
+        );
+
+        let branch = BranchName::new(branch_str.clone(), &repo.remote)?;
+        repo_plans.push(plan_repo(repo, branch, &forest_dir)?);
+    }
+
+    Ok(AddRepoPlan {
+        forest_name: meta.name.clone(),
+        forest_dir,
+        mode: meta.mode.clone(),
+        repo_plans,
+    })
+}
+
+fn plan_to_result(plan: &AddRepoPlan, dry_run: bool) -> AddRepoResult {
+    AddRepoResult {
+        forest_name: plan.forest_name.clone(),
+        forest_dir: plan.forest_dir.clone(),
+        mode: plan.mode.clone(),
+        dry_run,
+        repos: plan.repo_plans.iter().map(repo_plan_result).collect(),
+    }
+}
+
+/// Create the planned worktrees, returning the result and the updated metadata.
+pub fn execute_add_repo(
+    plan: &AddRepoPlan,
+    meta: &ForestMeta,
+    jobs: usize,
+) -> Result<(AddRepoResult, ForestMeta)> {
+    let mut meta = meta.clone();
+    create_worktrees(&plan.repo_plans, &plan.forest_dir, jobs, &mut meta)?;
+    Ok((plan_to_result(plan, false), meta))
+}
+
+pub fn cmd_add_repo(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: AddRepoInputs,
+    tmpl: &ResolvedTemplate,
+) -> Result<AddRepoResult> {
+    ensure!(
+        inputs.jobs > 0,
+        "--jobs must be at least 1\n  hint: use --jobs 1 to add repos one at a time"
+    );
+
+    let repos = resolve_repo_specs(&inputs, tmpl)?;
+
+    let clones = if inputs.dry_run {
+        Vec::new()
+    } else {
+        clone_missing_repos(&repos, inputs.jobs)?
+    };
+
+    // Fetch unless --no-fetch
+    if !inputs.no_fetch {
+        fetch_remotes(&repos, inputs.jobs)?;
+    }
+
+    let mut plan = plan_add_repo(forest_dir, meta, &repos, &inputs, tmpl)?;
+    record_clones(&mut plan.repo_plans, &clones);
+
+    if inputs.dry_run {
+        return Ok(plan_to_result(&plan, true));
+    }
+
+    let (mut result, updated) = execute_add_repo(&plan, meta, inputs.jobs)?;
+
+    if !inputs.no_hooks {
+        let hook_jobs = if inputs.parallel_hooks {
+            inputs.jobs
+        } else {
+            1
+        };
+        run_post_create_hooks(
+            &plan.forest_dir,
+            &updated,
+            &plan.repo_plans,
+            &mut result.repos,
+            hook_jobs,
+        );
+    }
+
+    Ok(result)
+}
+
+pub fn format_add_repo_human(result: &AddRepoResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes made.".to_string());
+        lines.push(String::new());
+    }
+
+    lines.push(format!(
+        "Added to forest {:?} ({} mode)",
+        result.forest_name.as_str(),
+        result.mode
+    ));
+    lines.push(format!("  {}", result.forest_dir.display()));
+    lines.push(String::new());
+
+    for repo in &result.repos {
+        lines.push(format_new_repo_line(repo));
+        lines.extend(format_clone_line(repo, result.dry_run));
+        lines.extend(format_post_create_lines(repo, result.dry_run));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{cmd_new, NewInputs};
+    use crate::testutil::TestEnv;
+
+    fn make_inputs(repos: &[&str]) -> AddRepoInputs {
+        AddRepoInputs {
+            repos: repos.iter().map(|r| r.to_string()).collect(),
+            branch_override: None,
+            no_fetch: true,
+            dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
+        }
+    }
+
+    /// Create a forest from a template holding only `initial`, and return the
+    /// full template (which also holds `extra`) for add-repo lookups.
+    fn setup(
+        env: &TestEnv,
+        name: &str,
+        mode: ForestMode,
+        initial: &[&str],
+        extra: &[&str],
+    ) -> (std::path::PathBuf, ForestMeta, ResolvedTemplate) {
+        for repo in initial.iter().chain(extra) {
+            env.create_repo_with_remote(repo);
+        }
+        let initial_tmpl = env.default_template(initial);
+        let all: Vec<&str> = initial.iter().chain(extra).copied().collect();
+        let full_tmpl = env.default_template(&all);
+
+        let result = cmd_new(
+            NewInputs {
+                name: name.to_string(),
+                mode,
+                branch_override: None,
+                repo_branches: vec![],
+                pull_requests: vec![],
+                no_fetch: true,
+                dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
+            },
+            &initial_tmpl,
+        )
+        .unwrap();
+        let dir = result.forest_dir.to_path_buf();
+        let meta = ForestMeta::read(&dir.join(META_FILENAME)).unwrap();
+        (dir, meta, full_tmpl)
+    }
+
+    #[test]
+    fn add_template_repo_uses_feature_branch_and_appends_meta() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(&env, "grow", ForestMode::Feature, &["api"], &["web"]);
+
+        let result = cmd_add_repo(&dir, &meta, make_inputs(&["web"]), &tmpl).unwrap();
+
+        assert!(!result.dry_run);
+        assert_eq!(result.repos.len(), 1);
+        assert_eq!(result.repos[0].branch, "testuser/grow");
+        assert!(result.repos[0].branch_created);
+        assert!(dir.join("web").join(".git").exists());
+
+        let meta = ForestMeta::read(&dir.join(META_FILENAME)).unwrap();
+        let names: Vec<&str> = meta.repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["api", "web"]);
+        assert_eq!(meta.repos[1].branch, "testuser/grow");
+        assert!(meta.repos[1].branch_created);
+    }
+
+    #[test]
+    fn add_repo_in_review_mode_uses_forest_branch() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(&env, "pr-9", ForestMode::Review, &["api"], &["web"]);
+
+        let result = cmd_add_repo(&dir, &meta, make_inputs(&["web"]), &tmpl).unwrap();
+
+        assert_eq!(result.repos[0].branch, "forest/pr-9");
+    }
+
+    #[test]
+    fn add_repo_by_path() {
+        let env = TestEnv::new();
+        let (dir, meta, _) = setup(&env, "by-path", ForestMode::Feature, &["api"], &[]);
+        let extra = env.create_repo_with_remote("tools");
+        let tmpl = env.default_template(&["api"]);
+
+        let result =
+            cmd_add_repo(&dir, &meta, make_inputs(&[extra.to_str().unwrap()]), &tmpl).unwrap();
+
+        assert_eq!(result.repos[0].name.as_str(), "tools");
+        assert_eq!(result.repos[0].base_branch, "main");
+        let meta = ForestMeta::read(&dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.repos.len(), 2);
+        assert_eq!(meta.repos[1].remote.as_deref(), Some("origin"));
+    }
+
+    #[test]
+    fn add_repo_follows_branch_existing_repos_share() {
+        let env = TestEnv::new();
+        let (dir, mut meta, tmpl) = setup(&env, "custom", ForestMode::Feature, &["api"], &["web"]);
+        // As if created with `--branch custom/x` or from another template
+        meta.repos[0].branch = "custom/x".to_string();
+
+        let mut inputs = make_inputs(&["web"]);
+        inputs.dry_run = true;
+        let result = cmd_add_repo(&dir, &meta, inputs, &tmpl).unwrap();
+        assert_eq!(result.repos[0].branch, "custom/x");
+
+        meta.repos.push(meta.repos[0].clone());
+        meta.repos[1].branch = "other/y".to_string();
+        let err = cmd_add_repo(&dir, &meta, make_inputs(&["web"]), &tmpl)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("different branches (custom/x, other/y)"),
+            "error: {}",
+            err
+        );
+        assert!(err.contains("--branch"), "error: {}", err);
+    }
+
+    #[test]
+    fn add_repo_by_path_detects_remote() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(&env, "remote", ForestMode::Feature, &["api"], &[]);
+        let extra = env.create_repo_with_remote("tools");
+        crate::git::git(&extra, &["remote", "rename", "origin", "upstream"]).unwrap();
+
+        let mut inputs = make_inputs(&[extra.to_str().unwrap()]);
+        inputs.dry_run = true;
+        let repos = resolve_repo_specs(&inputs, &tmpl).unwrap();
+        assert_eq!(repos[0].remote, "upstream");
+        cmd_add_repo(&dir, &meta, inputs, &tmpl).unwrap();
+    }
+
+    #[test]
+    fn add_repo_dry_run_does_not_create() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(&env, "dry", ForestMode::Feature, &["api"], &["web"]);
+
+        let mut inputs = make_inputs(&["web"]);
+        inputs.dry_run = true;
+        let result = cmd_add_repo(&dir, &meta, inputs, &tmpl).unwrap();
+
+        assert!(result.dry_run);
+        assert!(matches!(
+            result.repos[0].checkout_kind,
+            crate::commands::CheckoutKind::NewBranch
+        ));
+        assert!(!dir.join("web").exists());
+        let meta = ForestMeta::read(&dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.repos.len(), 1);
+    }
+
+    #[test]
+    fn add_repo_already_in_forest_errors() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(&env, "dup", ForestMode::Feature, &["api"], &[]);
+
+        let err = cmd_add_repo(&dir, &meta, make_inputs(&["api"]), &tmpl)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("already in forest"), "error: {}", err);
+    }
+
+    #[test]
+    fn add_unknown_repo_lists_template_repos() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(&env, "unknown", ForestMode::Feature, &["api"], &["web"]);
+
+        let err = cmd_add_repo(&dir, &meta, make_inputs(&["nope"]), &tmpl)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown repo: nope"), "error: {}", err);
+        assert!(err.contains("web"), "should list known repos: {}", err);
+    }
+
+    #[test]
+    fn failed_add_rolls_back_and_preserves_meta() {
+        let env = TestEnv::new();
+        let (dir, meta, tmpl) = setup(
+            &env,
+            "rollback",
+            ForestMode::Feature,
+            &["api"],
+            &["web", "worker"],
+        );
+        let repos = resolve_repo_specs(&make_inputs(&["web", "worker"]), &tmpl).unwrap();
+        let plan = plan_add_repo(&dir, &meta, &repos, &make_inputs(&[]), &tmpl).unwrap();
+
+        // Sabotage the second repo so its worktree add fails
+        std::fs::remove_dir_all(&*plan.repo_plans[1].source).unwrap();
+
+        assert!(execute_add_repo(&plan, &meta, 2).is_err());
+        assert!(!dir.join("web").exists());
+        assert!(dir.join("api").exists());
+        let meta = ForestMeta::read(&dir.join(META_FILENAME)).unwrap();
+        let names: Vec<&str> = meta.repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["api"]);
+    }
+}
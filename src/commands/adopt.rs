@@ -0,0 +1,456 @@
+use anyhow::{bail, ensure, Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{base_ref, compact_git_error};
+use super::doctor::{parse_worktree_list, path_key};
+use super::new::{plan_forest_dir, template_repo};
+use super::outcome::{planned_results, repo_errors, Outcome, PlannedRepo, RepoOutcome};
+use crate::config::{ResolvedRepo, ResolvedTemplate};
+use crate::meta::{ForestMeta, ForestMode, RepoMeta, META_FILENAME};
+use crate::paths::{AbsolutePath, DisposableRootEntry, ForestName, RepoName};
+
+pub struct AdoptInputs {
+    pub name: String,
+    pub mode: ForestMode,
+    /// Existing worktree path per template repo name.
+    pub worktrees: Vec<(String, PathBuf)>,
+    pub dry_run: bool,
+}
+
+pub struct AdoptPlan {
+    pub forest_name: ForestName,
+    pub forest_dir: AbsolutePath,
+    pub mode: ForestMode,
+    pub disposable_root_entries: Vec<DisposableRootEntry>,
+    pub env: BTreeMap<String, String>,
+    pub repo_plans: Vec<AdoptRepoPlan>,
+}
+
+pub struct AdoptRepoPlan {
+    pub repo: ResolvedRepo,
+    /// Canonical path of the existing worktree.
+    pub from: PathBuf,
+    pub dest: PathBuf,
+    /// Branch checked out in the worktree; recorded with `branch_created = false`.
+    pub branch: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdoptResult {
+    pub forest_name: ForestName,
+    pub forest_dir: AbsolutePath,
+    pub mode: ForestMode,
+    pub dry_run: bool,
+    pub repos: Vec<AdoptRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdoptRepoResult {
+    pub name: RepoName,
+    pub branch: String,
+    /// Where the worktree was before adoption.
+    pub from: PathBuf,
+    pub worktree_path: PathBuf,
+    pub outcome: Outcome,
+}
+
+// --- Planning (read-only) ---
+
+pub fn plan_adopt(inputs: &AdoptInputs, tmpl: &ResolvedTemplate) -> Result<AdoptPlan> {
+    let forest_name = ForestName::new(inputs.name.clone())?;
+    ensure!(
+        !inputs.worktrees.is_empty(),
+        "no worktrees to adopt\n  hint: pass --worktree repo-name=path for each existing worktree"
+    );
+
+    let fdir = plan_forest_dir(&tmpl.worktree_base, &forest_name)?;
+
+    let mut seen_repos = HashSet::new();
+    let mut seen_paths = HashSet::new();
+    let mut repo_plans = Vec::new();
+    for (repo_name, path) in &inputs.worktrees {
+        let repo = template_repo(tmpl, repo_name)?;
+        ensure!(
+            seen_repos.insert(repo_name.as_str()),
+            "repo {} is listed more than once",
+            repo_name
+        );
+        let plan = plan_repo(repo, path, &fdir)?;
+        ensure!(
+            seen_paths.insert(plan.from.clone()),
+            "worktree {} is listed more than once",
+            plan.from.display()
+        );
+        repo_plans.push(plan);
+    }
+
+    Ok(AdoptPlan {
+        forest_name,
+        forest_dir: fdir,
+        mode: inputs.mode.clone(),
+        disposable_root_entries: tmpl.disposable_root_entries.clone(),
+        env: tmpl.env.clone(),
+        repo_plans,
+    })
+}
+
+/// Validate that `path` is a linked worktree registered with the repo's
+/// configured source and is not already part of a forest.
+fn plan_repo(repo: &ResolvedRepo, path: &Path, fdir: &Path) -> Result<AdoptRepoPlan> {
+    let from = path
+        .canonicalize()
+        .with_context(|| format!("worktree path {} does not exist", path.display()))?;
+    let toplevel = crate::git::git(&from, &["rev-parse", "--show-toplevel"])
+        .ok()
+        .map(|toplevel| path_key(Path::new(&toplevel)));
+    ensure!(
+        toplevel.as_ref() == Some(&from),
+        "{} is not the root of a git worktree",
+        from.display()
+    );
+    if from == path_key(&repo.path) {
+        bail!(
+            "{} is the source repo for {} itself\n  hint: adopt a linked worktree created with `git worktree add`",
+            from.display(),
+            repo.name
+        );
+    }
+    let registered = crate::git::git(&repo.path, &["worktree", "list", "--porcelain"])?;
+    if !parse_worktree_list(&registered)
+        .iter()
+        .any(|worktree| path_key(worktree) == from)
+    {
+        bail!(
+            "{} is not a registered worktree of {} (the source for repo {})\n  hint: check the repo name, or run `git worktree repair` in the source if the worktree was moved by hand",
+            from.display(),
+            repo.path.display(),
+            repo.name
+        );
+    }
+    if let Some((existing_dir, existing_meta)) = crate::forest::detect_current_forest(&from)? {
+        bail!(
+            "{} already belongs to forest {:?} at {}",
+            from.display(),
+            existing_meta.name.as_str(),
+            existing_dir.display()
+        );
+    }
+    let branch = crate::git::git(&from, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "{} has a detached HEAD\n  hint: check out a branch in it first; the forest records the checked-out branch",
+                from.display()
+            )
+        })?;
+
+    Ok(AdoptRepoPlan {
+        repo: repo.clone(),
+        dest: fdir.join(repo.name.as_str()),
+        from,
+        branch,
+    })
+}
+
+impl PlannedRepo for AdoptRepoPlan {
+    type Outcome = Outcome;
+    type Result = AdoptRepoResult;
+
+    /// Planning refuses any worktree it can't adopt, so every planned repo proceeds.
+    fn planned_outcome(&self) -> Outcome {
+        Outcome::Success
+    }
+
+    fn result(&self, outcome: Outcome) -> AdoptRepoResult {
+        AdoptRepoResult {
+            name: self.repo.name.clone(),
+            branch: self.branch.clone(),
+            from: self.from.clone(),
+            worktree_path: self.dest.clone(),
+            outcome,
+        }
+    }
+}
+
+impl RepoOutcome for AdoptRepoResult {
+    fn name(&self) -> &RepoName {
+        &self.name
+    }
+
+    fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+}
+
+fn finish(plan: &AdoptPlan, dry_run: bool, repos: Vec<AdoptRepoResult>) -> AdoptResult {
+    AdoptResult {
+        forest_name: plan.forest_name.clone(),
+        forest_dir: plan.forest_dir.clone(),
+        mode: plan.mode.clone(),
+        dry_run,
+        errors: repo_errors(&repos),
+        repos,
+    }
+}
+
+// --- Execution (impure) ---
+
+/// Move each worktree into the new forest, continuing past failures (ADR 0009)
+/// and recording each adopted repo in metadata as it lands (ADR 0011).
+///
+/// If no worktree could be moved, the empty forest directory is removed.
+pub fn execute_adopt(plan: &AdoptPlan) -> Result<AdoptResult> {
+    if let Some(worktree_base) = plan.forest_dir.parent() {
+        std::fs::create_dir_all(worktree_base)?;
+    }
+    // create_dir (not create_dir_all) fails if another process took the name
+    // after planning.
+    std::fs::create_dir(&plan.forest_dir)?;
+    let meta_path = plan.forest_dir.join(META_FILENAME);
+    let mut meta = ForestMeta {
+        name: plan.forest_name.clone(),
+        created_at: Utc::now(),
+        mode: plan.mode.clone(),
+        disposable_root_entries: plan.disposable_root_entries.clone(),
+        env: plan.env.clone(),
+        repos: vec![],
+        pins: vec![],
+    };
+    meta.write(&meta_path)?;
+
+    let mut repos = Vec::new();
+    for rp in &plan.repo_plans {
+        let outcome = match move_worktree(rp) {
+            Ok(()) => {
+                meta.repos.push(adopted_repo_meta(rp));
+                match meta.write(&meta_path) {
+                    Ok(()) => Outcome::Success,
+                    Err(e) => Outcome::Failed {
+                        error: format!(
+                            "worktree moved to {}, but failed to record it in forest metadata: {:#}",
+                            rp.dest.display(),
+                            e
+                        ),
+                    },
+                }
+            }
+            Err(error) => Outcome::Failed { error },
+        };
+        repos.push(rp.result(outcome));
+    }
+
+    if meta.repos.is_empty() {
+        let _ = std::fs::remove_file(&meta_path);
+        let _ = std::fs::remove_dir(&plan.forest_dir);
+    }
+    Ok(finish(plan, false, repos))
+}
+
+fn move_worktree(rp: &AdoptRepoPlan) -> std::result::Result<(), String> {
+    let from = rp.from.to_string_lossy();
+    let dest = rp.dest.to_string_lossy();
+    crate::git::git(&rp.repo.path, &["worktree", "move", &from, &dest])
+        .map(|_| ())
+        .map_err(|e| {
+            format!(
+                "{}\n  hint: run `git worktree move {} {}` in {} to see the full error",
+                compact_git_error(&e.to_string()),
+                from,
+                dest,
+                rp.repo.path.display()
+            )
+        })
+}
+
+fn adopted_repo_meta(rp: &AdoptRepoPlan) -> RepoMeta {
+    let head = format!("refs/heads/{}", rp.branch);
+    let base_commit = base_ref(&rp.dest, Some(&rp.repo.remote), &rp.repo.base_branch)
+        .and_then(|base| crate::git::git(&rp.dest, &["merge-base", &base, &head]).ok());
+    RepoMeta {
+        name: rp.repo.name.clone(),
+        source: rp.repo.path.clone(),
+        branch: rp.branch.clone(),
+        base_branch: rp.repo.base_branch.clone(),
+        remote: Some(rp.repo.remote.clone()),
+        // The forest never deletes a branch it did not create.
+        branch_created: false,
+        pre_remove: rp.repo.pre_remove.clone(),
+        base_commit,
+    }
+}
+
+pub fn cmd_adopt(inputs: AdoptInputs, tmpl: &ResolvedTemplate) -> Result<AdoptResult> {
+    let plan = plan_adopt(&inputs, tmpl)?;
+
+    if inputs.dry_run {
+        let repos = planned_results(&plan.repo_plans);
+        return Ok(finish(&plan, true, repos));
+    }
+
+    execute_adopt(&plan)
+}
+
+pub fn format_adopt_human(result: &AdoptResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    let verb = if result.dry_run {
+        "Adopting"
+    } else {
+        "Adopted"
+    };
+    lines.push(format!(
+        "{} worktrees into forest {:?} ({} mode) at {}",
+        verb,
+        result.forest_name.as_str(),
+        result.mode,
+        result.forest_dir
+    ));
+
+    for repo in &result.repos {
+        let detail = match &repo.outcome {
+            Outcome::Success => format!("{} from {}", repo.branch, repo.from.display()),
+            Outcome::Skipped { reason } => format!("skipped ({})", reason),
+            Outcome::Failed { .. } => "FAILED".to_string(),
+        };
+        lines.push(format!("  {}: {}", repo.name, detail));
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TestEnv;
+
+    fn add_worktree(env: &TestEnv, repo: &str, branch: &str) -> PathBuf {
+        let path = env.root().join("hand-made").join(repo);
+        crate::git::git(
+            &env.repo_path(repo),
+            &["worktree", "add", "-b", branch, path.to_str().unwrap()],
+        )
+        .unwrap();
+        path
+    }
+
+    fn inputs(worktrees: Vec<(&str, PathBuf)>, dry_run: bool) -> AdoptInputs {
+        AdoptInputs {
+            name: "adopted".to_string(),
+            mode: ForestMode::Feature,
+            worktrees: worktrees
+                .into_iter()
+                .map(|(repo, path)| (repo.to_string(), path))
+                .collect(),
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn adopt_moves_worktrees_and_records_branches_as_not_created() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let tmpl = env.default_template(&["foo-api", "foo-web"]);
+        let api = add_worktree(&env, "foo-api", "sue/old-work");
+        let web = add_worktree(&env, "foo-web", "sue/old-work");
+
+        let preview = cmd_adopt(
+            inputs(
+                vec![("foo-api", api.clone()), ("foo-web", web.clone())],
+                true,
+            ),
+            &tmpl,
+        )
+        .unwrap();
+        assert!(preview.dry_run);
+        assert!(api.exists());
+        assert!(!preview.forest_dir.exists());
+
+        let result = cmd_adopt(
+            inputs(vec![("foo-api", api.clone()), ("foo-web", web)], false),
+            &tmpl,
+        )
+        .unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert!(!api.exists());
+
+        let meta = ForestMeta::read(&result.forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.repos.len(), 2);
+        for repo in &meta.repos {
+            assert_eq!(repo.branch, "sue/old-work");
+            assert!(!repo.branch_created);
+            assert!(repo.base_commit.is_some());
+        }
+        let moved = result.forest_dir.join("foo-api");
+        assert_eq!(
+            crate::git::git(&moved, &["symbolic-ref", "--short", "HEAD"]).unwrap(),
+            "sue/old-work"
+        );
+    }
+
+    #[test]
+    fn adopt_rejects_path_not_registered_with_source() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let tmpl = env.default_template(&["foo-api", "foo-web"]);
+        let web = add_worktree(&env, "foo-web", "sue/old-work");
+
+        let err = cmd_adopt(inputs(vec![("foo-api", web)], false), &tmpl)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(
+            err.contains("is not a registered worktree of"),
+            "error: {}",
+            err
+        );
+
+        let err = cmd_adopt(
+            inputs(
+                vec![("foo-api", env.repo_path("foo-api").into_inner())],
+                false,
+            ),
+            &tmpl,
+        )
+        .err()
+        .unwrap()
+        .to_string();
+        assert!(
+            err.contains("is the source repo for foo-api"),
+            "error: {}",
+            err
+        );
+        assert!(!env.worktree_base().join("adopted").exists());
+    }
+
+    #[test]
+    fn adopt_rejects_detached_worktree() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = env.default_template(&["foo-api"]);
+        let api = add_worktree(&env, "foo-api", "sue/old-work");
+        crate::git::git(&api, &["checkout", "--detach"]).unwrap();
+
+        let err = cmd_adopt(inputs(vec![("foo-api", api)], false), &tmpl)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("has a detached HEAD"), "error: {}", err);
+    }
+}
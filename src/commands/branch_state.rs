@@ -72,7 +72,7 @@ impl WorktreeBranchState {
         }
     }
 
-    fn actual_display(&self) -> String {
+    pub(crate) fn actual_display(&self) -> String {
         match &self.actual {
             ActualBranchState::Branch { actual_branch } => actual_branch.clone(),
             ActualBranchState::Detached {
@@ -90,6 +90,17 @@ pub(crate) fn path_exists_or_symlink(path: &Path) -> bool {
     path.symlink_metadata().is_ok()
 }
 
+/// Uncommitted changes, untracked files included. A missing or symlinked
+/// worktree, or a directory that is not a worktree root (whose status would be
+/// the parent repo's), counts as clean.
+pub(crate) fn worktree_has_dirty_files(worktree: &Path) -> bool {
+    worktree.symlink_metadata().is_ok_and(|m| m.is_dir())
+        && verify_worktree_root(worktree).is_ok()
+        && crate::git::git(worktree, &["status", "--porcelain"])
+            .map(|output| !output.is_empty())
+            .unwrap_or(false)
+}
+
 pub(crate) fn compact_git_error(message: &str) -> String {
     let first = message.lines().next().unwrap_or(message);
     let stderr = message.lines().find_map(|line| {
@@ -150,6 +161,15 @@ fn canonicalize_for_compare(path: &Path) -> Result<PathBuf, String> {
         .map_err(|e| format!("failed to canonicalize {}: {}", path.display(), e))
 }
 
-fn short_commit(commit: &str) -> &str {
+/// The base branch's remote-tracking ref, falling back to the local branch.
+pub(crate) fn base_ref(worktree: &Path, remote: Option<&str>, base_branch: &str) -> Option<String> {
+    remote
+        .map(|remote| format!("refs/remotes/{}/{}", remote, base_branch))
+        .into_iter()
+        .chain([format!("refs/heads/{}", base_branch)])
+        .find(|r| matches!(crate::git::ref_exists(worktree, r), Ok(true)))
+}
+
+pub(crate) fn short_commit(commit: &str) -> &str {
     commit.get(..12).unwrap_or(commit)
 }
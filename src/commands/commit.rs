@@ -0,0 +1,413 @@
+use anyhow::{bail, ensure, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{
+    compact_git_error, path_exists_or_symlink, short_commit, WorktreeBranchState,
+};
+use super::outcome::{planned_results, repo_errors, Outcome, PlannedRepo, RepoOutcome};
+use crate::meta::ForestMeta;
+use crate::paths::{ForestName, RepoName};
+
+pub struct CommitInputs {
+    pub message: String,
+    /// Stage modified tracked files first, like `git commit --all`.
+    pub all: bool,
+    /// Append a `Forest: <name>` trailer linking the commits across repos.
+    pub forest_trailer: bool,
+    pub dry_run: bool,
+}
+
+pub struct CommitPlan {
+    pub forest_name: ForestName,
+    /// Full commit message, including any trailer.
+    pub message: String,
+    pub all: bool,
+    pub repo_plans: Vec<CommitRepoPlan>,
+}
+
+pub struct CommitRepoPlan {
+    pub name: RepoName,
+    pub worktree: PathBuf,
+    pub branch: String,
+    pub action: CommitAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitAction {
+    Commit,
+    Skip { reason: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitResult {
+    pub forest_name: ForestName,
+    pub dry_run: bool,
+    pub message: String,
+    pub repos: Vec<CommitRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitRepoResult {
+    pub name: RepoName,
+    pub branch: String,
+    /// SHA of the new commit; `None` in a dry run or when nothing was committed.
+    pub commit: Option<String>,
+    pub outcome: Outcome,
+}
+
+// --- Planning (read-only) ---
+
+/// Plan a commit in every repo with changes.
+///
+/// Refuses outright if any worktree has drifted off its recorded branch, so a
+/// forest-wide commit never lands on an unrelated branch.
+pub fn plan_commit(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: &CommitInputs,
+) -> Result<CommitPlan> {
+    ensure!(
+        !inputs.message.trim().is_empty(),
+        "commit message is empty\n  hint: pass the message with -m <msg>"
+    );
+
+    let drifted: Vec<String> = meta
+        .repos
+        .iter()
+        .filter_map(|repo| {
+            WorktreeBranchState::read(&forest_dir.join(repo.name.as_str()), &repo.branch)
+                .drift_message()
+                .map(|message| format!("{} ({})", repo.name, message))
+        })
+        .collect();
+    if !drifted.is_empty() {
+        bail!(
+            "cannot commit in forest {:?}: {}\n  hint: check out the recorded branches (see `git forest status`) and retry",
+            meta.name.as_str(),
+            drifted.join(", ")
+        );
+    }
+
+    let repo_plans = meta
+        .repos
+        .iter()
+        .map(|repo| {
+            let worktree = forest_dir.join(repo.name.as_str());
+            let action = if !path_exists_or_symlink(&worktree) {
+                CommitAction::Skip {
+                    reason: "worktree missing".to_string(),
+                }
+            } else if has_changes_to_commit(&worktree, inputs.all) {
+                CommitAction::Commit
+            } else {
+                CommitAction::Skip {
+                    reason: "nothing to commit".to_string(),
+                }
+            };
+            CommitRepoPlan {
+                name: repo.name.clone(),
+                worktree,
+                branch: repo.branch.clone(),
+                action,
+            }
+        })
+        .collect();
+
+    let message = if inputs.forest_trailer {
+        format!(
+            "{}\n\nForest: {}",
+            inputs.message.trim_end(),
+            meta.name.as_str()
+        )
+    } else {
+        inputs.message.clone()
+    };
+
+    Ok(CommitPlan {
+        forest_name: meta.name.clone(),
+        message,
+        all: inputs.all,
+        repo_plans,
+    })
+}
+
+/// Staged changes, or with `all`, any modified tracked file.
+fn has_changes_to_commit(worktree: &Path, all: bool) -> bool {
+    let output = if all {
+        crate::git::git(worktree, &["status", "--porcelain", "--untracked-files=no"])
+    } else {
+        crate::git::git(worktree, &["diff", "--cached", "--name-only"])
+    };
+    output.map(|out| !out.is_empty()).unwrap_or(false)
+}
+
+impl PlannedRepo for CommitRepoPlan {
+    type Outcome = Outcome;
+    type Result = CommitRepoResult;
+
+    fn planned_outcome(&self) -> Outcome {
+        match &self.action {
+            CommitAction::Commit => Outcome::Success,
+            CommitAction::Skip { reason } => Outcome::Skipped {
+                reason: reason.clone(),
+            },
+        }
+    }
+
+    fn result(&self, outcome: Outcome) -> CommitRepoResult {
+        CommitRepoResult {
+            name: self.name.clone(),
+            branch: self.branch.clone(),
+            commit: None,
+            outcome,
+        }
+    }
+}
+
+impl RepoOutcome for CommitRepoResult {
+    fn name(&self) -> &RepoName {
+        &self.name
+    }
+
+    fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+}
+
+fn finish(plan: &CommitPlan, dry_run: bool, repos: Vec<CommitRepoResult>) -> CommitResult {
+    CommitResult {
+        forest_name: plan.forest_name.clone(),
+        dry_run,
+        message: plan.message.clone(),
+        errors: repo_errors(&repos),
+        repos,
+    }
+}
+
+// --- Execution (impure) ---
+
+/// Commit in every planned repo, continuing past failures (ADR 0009).
+pub fn execute_commit(plan: &CommitPlan) -> CommitResult {
+    let repos = plan
+        .repo_plans
+        .iter()
+        .map(|rp| match rp.action {
+            CommitAction::Commit => commit_repo(rp, &plan.message, plan.all),
+            CommitAction::Skip { .. } => rp.planned_result(),
+        })
+        .collect();
+    finish(plan, false, repos)
+}
+
+fn commit_repo(rp: &CommitRepoPlan, message: &str, all: bool) -> CommitRepoResult {
+    let mut args = vec!["commit", "-m", message];
+    if all {
+        args.push("--all");
+    }
+    let outcome = match crate::git::git(&rp.worktree, &args) {
+        Ok(_) => Outcome::Success,
+        Err(e) => Outcome::Failed {
+            error: compact_git_error(&e.to_string()),
+        },
+    };
+    let commit = (outcome == Outcome::Success)
+        .then(|| crate::git::git(&rp.worktree, &["rev-parse", "HEAD"]).ok())
+        .flatten();
+
+    CommitRepoResult {
+        commit,
+        ..rp.result(outcome)
+    }
+}
+
+pub fn cmd_commit(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: CommitInputs,
+) -> Result<CommitResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let plan = plan_commit(forest_dir, meta, &inputs)?;
+
+    if inputs.dry_run {
+        let repos = planned_results(&plan.repo_plans);
+        return Ok(finish(&plan, true, repos));
+    }
+
+    Ok(execute_commit(&plan))
+}
+
+pub fn format_commit_human(result: &CommitResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!(
+        "Committing in forest {:?}: {}",
+        result.forest_name.as_str(),
+        result.message.lines().next().unwrap_or_default()
+    ));
+
+    for repo in &result.repos {
+        let detail = match (&repo.outcome, &repo.commit) {
+            (Outcome::Success, Some(sha)) => {
+                format!("committed {} on {}", short_commit(sha), repo.branch)
+            }
+            (Outcome::Success, None) => format!("would commit on {}", repo.branch),
+            (Outcome::Skipped { reason }, _) => format!("skipped ({})", reason),
+            (Outcome::Failed { .. }, _) => "FAILED".to_string(),
+        };
+        lines.push(format!("  {}: {}", repo.name, detail));
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::setup_forest_with_git_repos;
+
+    fn setup(tmp: &Path) -> (PathBuf, ForestMeta) {
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp);
+        for repo in &meta.repos {
+            let worktree = forest_dir.join(repo.name.as_str());
+            crate::git::git(&worktree, &["config", "user.name", "Test"]).unwrap();
+            crate::git::git(&worktree, &["config", "user.email", "test@test.com"]).unwrap();
+        }
+        (forest_dir, meta)
+    }
+
+    fn inputs(message: &str) -> CommitInputs {
+        CommitInputs {
+            message: message.to_string(),
+            all: false,
+            forest_trailer: false,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn commit_staged_changes_and_skip_clean_repos() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup(tmp.path());
+        let api = forest_dir.join("api");
+        std::fs::write(api.join("a.txt"), "a").unwrap();
+        crate::git::git(&api, &["add", "a.txt"]).unwrap();
+        // Untracked files are never committed
+        std::fs::write(forest_dir.join("web").join("b.txt"), "b").unwrap();
+
+        let dry = cmd_commit(
+            &forest_dir,
+            &meta,
+            CommitInputs {
+                dry_run: true,
+                ..inputs("Add a")
+            },
+        )
+        .unwrap();
+        assert_eq!(dry.repos[0].outcome, Outcome::Success);
+        assert_eq!(dry.repos[0].commit, None);
+        assert!(format_commit_human(&dry).contains("api: would commit on main"));
+
+        let result = cmd_commit(
+            &forest_dir,
+            &meta,
+            CommitInputs {
+                forest_trailer: true,
+                ..inputs("Add a")
+            },
+        )
+        .unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repos[0].outcome, Outcome::Success);
+        let head = crate::git::git(&api, &["rev-parse", "HEAD"]).unwrap();
+        assert_eq!(result.repos[0].commit.as_deref(), Some(head.as_str()));
+        assert_eq!(
+            crate::git::git(&api, &["log", "-1", "--format=%B"]).unwrap(),
+            "Add a\n\nForest: test-forest"
+        );
+        assert_eq!(
+            result.repos[1].outcome,
+            Outcome::Skipped {
+                reason: "nothing to commit".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn commit_all_includes_modified_tracked_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup(tmp.path());
+        let web = forest_dir.join("web");
+        std::fs::write(web.join("b.txt"), "v1").unwrap();
+        crate::git::git(&web, &["add", "b.txt"]).unwrap();
+        crate::git::git(&web, &["commit", "-m", "b"]).unwrap();
+        std::fs::write(web.join("b.txt"), "v2").unwrap();
+
+        let staged_only = cmd_commit(&forest_dir, &meta, inputs("Update b")).unwrap();
+        assert!(matches!(
+            staged_only.repos[1].outcome,
+            Outcome::Skipped { .. }
+        ));
+
+        let result = cmd_commit(
+            &forest_dir,
+            &meta,
+            CommitInputs {
+                all: true,
+                ..inputs("Update b")
+            },
+        )
+        .unwrap();
+        assert_eq!(result.repos[1].outcome, Outcome::Success);
+        assert!(crate::git::git(&web, &["status", "--porcelain"])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn commit_refuses_when_any_branch_drifted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup(tmp.path());
+        let api = forest_dir.join("api");
+        std::fs::write(api.join("a.txt"), "a").unwrap();
+        crate::git::git(&api, &["add", "a.txt"]).unwrap();
+        crate::git::git(&forest_dir.join("web"), &["checkout", "-b", "elsewhere"]).unwrap();
+
+        let err = cmd_commit(&forest_dir, &meta, inputs("Add a"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("web (branch drift: expected main, actual elsewhere)"),
+            "error: {}",
+            err
+        );
+        // Nothing was committed anywhere
+        assert_eq!(
+            crate::git::git(&api, &["rev-list", "--count", "HEAD"]).unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn commit_rejects_empty_message() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup(tmp.path());
+
+        let err = cmd_commit(&forest_dir, &meta, inputs("  ")).unwrap_err();
+        assert!(err.to_string().contains("commit message is empty"));
+    }
+}
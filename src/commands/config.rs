@@ -0,0 +1,1098 @@
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::short_commit;
+use super::init::validate_repo_path;
+use crate::config::{
+    discover_project_config, load_project_config, raw_config_to_toml, read_versioned_config,
+    resolve_from_cwd, resolve_layered, trust_key, upgrade_config_file, ConfigOrigin,
+    MultiTemplateConfig, RepoConfig, TemplateConfig, VersionCheckConfig, CONFIG_SCHEMA,
+    PROJECT_CONFIG_FILENAME,
+};
+use crate::forest::{dedupe_discovered_forests, discover_forests_with_dirs};
+use crate::meta::{ForestMeta, META_FILENAME, META_SCHEMA};
+use crate::paths::{expand_tilde, AbsolutePath, DisposableRootEntry};
+use crate::schema::unified_diff;
+
+/// One edit to the config file made by a `git forest config` subcommand.
+pub enum ConfigEdit {
+    AddRepo {
+        template: Option<String>,
+        path: String,
+        name: Option<String>,
+        base_branch: Option<String>,
+        remote: Option<String>,
+    },
+    RemoveRepo {
+        template: Option<String>,
+        name: String,
+    },
+    Set {
+        template: Option<String>,
+        /// Set a per-repo override instead of the template-wide value.
+        repo: Option<String>,
+        key: ConfigKey,
+        value: String,
+    },
+    RenameTemplate {
+        from: String,
+        to: String,
+    },
+    DeleteTemplate {
+        name: String,
+    },
+    SetDefault {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum ConfigKey {
+    BaseBranch,
+    Remote,
+    WorktreeBase,
+    FeatureBranchTemplate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigEditResult {
+    pub config_path: PathBuf,
+    /// Template the edit applied to; the new name for a rename.
+    pub template: String,
+    pub default_template: String,
+    pub summary: String,
+    pub warnings: Vec<String>,
+}
+
+/// Apply `edit` to the config at `config_path`.
+///
+/// The edited config must pass the same validation as a freshly loaded one
+/// (`parse_config`) before it is written back atomically.
+pub fn cmd_config_edit(config_path: &Path, edit: ConfigEdit) -> Result<ConfigEditResult> {
+    let mut raw = crate::config::load_raw_config(config_path)?;
+    // Not required to be valid: editing is how a broken config gets fixed.
+    let before = resolve_from_cwd(&raw, config_path).ok();
+    let (template, summary) = apply_edit(&mut raw, edit)?;
+
+    let resolved = resolve_from_cwd(&raw, config_path)
+        .context("edit would leave the config invalid; nothing was written")?
+        .config;
+    crate::config::write_raw_config_atomic(config_path, &raw)?;
+
+    // Templates extending the edited one may have moved too.
+    let mut warnings = Vec::new();
+    for (name, old) in before.iter().flat_map(|before| &before.config.templates) {
+        let Some(new) = resolved.templates.get(name) else {
+            continue;
+        };
+        if new.worktree_base != old.worktree_base && old.worktree_base.exists() {
+            warnings.push(format!(
+                "template {:?}: forests already under {} stay there and are not moved",
+                name,
+                old.worktree_base.display()
+            ));
+        }
+    }
+
+    Ok(ConfigEditResult {
+        config_path: config_path.to_path_buf(),
+        template,
+        default_template: resolved.default_template,
+        summary,
+        warnings,
+    })
+}
+
+fn apply_edit(raw: &mut MultiTemplateConfig, edit: ConfigEdit) -> Result<(String, String)> {
+    match edit {
+        ConfigEdit::AddRepo {
+            template,
+            path,
+            name,
+            base_branch,
+            remote,
+        } => {
+            let (tmpl_name, tmpl) = template_mut(raw, template.as_deref())?;
+            let path = expand_tilde(&path)?;
+            validate_repo_path(&path)?;
+            let repo = RepoConfig {
+                path: Some(path.into_inner()),
+                url: None,
+                name,
+                base_branch,
+                remote,
+                post_create: None,
+                pre_remove: None,
+            };
+            let summary = format!(
+                "added repo {} to template {:?}",
+                repo.name_or_default(),
+                tmpl_name
+            );
+            tmpl.repos.push(repo);
+            Ok((tmpl_name, summary))
+        }
+        ConfigEdit::RemoveRepo { template, name } => {
+            let (tmpl_name, tmpl) = template_mut(raw, template.as_deref())?;
+            let own = tmpl.repos.iter().any(|repo| repo.name_or_default() == name);
+            if !own && tmpl.extends.is_some() {
+                // Validation rejects the name if the parent doesn't have it either.
+                tmpl.remove_repos.push(name.clone());
+                let summary = format!("removed repo {} from template {:?}", name, tmpl_name);
+                return Ok((tmpl_name, summary));
+            }
+            let index = repo_index(&tmpl_name, tmpl, &name)?;
+            tmpl.repos.remove(index);
+            ensure!(
+                !tmpl.repos.is_empty() || tmpl.extends.is_some(),
+                "cannot remove {}: it is the only repo in template {:?}\n  hint: delete the template with `git forest config delete-template {}` instead",
+                name,
+                tmpl_name,
+                tmpl_name
+            );
+            let summary = format!("removed repo {} from template {:?}", name, tmpl_name);
+            Ok((tmpl_name, summary))
+        }
+        ConfigEdit::Set {
+            template,
+            repo,
+            key,
+            value,
+        } => {
+            let (tmpl_name, tmpl) = template_mut(raw, template.as_deref())?;
+            let summary = match repo {
+                Some(repo) => set_repo_key(&tmpl_name, tmpl, &repo, key, value)?,
+                None => set_template_key(&tmpl_name, tmpl, key, value)?,
+            };
+            Ok((tmpl_name, summary))
+        }
+        ConfigEdit::RenameTemplate { from, to } => {
+            ensure!(
+                !to.trim().is_empty() && to == to.trim(),
+                "template name must not be empty or have leading/trailing whitespace"
+            );
+            template_mut(raw, Some(&from))?;
+            ensure!(
+                !raw.template.contains_key(&to),
+                "template {:?} already exists in config",
+                to
+            );
+            let tmpl = raw.template.remove(&from).expect("checked above");
+            raw.template.insert(to.clone(), tmpl);
+            if raw.default_template == from {
+                raw.default_template = to.clone();
+            }
+            for tmpl in raw.template.values_mut() {
+                if tmpl.extends.as_deref() == Some(from.as_str()) {
+                    tmpl.extends = Some(to.clone());
+                }
+            }
+            // Forests don't record their template (ADR 0012), so nothing else changes.
+            let summary = format!("renamed template {:?} to {:?}", from, to);
+            Ok((to, summary))
+        }
+        ConfigEdit::DeleteTemplate { name } => {
+            template_mut(raw, Some(&name))?;
+            if raw.default_template == name {
+                let others: Vec<&str> = raw
+                    .template
+                    .keys()
+                    .filter(|key| **key != name)
+                    .map(String::as_str)
+                    .collect();
+                if others.is_empty() {
+                    bail!(
+                        "cannot delete {:?}: it is the only template\n  hint: use `git forest reset --config-only` to remove the whole config",
+                        name
+                    );
+                }
+                bail!(
+                    "cannot delete {:?}: it is the default template\n  hint: pick another default first, e.g. `git forest config set-default {}`",
+                    name,
+                    others[0]
+                );
+            }
+            raw.template.remove(&name);
+            let summary = format!("deleted template {:?}", name);
+            Ok((name, summary))
+        }
+        ConfigEdit::SetDefault { name } => {
+            template_mut(raw, Some(&name))?;
+            raw.default_template = name.clone();
+            let summary = format!("default template is now {:?}", name);
+            Ok((name, summary))
+        }
+    }
+}
+
+fn set_template_key(
+    tmpl_name: &str,
+    tmpl: &mut TemplateConfig,
+    key: ConfigKey,
+    value: String,
+) -> Result<String> {
+    match key {
+        ConfigKey::BaseBranch => {
+            // Older configs spell out the template's base branch on every
+            // repo; make those implicit so they follow the new value.
+            let old = tmpl.base_branch.replace(value.clone());
+            for repo in &mut tmpl.repos {
+                if old.is_some() && repo.base_branch == old {
+                    repo.base_branch = None;
+                }
+            }
+            let following = tmpl
+                .repos
+                .iter()
+                .filter(|repo| repo.base_branch.is_none())
+                .count();
+            Ok(format!(
+                "set base_branch of template {:?} to {} ({} of {} repos follow it)",
+                tmpl_name,
+                value,
+                following,
+                tmpl.repos.len()
+            ))
+        }
+        ConfigKey::Remote => {
+            for repo in &mut tmpl.repos {
+                repo.remote = Some(value.clone());
+            }
+            Ok(format!(
+                "set remote of every repo in template {:?} to {}",
+                tmpl_name, value
+            ))
+        }
+        ConfigKey::WorktreeBase => {
+            tmpl.worktree_base = Some(PathBuf::from(&value));
+            Ok(format!(
+                "set worktree_base of template {:?} to {}",
+                tmpl_name, value
+            ))
+        }
+        ConfigKey::FeatureBranchTemplate => {
+            tmpl.feature_branch_template = Some(value.clone());
+            Ok(format!(
+                "set feature_branch_template of template {:?} to {}",
+                tmpl_name, value
+            ))
+        }
+    }
+}
+
+fn set_repo_key(
+    tmpl_name: &str,
+    tmpl: &mut TemplateConfig,
+    repo: &str,
+    key: ConfigKey,
+    value: String,
+) -> Result<String> {
+    let index = repo_index(tmpl_name, tmpl, repo)?;
+    let entry = &mut tmpl.repos[index];
+    match key {
+        ConfigKey::BaseBranch => entry.base_branch = Some(value.clone()),
+        ConfigKey::Remote => entry.remote = Some(value.clone()),
+        ConfigKey::WorktreeBase | ConfigKey::FeatureBranchTemplate => bail!(
+            "{} is a template-wide setting\n  hint: drop --repo to set it for template {:?}",
+            key.as_str(),
+            tmpl_name
+        ),
+    }
+    Ok(format!(
+        "set {} of repo {} in template {:?} to {}",
+        key.as_str(),
+        repo,
+        tmpl_name,
+        value
+    ))
+}
+
+impl ConfigKey {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::BaseBranch => "base_branch",
+            Self::Remote => "remote",
+            Self::WorktreeBase => "worktree_base",
+            Self::FeatureBranchTemplate => "feature_branch_template",
+        }
+    }
+}
+
+fn template_mut<'a>(
+    raw: &'a mut MultiTemplateConfig,
+    name: Option<&str>,
+) -> Result<(String, &'a mut TemplateConfig)> {
+    let key = name.unwrap_or(&raw.default_template).to_string();
+    let available: Vec<String> = raw.template.keys().cloned().collect();
+    let tmpl = raw.template.get_mut(&key).ok_or_else(|| {
+        anyhow!(
+            "template {:?} not found\n  hint: available templates: {}",
+            key,
+            available.join(", ")
+        )
+    })?;
+    Ok((key, tmpl))
+}
+
+fn repo_index(tmpl_name: &str, tmpl: &TemplateConfig, name: &str) -> Result<usize> {
+    tmpl.repos
+        .iter()
+        .position(|repo| repo.name_or_default() == name)
+        .ok_or_else(|| {
+            let known: Vec<String> = tmpl.repos.iter().map(RepoConfig::name_or_default).collect();
+            anyhow!(
+                "repo {} not found in template {:?}\n  hint: known repos: {}",
+                name,
+                tmpl_name,
+                known.join(", ")
+            )
+        })
+}
+
+pub fn format_config_edit_human(result: &ConfigEditResult) -> String {
+    let mut lines = vec![format!(
+        "Updated {}: {}",
+        result.config_path.display(),
+        result.summary
+    )];
+    for warning in &result.warnings {
+        lines.push(format!("warning: {}", warning));
+    }
+    lines.join("\n")
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigShowResult {
+    pub config_path: PathBuf,
+    pub project_config_path: Option<PathBuf>,
+    /// Whether the project config is trusted; absent outside a project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_trusted: Option<bool>,
+    pub default_template: ShownValue<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_check: Option<VersionCheckConfig>,
+    pub templates: Vec<ShownTemplate>,
+}
+
+/// A resolved value, with where it was set when `--origin` is given.
+#[derive(Debug, Serialize)]
+pub struct ShownValue<T> {
+    pub value: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<ConfigOrigin>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShownTemplate {
+    pub name: String,
+    pub worktree_base: ShownValue<AbsolutePath>,
+    pub base_branch: ShownValue<String>,
+    pub feature_branch_template: ShownValue<String>,
+    pub disposable_root_entries: ShownValue<Vec<DisposableRootEntry>>,
+    pub post_create: ShownValue<Option<String>>,
+    pub pre_remove: ShownValue<Option<String>>,
+    pub env: BTreeMap<String, ShownValue<String>>,
+    pub repos: Vec<ShownRepo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShownRepo {
+    pub name: String,
+    pub path: ShownValue<AbsolutePath>,
+    pub base_branch: ShownValue<String>,
+    pub remote: ShownValue<String>,
+}
+
+/// Show the config commands run from cwd use: the personal config at
+/// `config_path` layered over any project config found from cwd.
+pub fn cmd_config_show(
+    config_path: &Path,
+    template: Option<&str>,
+    origin: bool,
+) -> Result<ConfigShowResult> {
+    let raw = read_versioned_config(config_path)?.value;
+    let layered = resolve_from_cwd(&raw, config_path)?;
+    if let Some(name) = template {
+        layered.config.resolve_template(Some(name))?;
+    }
+
+    let shown = |value_origin: &ConfigOrigin| origin.then(|| value_origin.clone());
+    let templates = layered
+        .config
+        .templates
+        .iter()
+        .filter(|(name, _)| template.is_none_or(|t| t == name.as_str()))
+        .map(|(name, tmpl)| {
+            let origins = &layered.origins[name];
+            ShownTemplate {
+                name: name.clone(),
+                worktree_base: ShownValue {
+                    value: tmpl.worktree_base.clone(),
+                    origin: shown(&origins.worktree_base),
+                },
+                base_branch: ShownValue {
+                    value: tmpl.base_branch.clone(),
+                    origin: shown(&origins.base_branch),
+                },
+                feature_branch_template: ShownValue {
+                    value: tmpl.feature_branch_template.clone(),
+                    origin: shown(&origins.feature_branch_template),
+                },
+                disposable_root_entries: ShownValue {
+                    value: tmpl.disposable_root_entries.clone(),
+                    origin: shown(&origins.disposable_root_entries),
+                },
+                post_create: ShownValue {
+                    value: tmpl.post_create.clone(),
+                    origin: shown(&origins.post_create),
+                },
+                pre_remove: ShownValue {
+                    value: tmpl.pre_remove.clone(),
+                    origin: shown(&origins.pre_remove),
+                },
+                env: tmpl
+                    .env
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = ShownValue {
+                            value: value.clone(),
+                            origin: shown(&origins.env[key]),
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect(),
+                repos: tmpl
+                    .repos
+                    .iter()
+                    .map(|repo| {
+                        let repo_origins = &origins.repos[repo.name.as_str()];
+                        ShownRepo {
+                            name: repo.name.to_string(),
+                            path: ShownValue {
+                                value: repo.path.clone(),
+                                origin: shown(&repo_origins.entry),
+                            },
+                            base_branch: ShownValue {
+                                value: repo.base_branch.clone(),
+                                origin: shown(&repo_origins.base_branch),
+                            },
+                            remote: ShownValue {
+                                value: repo.remote.clone(),
+                                origin: shown(&repo_origins.remote),
+                            },
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(ConfigShowResult {
+        config_path: config_path.to_path_buf(),
+        project_trusted: layered
+            .project_path
+            .is_some()
+            .then_some(layered.project_trusted),
+        project_config_path: layered.project_path,
+        default_template: ShownValue {
+            value: layered.config.default_template,
+            origin: shown(&layered.default_template_origin),
+        },
+        version_check: layered.config.version_check,
+        templates,
+    })
+}
+
+/// TOML-like listing; `--origin` adds a trailing comment per value.
+pub fn format_config_show_human(result: &ConfigShowResult) -> String {
+    fn line<T>(key: &str, shown: &ShownValue<T>, value: String) -> String {
+        match &shown.origin {
+            Some(origin) => format!("{} = {}  # {}", key, value, origin),
+            None => format!("{} = {}", key, value),
+        }
+    }
+
+    let mut lines = vec![format!(
+        "# personal config: {}",
+        result.config_path.display()
+    )];
+    if let Some(project) = &result.project_config_path {
+        lines.push(format!("# project config: {}", project.display()));
+        if result.project_trusted == Some(false) {
+            lines.push(
+                "# not trusted: its hooks, env, and default_template are ignored until `git forest config trust`"
+                    .to_string(),
+            );
+        }
+    }
+    let default = &result.default_template;
+    lines.push(line(
+        "default_template",
+        default,
+        format!("{:?}", default.value),
+    ));
+    if let Some(version_check) = &result.version_check {
+        lines.push(String::new());
+        lines.push("[version_check]".to_string());
+        lines.push(format!("enabled = {}", version_check.enabled));
+    }
+
+    for tmpl in &result.templates {
+        let section = format!("template.{}", tmpl.name);
+        lines.push(String::new());
+        lines.push(format!("[{}]", section));
+        let path = |p: &AbsolutePath| format!("{:?}", p.display().to_string());
+        lines.push(line(
+            "worktree_base",
+            &tmpl.worktree_base,
+            path(&tmpl.worktree_base.value),
+        ));
+        lines.push(line(
+            "base_branch",
+            &tmpl.base_branch,
+            format!("{:?}", tmpl.base_branch.value),
+        ));
+        lines.push(line(
+            "feature_branch_template",
+            &tmpl.feature_branch_template,
+            format!("{:?}", tmpl.feature_branch_template.value),
+        ));
+        let entries: Vec<&str> = tmpl
+            .disposable_root_entries
+            .value
+            .iter()
+            .map(DisposableRootEntry::as_str)
+            .collect();
+        lines.push(line(
+            "disposable_root_entries",
+            &tmpl.disposable_root_entries,
+            format!("{:?}", entries),
+        ));
+        for (key, hook) in [
+            ("post_create", &tmpl.post_create),
+            ("pre_remove", &tmpl.pre_remove),
+        ] {
+            if let Some(command) = &hook.value {
+                lines.push(line(key, hook, format!("{:?}", command)));
+            }
+        }
+        if !tmpl.env.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("[{}.env]", section));
+            for (key, value) in &tmpl.env {
+                lines.push(line(key, value, format!("{:?}", value.value)));
+            }
+        }
+        for repo in &tmpl.repos {
+            lines.push(String::new());
+            lines.push(format!("[[{}.repos]]", section));
+            lines.push(format!("name = {:?}", repo.name));
+            lines.push(line("path", &repo.path, path(&repo.path.value)));
+            lines.push(line(
+                "base_branch",
+                &repo.base_branch,
+                format!("{:?}", repo.base_branch.value),
+            ));
+            lines.push(line(
+                "remote",
+                &repo.remote,
+                format!("{:?}", repo.remote.value),
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigTrustResult {
+    pub config_path: PathBuf,
+    pub project_config_path: PathBuf,
+    /// Git blob hash of the trusted contents.
+    pub hash: String,
+}
+
+/// Trust the project config at `project` (a file or the directory holding
+/// one), or the one found from cwd, recording its current hash in the
+/// personal config. Editing the file revokes the trust.
+pub fn cmd_config_trust(config_path: &Path, project: Option<&Path>) -> Result<ConfigTrustResult> {
+    let project_path = match project {
+        Some(path) if path.is_dir() => path.join(PROJECT_CONFIG_FILENAME),
+        Some(path) => path.to_path_buf(),
+        None => {
+            let cwd = std::env::current_dir().context("failed to determine current directory")?;
+            discover_project_config(&cwd).with_context(|| {
+                format!(
+                    "no {} found in {} or its parents\n  hint: pass the project config's path",
+                    PROJECT_CONFIG_FILENAME,
+                    cwd.display()
+                )
+            })?
+        }
+    };
+    let project = load_project_config(&project_path)?;
+
+    let mut raw = crate::config::load_raw_config(config_path)?;
+    raw.trusted_projects
+        .insert(trust_key(&project.path), project.hash.clone());
+    resolve_layered(&raw, config_path, Some(&project))?;
+    crate::config::write_raw_config_atomic(config_path, &raw)?;
+
+    Ok(ConfigTrustResult {
+        config_path: config_path.to_path_buf(),
+        project_config_path: project.path,
+        hash: project.hash,
+    })
+}
+
+pub fn format_config_trust_human(result: &ConfigTrustResult) -> String {
+    format!(
+        "Trusted {} (blob {})\n  Its hooks, env, and default_template now apply; trust it again after it changes.",
+        result.project_config_path.display(),
+        short_commit(&result.hash)
+    )
+}
+
+/// One file `config migrate` upgrades to the current format.
+#[derive(Debug, Serialize)]
+pub struct FileMigration {
+    pub path: PathBuf,
+    /// `config_version` or `meta_version`.
+    pub version_key: &'static str,
+    /// Version the file was written as; files without a version key are
+    /// assigned one from their shape.
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Unified diff of the file's contents before and after.
+    pub diff: String,
+    /// Copy of the original config, written before replacing it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigMigrateResult {
+    pub dry_run: bool,
+    pub files: Vec<FileMigration>,
+}
+
+/// Upgrade the personal config and the metadata of every forest under its
+/// worktree bases to the current format, including files that only lack a
+/// version key. The config is backed up first; forest metadata is rewritten
+/// without one, since a backup in the forest root would block `rm`.
+pub fn cmd_config_migrate(config_path: &Path, dry_run: bool) -> Result<ConfigMigrateResult> {
+    let parsed = read_versioned_config(config_path)?;
+    let config = resolve_from_cwd(&parsed.value, config_path)?.config;
+    let mut files = Vec::new();
+
+    if parsed.needs_upgrade(&CONFIG_SCHEMA) {
+        let diff = file_diff(config_path, &raw_config_to_toml(&parsed.value)?)?;
+        let backup = if dry_run {
+            None
+        } else {
+            Some(upgrade_config_file(config_path, &parsed)?)
+        };
+        files.push(FileMigration {
+            path: config_path.to_path_buf(),
+            version_key: CONFIG_SCHEMA.version_key,
+            from_version: parsed.from,
+            to_version: CONFIG_SCHEMA.current,
+            diff,
+            backup,
+        });
+    }
+
+    let mut forests = Vec::new();
+    for base in config.all_worktree_bases() {
+        forests.extend(discover_forests_with_dirs(base)?);
+    }
+    for forest in dedupe_discovered_forests(forests) {
+        let meta_path = forest.dir.join(META_FILENAME);
+        let parsed = ForestMeta::read_versioned(&meta_path)?;
+        if !parsed.needs_upgrade(&META_SCHEMA) {
+            continue;
+        }
+        let diff = file_diff(&meta_path, &parsed.value.to_toml()?)?;
+        if !dry_run {
+            parsed.value.write(&meta_path)?;
+        }
+        files.push(FileMigration {
+            path: meta_path,
+            version_key: META_SCHEMA.version_key,
+            from_version: parsed.from,
+            to_version: META_SCHEMA.current,
+            diff,
+            backup: None,
+        });
+    }
+
+    Ok(ConfigMigrateResult { dry_run, files })
+}
+
+fn file_diff(path: &Path, upgraded: &str) -> Result<String> {
+    let current = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(unified_diff(&current, upgraded))
+}
+
+pub fn format_config_migrate_human(result: &ConfigMigrateResult) -> String {
+    if result.files.is_empty() {
+        return "Config and forest metadata are already current.".to_string();
+    }
+
+    let mut lines = Vec::new();
+    for file in &result.files {
+        let verb = if result.dry_run {
+            "Would upgrade"
+        } else {
+            "Upgraded"
+        };
+        lines.push(format!(
+            "{} {} from {} {} to {}",
+            verb,
+            file.path.display(),
+            file.version_key,
+            file.from_version,
+            file.to_version
+        ));
+        if let Some(backup) = &file.backup {
+            lines.push(format!("  backup: {}", backup.display()));
+        }
+        if result.dry_run {
+            lines.push(format!("--- {}", file.path.display()));
+            lines.push(format!("+++ {}", file.path.display()));
+            lines.push(file.diff.trim_end().to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{load_config, ResolvedConfig};
+    use crate::testutil::{write_config_atomic, TestEnv};
+    use std::collections::BTreeMap;
+
+    fn write_config(env: &TestEnv, repos: &[&str]) -> PathBuf {
+        for repo in repos {
+            env.create_repo(repo);
+        }
+        let config = ResolvedConfig {
+            default_template: "work".to_string(),
+            templates: BTreeMap::from([("work".to_string(), env.default_template(repos))]),
+            version_check: None,
+        };
+        let path = env.root().join("config.toml");
+        write_config_atomic(&path, &config).unwrap();
+        path
+    }
+
+    #[test]
+    fn config_add_and_remove_repo() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api"]);
+        let web = env.create_repo("foo-web");
+
+        let result = cmd_config_edit(
+            &path,
+            ConfigEdit::AddRepo {
+                template: None,
+                path: web.to_string_lossy().into_owned(),
+                name: None,
+                base_branch: Some("develop".to_string()),
+                remote: Some("upstream".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(result.summary, "added repo foo-web to template \"work\"");
+        let config = load_config(&path).unwrap();
+        let repo = &config.templates["work"].repos[1];
+        assert_eq!(repo.name.as_str(), "foo-web");
+        assert_eq!(repo.base_branch, "develop");
+        assert_eq!(repo.remote, "upstream");
+
+        cmd_config_edit(
+            &path,
+            ConfigEdit::RemoveRepo {
+                template: Some("work".to_string()),
+                name: "foo-api".to_string(),
+            },
+        )
+        .unwrap();
+        let err = cmd_config_edit(
+            &path,
+            ConfigEdit::RemoveRepo {
+                template: None,
+                name: "foo-web".to_string(),
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("only repo in template"), "error: {}", err);
+        assert_eq!(load_config(&path).unwrap().templates["work"].repos.len(), 1);
+    }
+
+    #[test]
+    fn config_add_repo_rejects_duplicate_and_non_git_paths() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api"]);
+        let before = std::fs::read_to_string(&path).unwrap();
+
+        let err = cmd_config_edit(
+            &path,
+            ConfigEdit::AddRepo {
+                template: None,
+                path: env.repo_path("foo-api").to_string_lossy().into_owned(),
+                name: None,
+                base_branch: None,
+                remote: None,
+            },
+        )
+        .unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("duplicate repo name: foo-api"),
+            "error: {:#}",
+            err
+        );
+
+        let plain = env.root().join("plain");
+        std::fs::create_dir(&plain).unwrap();
+        let err = cmd_config_edit(
+            &path,
+            ConfigEdit::AddRepo {
+                template: None,
+                path: plain.to_string_lossy().into_owned(),
+                name: None,
+                base_branch: None,
+                remote: None,
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("not a git repository"), "error: {}", err);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn config_set_base_branch_carries_inheriting_repos() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api", "foo-web"]);
+        cmd_config_edit(
+            &path,
+            ConfigEdit::Set {
+                template: None,
+                repo: Some("foo-web".to_string()),
+                key: ConfigKey::BaseBranch,
+                value: "release".to_string(),
+            },
+        )
+        .unwrap();
+
+        let result = cmd_config_edit(
+            &path,
+            ConfigEdit::Set {
+                template: None,
+                repo: None,
+                key: ConfigKey::BaseBranch,
+                value: "dev".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(result.summary.contains("(1 of 2 repos follow it)"));
+        let tmpl = &load_config(&path).unwrap().templates["work"];
+        assert_eq!(tmpl.base_branch, "dev");
+        assert_eq!(tmpl.repos[0].base_branch, "dev");
+        assert_eq!(tmpl.repos[1].base_branch, "release");
+
+        let err = cmd_config_edit(
+            &path,
+            ConfigEdit::Set {
+                template: None,
+                repo: None,
+                key: ConfigKey::FeatureBranchTemplate,
+                value: "no-placeholder".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("must contain {name}"),
+            "error: {:#}",
+            err
+        );
+    }
+
+    #[test]
+    fn config_rename_delete_and_default_templates() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api"]);
+
+        cmd_config_edit(
+            &path,
+            ConfigEdit::RenameTemplate {
+                from: "work".to_string(),
+                to: "day-job".to_string(),
+            },
+        )
+        .unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.default_template, "day-job");
+
+        let err = cmd_config_edit(
+            &path,
+            ConfigEdit::DeleteTemplate {
+                name: "day-job".to_string(),
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("it is the only template"), "error: {}", err);
+
+        let mut raw = crate::config::load_raw_config(&path).unwrap();
+        let copy = raw.template["day-job"].clone();
+        raw.template.insert("side".to_string(), copy);
+        std::fs::write(&path, toml::to_string_pretty(&raw).unwrap()).unwrap();
+
+        cmd_config_edit(
+            &path,
+            ConfigEdit::SetDefault {
+                name: "side".to_string(),
+            },
+        )
+        .unwrap();
+        let result = cmd_config_edit(
+            &path,
+            ConfigEdit::DeleteTemplate {
+                name: "day-job".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(result.default_template, "side");
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.templates.keys().collect::<Vec<_>>(), vec!["side"]);
+    }
+
+    #[test]
+    fn config_show_reports_origins_only_when_asked() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api"]);
+
+        let plain = cmd_config_show(&path, None, false).unwrap();
+        assert_eq!(plain.default_template.value, "work");
+        assert!(plain.default_template.origin.is_none());
+        assert!(!format_config_show_human(&plain).contains("  # "));
+        assert!(plain.version_check.is_none());
+
+        let mut contents = std::fs::read_to_string(&path).unwrap();
+        contents.push_str("\n[version_check]\nenabled = false\n");
+        std::fs::write(&path, contents).unwrap();
+        let human = format_config_show_human(&cmd_config_show(&path, None, false).unwrap());
+        assert!(
+            human.contains("[version_check]\nenabled = false"),
+            "{}",
+            human
+        );
+
+        let result = cmd_config_show(&path, Some("work"), true).unwrap();
+        let personal = ConfigOrigin::Personal {
+            path: path.clone(),
+            section: Some("template.work".to_string()),
+        };
+        let tmpl = &result.templates[0];
+        assert_eq!(tmpl.base_branch.origin.as_ref(), Some(&personal));
+        assert_eq!(tmpl.pre_remove.origin, Some(ConfigOrigin::Default));
+        assert_eq!(tmpl.repos[0].path.origin.as_ref(), Some(&personal));
+
+        let err = cmd_config_show(&path, Some("nope"), false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("nope"), "error: {}", err);
+    }
+
+    #[test]
+    fn config_trust_records_the_project_hash() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api"]);
+        let project_dir = env.root().join("acme");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let project_path = project_dir.join(PROJECT_CONFIG_FILENAME);
+        std::fs::write(
+            &project_path,
+            "[template.acme]\nbase_branch = \"main\"\npost_create = \"make\"\n[[template.acme.repos]]\npath = \".\"\n",
+        )
+        .unwrap();
+
+        let result = cmd_config_trust(&path, Some(&project_dir)).unwrap();
+        assert_eq!(result.project_config_path, project_path);
+        assert!(format_config_trust_human(&result).starts_with("Trusted "));
+
+        let raw = crate::config::load_raw_config(&path).unwrap();
+        let project = load_project_config(&project_path).unwrap();
+        assert_eq!(raw.trusted_projects[&trust_key(&project_path)], result.hash);
+        assert!(project.is_trusted_by(&raw));
+        assert!(load_config(&path).is_ok());
+
+        let err = cmd_config_trust(&path, Some(&env.root().join("missing.toml")))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing.toml"), "error: {}", err);
+    }
+
+    #[test]
+    fn config_show_reads_an_old_layout_without_rewriting_it() {
+        let env = TestEnv::new();
+        let path = env.root().join("config.toml");
+        let legacy = format!(
+            "[general]\nworktree_base = {:?}\nbase_branch = \"main\"\nfeature_branch_template = \"me/{{name}}\"\n\n[[repos]]\npath = {:?}\n",
+            env.worktree_base().to_str().unwrap(),
+            env.repo_path("foo-api").to_str().unwrap()
+        );
+        std::fs::write(&path, &legacy).unwrap();
+
+        let result = cmd_config_show(&path, None, false).unwrap();
+        assert_eq!(result.default_template.value, "default");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), legacy);
+        assert!(!env.root().join("config.toml.v1.bak").exists());
+    }
+
+    #[test]
+    fn config_migrate_stamps_unversioned_files() {
+        let env = TestEnv::new();
+        let path = write_config(&env, &["foo-api"]);
+        let stamped = std::fs::read_to_string(&path).unwrap();
+        let unversioned = stamped.strip_prefix("config_version = 2\n").unwrap();
+        std::fs::write(&path, unversioned).unwrap();
+        let forest_dir = env.worktree_base().join("old-forest");
+        std::fs::create_dir_all(&forest_dir).unwrap();
+        let meta_path = forest_dir.join(META_FILENAME);
+        let old_meta = "name = \"old-forest\"\ncreated_at = \"2026-02-07T14:30:00Z\"\nmode = \"feature\"\nrepos = []\n";
+        std::fs::write(&meta_path, old_meta).unwrap();
+
+        let preview = cmd_config_migrate(&path, true).unwrap();
+        let paths: Vec<&Path> = preview.files.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(paths, vec![path.as_path(), &*meta_path]);
+        assert!(preview.files[0].diff.contains("+config_version = 2"));
+        assert_eq!(preview.files[1].from_version, 1);
+        assert!(preview.files[1].diff.contains("+meta_version = 1"));
+        assert!(format_config_migrate_human(&preview).contains("Would upgrade"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), unversioned);
+        assert_eq!(std::fs::read_to_string(&meta_path).unwrap(), old_meta);
+
+        let result = cmd_config_migrate(&path, false).unwrap();
+        let backup = result.files[0].backup.clone().unwrap();
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), unversioned);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), stamped);
+        assert!(std::fs::read_to_string(&meta_path)
+            .unwrap()
+            .starts_with("meta_version = 1\n"));
+
+        let again = cmd_config_migrate(&path, false).unwrap();
+        assert!(again.files.is_empty());
+        assert_eq!(
+            format_config_migrate_human(&again),
+            "Config and forest metadata are already current."
+        );
+    }
+}
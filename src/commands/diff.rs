@@ -0,0 +1,328 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{base_ref, compact_git_error, path_exists_or_symlink};
+use crate::meta::{ForestMeta, RepoMeta};
+use crate::paths::{ForestName, RepoName};
+
+/// How much of each repo's changes `diff` and `log` print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeView {
+    /// Full patch for `diff`; one line per commit for `log`.
+    Full,
+    Stat,
+    NameOnly,
+}
+
+impl ChangeView {
+    pub(super) fn git_args(self) -> &'static [&'static str] {
+        match self {
+            ChangeView::Full => &[],
+            ChangeView::Stat => &["--stat"],
+            ChangeView::NameOnly => &["--name-only"],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiffResult {
+    pub forest_name: ForestName,
+    pub view: ChangeView,
+    pub repos: Vec<DiffRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiffRepoResult {
+    pub name: RepoName,
+    pub branch: String,
+    /// What the branch is compared against, e.g. `origin/main`.
+    pub base_ref: Option<String>,
+    pub files: Vec<DiffFile>,
+    /// `git diff` output for the requested view.
+    pub output: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DiffFile {
+    pub path: String,
+    /// Status letter from `git diff --name-status`: A, M, D, T, ...
+    pub status: String,
+    /// `None` for binary files.
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
+}
+
+/// Worktree and base ref to compare a repo's branch against.
+///
+/// Prefers `<remote>/<base_branch>` so a stale local base branch doesn't hide
+/// or invent changes.
+pub(super) fn resolve_base(
+    forest_dir: &Path,
+    repo: &RepoMeta,
+) -> Result<(PathBuf, String), String> {
+    let worktree = forest_dir.join(repo.name.as_str());
+    if !path_exists_or_symlink(&worktree) {
+        return Err(format!("worktree missing at {}", worktree.display()));
+    }
+    let full_ref = base_ref(&worktree, repo.remote.as_deref(), &repo.base_branch)
+        .ok_or_else(|| format!("base branch {} not found", repo.base_branch))?;
+    let short = full_ref
+        .strip_prefix("refs/remotes/")
+        .or_else(|| full_ref.strip_prefix("refs/heads/"))
+        .unwrap_or(&full_ref)
+        .to_string();
+    Ok((worktree, short))
+}
+
+pub fn cmd_diff(forest_dir: &Path, meta: &ForestMeta, view: ChangeView) -> Result<DiffResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let repos: Vec<DiffRepoResult> = meta
+        .repos
+        .iter()
+        .map(|repo| diff_repo(forest_dir, repo, view))
+        .collect();
+    let errors = repos
+        .iter()
+        .filter_map(|r| r.error.as_ref().map(|e| format!("{}: {}", r.name, e)))
+        .collect();
+
+    Ok(DiffResult {
+        forest_name: meta.name.clone(),
+        view,
+        repos,
+        errors,
+    })
+}
+
+fn diff_repo(forest_dir: &Path, repo: &RepoMeta, view: ChangeView) -> DiffRepoResult {
+    let mut result = DiffRepoResult {
+        name: repo.name.clone(),
+        branch: repo.branch.clone(),
+        base_ref: None,
+        files: Vec::new(),
+        output: String::new(),
+        error: None,
+    };
+
+    let (worktree, base) = match resolve_base(forest_dir, repo) {
+        Ok(resolved) => resolved,
+        Err(error) => {
+            result.error = Some(error);
+            return result;
+        }
+    };
+    // Three dots: changes on the branch since it diverged from the base
+    let range = format!("{}...{}", base, repo.branch);
+    result.base_ref = Some(base);
+
+    let diff = |extra: &[&str]| {
+        let mut args = vec!["diff", "--no-color", "--no-renames"];
+        args.extend_from_slice(extra);
+        args.push(&range);
+        crate::git::git(&worktree, &args).map_err(|e| compact_git_error(&e.to_string()))
+    };
+    let outputs = diff(view.git_args()).and_then(|output| {
+        let name_status = diff(&["--name-status"])?;
+        let numstat = diff(&["--numstat"])?;
+        Ok((output, name_status, numstat))
+    });
+    match outputs {
+        Ok((output, name_status, numstat)) => {
+            result.output = output;
+            result.files = parse_diff_files(&name_status, &numstat);
+        }
+        Err(error) => result.error = Some(error),
+    }
+    result
+}
+
+/// Join `--name-status` and `--numstat` output (both with renames disabled,
+/// so each line names exactly one path).
+fn parse_diff_files(name_status: &str, numstat: &str) -> Vec<DiffFile> {
+    let counts: Vec<(Option<usize>, Option<usize>, &str)> = numstat
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let additions = fields.next()?.parse().ok();
+            let deletions = fields.next()?.parse().ok();
+            Some((additions, deletions, fields.next()?))
+        })
+        .collect();
+
+    name_status
+        .lines()
+        .filter_map(|line| {
+            let (status, path) = line.split_once('\t')?;
+            let (additions, deletions) = counts
+                .iter()
+                .find(|(_, _, p)| *p == path)
+                .map_or((None, None), |(a, d, _)| (*a, *d));
+            Some(DiffFile {
+                path: path.to_string(),
+                status: status.to_string(),
+                additions,
+                deletions,
+            })
+        })
+        .collect()
+}
+
+/// One repo's block in `diff` and `log` human output.
+pub(super) struct RepoSection<'a> {
+    pub name: &'a RepoName,
+    pub branch: &'a str,
+    pub base_ref: Option<&'a str>,
+    pub output: &'a str,
+    pub failed: bool,
+}
+
+pub(super) fn format_repo_sections<'a>(
+    sections: impl Iterator<Item = RepoSection<'a>>,
+    empty: &str,
+    errors: &[String],
+) -> String {
+    let mut lines = Vec::new();
+    for section in sections {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        match section.base_ref {
+            Some(base_ref) => lines.push(format!(
+                "=== {}: {} vs {} ===",
+                section.name, section.branch, base_ref
+            )),
+            None => lines.push(format!("=== {} ===", section.name)),
+        }
+        if section.failed {
+            lines.push("  FAILED".to_string());
+        } else if section.output.is_empty() {
+            lines.push(format!("  ({})", empty));
+        } else {
+            lines.push(section.output.to_string());
+        }
+    }
+
+    if !errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+pub fn format_diff_human(result: &DiffResult) -> String {
+    let sections = result.repos.iter().map(|r| RepoSection {
+        name: &r.name,
+        branch: &r.branch,
+        base_ref: r.base_ref.as_deref(),
+        output: &r.output,
+        failed: r.error.is_some(),
+    });
+    format_repo_sections(sections, "no changes", &result.errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::ForestMode;
+    use crate::testutil::{new_forest, TestEnv};
+
+    fn setup_forest(name: &str) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) =
+            new_forest(&env, name, ForestMode::Review, &["foo-api", "foo-web"], &[]);
+        (env, forest_dir, meta)
+    }
+
+    fn commit_file(dir: &Path, file: &str, contents: &str) {
+        crate::git::git(dir, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(dir, &["config", "user.email", "test@test.com"]).unwrap();
+        std::fs::write(dir.join(file), contents).unwrap();
+        crate::git::git(dir, &["add", file]).unwrap();
+        crate::git::git(dir, &["commit", "-m", &format!("Add {}", file)]).unwrap();
+    }
+
+    #[test]
+    fn diff_lists_committed_changes_against_remote_base() {
+        let (_env, forest_dir, meta) = setup_forest("diff-review");
+        let api = forest_dir.join("foo-api");
+        commit_file(&api, "handler.rs", "fn a() {}\nfn b() {}\n");
+        // Uncommitted work is not part of the branch
+        std::fs::write(api.join("scratch.txt"), "wip").unwrap();
+
+        let result = cmd_diff(&forest_dir, &meta, ChangeView::Full).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        let repo = &result.repos[0];
+        assert_eq!(repo.base_ref.as_deref(), Some("origin/main"));
+        assert_eq!(
+            repo.files,
+            vec![DiffFile {
+                path: "handler.rs".to_string(),
+                status: "A".to_string(),
+                additions: Some(2),
+                deletions: Some(0),
+            }]
+        );
+        assert!(repo.output.contains("+fn b() {}"));
+        assert!(result.repos[1].files.is_empty());
+
+        let human = format_diff_human(&result);
+        assert!(human.contains("=== foo-api: forest/diff-review vs origin/main ==="));
+        assert!(
+            human.contains("=== foo-web: forest/diff-review vs origin/main ===\n  (no changes)")
+        );
+
+        let names = cmd_diff(&forest_dir, &meta, ChangeView::NameOnly).unwrap();
+        assert_eq!(names.repos[0].output, "handler.rs");
+        let stat = cmd_diff(&forest_dir, &meta, ChangeView::Stat).unwrap();
+        assert!(stat.repos[0]
+            .output
+            .contains("1 file changed, 2 insertions(+)"));
+    }
+
+    #[test]
+    fn diff_reports_missing_worktree_and_continues() {
+        let (_env, forest_dir, meta) = setup_forest("diff-missing");
+        commit_file(&forest_dir.join("foo-web"), "page.html", "<p>\n");
+        std::fs::remove_dir_all(forest_dir.join("foo-api")).unwrap();
+
+        let result = cmd_diff(&forest_dir, &meta, ChangeView::NameOnly).unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].starts_with("foo-api: worktree missing"));
+        assert_eq!(result.repos[1].output, "page.html");
+        assert!(format_diff_human(&result).contains("=== foo-api ===\n  FAILED"));
+    }
+
+    #[test]
+    fn parse_diff_files_joins_status_and_counts() {
+        let files = parse_diff_files(
+            "M\tsrc/lib.rs\nA\tlogo.png",
+            "3\t1\tsrc/lib.rs\n-\t-\tlogo.png",
+        );
+        assert_eq!(
+            files,
+            vec![
+                DiffFile {
+                    path: "src/lib.rs".to_string(),
+                    status: "M".to_string(),
+                    additions: Some(3),
+                    deletions: Some(1),
+                },
+                DiffFile {
+                    path: "logo.png".to_string(),
+                    status: "A".to_string(),
+                    additions: None,
+                    deletions: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,840 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{path_exists_or_symlink, WorktreeBranchState};
+use super::drop_repo::drop_repo_command;
+use super::ls::is_dot_prefixed;
+use crate::config::ResolvedRepo;
+use crate::forest::{
+    dedupe_discovered_forests, scan_forest_inventory_allowing_staged, DiscoveredForest,
+    ForestInventoryEntry,
+};
+use crate::meta::{ForestMeta, RepoMeta, META_FILENAME};
+use crate::paths::{AbsolutePath, ForestName, RepoName};
+
+// --- Types ---
+
+#[derive(Debug, Serialize)]
+pub struct DoctorResult {
+    /// Whether `--fix` was given and repairs were attempted.
+    pub fix: bool,
+    pub forests_checked: usize,
+    pub findings: Vec<DoctorFinding>,
+    /// Findings still needing attention: no repair exists, it was not run, or it failed.
+    pub unresolved_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DoctorFindingCode {
+    StagedMetadata,
+    MissingMetadata,
+    UnreadableMetadata,
+    SourceMoved,
+    SourceMissing,
+    StaleRegistration,
+    UnregisteredWorktree,
+    MissingWorktree,
+    BranchDrift,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorFinding {
+    pub code: DoctorFindingCode,
+    pub forest: Option<ForestName>,
+    pub repo: Option<RepoName>,
+    pub path: String,
+    pub message: String,
+    /// Repair `--fix` applies; `None` when the finding needs manual attention.
+    pub fix: Option<DoctorFix>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorFix {
+    pub action: DoctorFixAction,
+    pub description: String,
+    /// `None` unless `--fix` ran this repair.
+    pub outcome: Option<DoctorFixOutcome>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DoctorFixAction {
+    Prune,
+    Reregister,
+    RepointSource,
+    RestoreMetadata,
+    RemoveStagedMetadata,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum DoctorFixOutcome {
+    Success,
+    Failed { error: String },
+}
+
+enum FixPlan {
+    Prune {
+        source: PathBuf,
+    },
+    Reregister {
+        source: PathBuf,
+        worktree: PathBuf,
+    },
+    RepointSource {
+        forest_dir: PathBuf,
+        repo: RepoName,
+        new_source: AbsolutePath,
+        worktree: PathBuf,
+    },
+    RestoreMetadata {
+        staged: PathBuf,
+        meta_path: PathBuf,
+    },
+    RemoveStagedMetadata {
+        staged: PathBuf,
+    },
+}
+
+struct PendingFinding {
+    code: DoctorFindingCode,
+    forest: Option<ForestName>,
+    repo: Option<RepoName>,
+    path: PathBuf,
+    message: String,
+    fix: Option<FixPlan>,
+}
+
+// --- Diagnosis ---
+
+/// Cross-check every forest under `worktree_bases` against git's worktree
+/// registry in each source repo. `template_repos` are used to find sources
+/// that moved; with `fix`, each finding's repair is run and its outcome recorded.
+pub fn cmd_doctor(
+    worktree_bases: &[&Path],
+    template_repos: &[&ResolvedRepo],
+    fix: bool,
+) -> Result<DoctorResult> {
+    let mut pending = Vec::new();
+    let mut forests = Vec::new();
+    let mut missing_metadata = Vec::new();
+    let mut restored_dirs = BTreeSet::new();
+
+    for base in worktree_bases {
+        let (inventory, staged) = scan_forest_inventory_allowing_staged(base)?;
+        for staged_path in staged {
+            let finding = staged_metadata_finding(base, staged_path);
+            if let Some(FixPlan::RestoreMetadata { meta_path, .. }) = &finding.fix {
+                if let Some(dir) = meta_path.parent() {
+                    restored_dirs.insert(path_key(dir));
+                }
+            }
+            pending.push(finding);
+        }
+        for entry in inventory {
+            match entry {
+                ForestInventoryEntry::Discovered(forest) => forests.push(forest),
+                ForestInventoryEntry::MissingMetadata { dir } => missing_metadata.push(dir),
+                ForestInventoryEntry::UnreadableMetadata { dir, message } => {
+                    pending.push(PendingFinding {
+                        code: DoctorFindingCode::UnreadableMetadata,
+                        forest: None,
+                        repo: None,
+                        path: dir,
+                        message,
+                        fix: None,
+                    });
+                }
+            }
+        }
+    }
+
+    // A staged-metadata restore covers its forest directory; nested worktree
+    // bases and dot-prefixed entries are not forests.
+    let base_keys: BTreeSet<PathBuf> = worktree_bases.iter().map(|b| path_key(b)).collect();
+    for dir in missing_metadata {
+        let key = path_key(&dir);
+        if restored_dirs.contains(&key) || base_keys.contains(&key) || is_dot_prefixed(&dir) {
+            continue;
+        }
+        pending.push(PendingFinding {
+            code: DoctorFindingCode::MissingMetadata,
+            forest: None,
+            repo: None,
+            path: dir,
+            message: "directory under worktree base has no .forest-meta.toml\n  hint: restore the metadata or remove the directory".to_string(),
+            fix: None,
+        });
+    }
+
+    let forests = dedupe_discovered_forests(forests);
+    let mut registries = Registries::default();
+    let mut reported = BTreeSet::new();
+    for forest in &forests {
+        for repo in &forest.meta.repos {
+            if let Some(finding) =
+                check_repo(forest, repo, template_repos, &mut registries, &mut reported)
+            {
+                pending.push(finding);
+            }
+        }
+    }
+    pending.extend(stale_registrations(
+        worktree_bases,
+        &forests,
+        template_repos,
+        &mut registries,
+        &reported,
+    ));
+
+    let mut outcomes: Vec<Option<DoctorFixOutcome>> = pending.iter().map(|_| None).collect();
+    if fix {
+        // Prune last: an earlier repair may re-register a worktree whose old
+        // registration a prune would otherwise discard.
+        let order = (0..pending.len())
+            .filter(|&i| !matches!(pending[i].fix, Some(FixPlan::Prune { .. })))
+            .chain(
+                (0..pending.len())
+                    .filter(|&i| matches!(pending[i].fix, Some(FixPlan::Prune { .. }))),
+            )
+            .collect::<Vec<_>>();
+        for i in order {
+            if let Some(plan) = &pending[i].fix {
+                outcomes[i] = Some(match apply_fix(plan) {
+                    Ok(()) => DoctorFixOutcome::Success,
+                    Err(error) => DoctorFixOutcome::Failed {
+                        error: format!("{error:#}"),
+                    },
+                });
+            }
+        }
+    }
+
+    let findings: Vec<DoctorFinding> = pending
+        .into_iter()
+        .zip(outcomes)
+        .map(|(finding, outcome)| DoctorFinding {
+            code: finding.code,
+            forest: finding.forest,
+            repo: finding.repo,
+            path: finding.path.to_string_lossy().into_owned(),
+            message: finding.message,
+            fix: finding.fix.map(|plan| DoctorFix {
+                action: plan.action(),
+                description: plan.description(),
+                outcome,
+            }),
+        })
+        .collect();
+    let unresolved_count = findings
+        .iter()
+        .filter(|finding| {
+            !matches!(
+                finding.fix,
+                Some(DoctorFix {
+                    outcome: Some(DoctorFixOutcome::Success),
+                    ..
+                })
+            )
+        })
+        .count();
+
+    Ok(DoctorResult {
+        fix,
+        forests_checked: forests.len(),
+        findings,
+        unresolved_count,
+    })
+}
+
+fn staged_metadata_finding(base: &Path, staged: PathBuf) -> PendingFinding {
+    let meta = match ForestMeta::read(&staged) {
+        Ok(meta) => meta,
+        Err(error) => {
+            return PendingFinding {
+                code: DoctorFindingCode::StagedMetadata,
+                forest: None,
+                repo: None,
+                message: format!(
+                    "staged metadata from an interrupted removal is unreadable: {error:#}"
+                ),
+                path: staged,
+                fix: None,
+            };
+        }
+    };
+
+    let forest_dir = base.join(meta.name.sanitized());
+    let meta_path = forest_dir.join(META_FILENAME);
+    let (message, fix) = if !path_exists_or_symlink(&forest_dir) {
+        (
+            "removal finished but left its staged metadata behind".to_string(),
+            Some(FixPlan::RemoveStagedMetadata {
+                staged: staged.clone(),
+            }),
+        )
+    } else if !path_exists_or_symlink(&meta_path) {
+        (
+            format!(
+                "removal was interrupted; {} is missing its metadata",
+                forest_dir.display()
+            ),
+            Some(FixPlan::RestoreMetadata {
+                staged: staged.clone(),
+                meta_path,
+            }),
+        )
+    } else {
+        (
+            format!(
+                "{} already has metadata\n  hint: compare the two files and remove the staged one",
+                forest_dir.display()
+            ),
+            None,
+        )
+    };
+    PendingFinding {
+        code: DoctorFindingCode::StagedMetadata,
+        forest: Some(meta.name),
+        repo: None,
+        path: staged,
+        message,
+        fix,
+    }
+}
+
+fn check_repo(
+    forest: &DiscoveredForest,
+    repo: &RepoMeta,
+    template_repos: &[&ResolvedRepo],
+    registries: &mut Registries,
+    reported: &mut BTreeSet<PathBuf>,
+) -> Option<PendingFinding> {
+    let worktree = forest.dir.join(repo.name.as_str());
+    let finding = |code, message, fix| PendingFinding {
+        code,
+        forest: Some(forest.meta.name.clone()),
+        repo: Some(repo.name.clone()),
+        path: worktree.clone(),
+        message,
+        fix,
+    };
+
+    let Some(registered) = registries.get(&repo.source) else {
+        return Some(match relocated_source(repo, template_repos) {
+            Some(new_source) => finding(
+                DoctorFindingCode::SourceMoved,
+                format!(
+                    "source repo {} is gone; the template now points to {}",
+                    repo.source, new_source
+                ),
+                Some(FixPlan::RepointSource {
+                    forest_dir: forest.dir.clone(),
+                    repo: repo.name.clone(),
+                    new_source,
+                    worktree: worktree.clone(),
+                }),
+            ),
+            None => finding(
+                DoctorFindingCode::SourceMissing,
+                format!(
+                    "source repo {} is not a git repository\n  hint: restore it, or point a template repo named {} at its new location and re-run doctor",
+                    repo.source, repo.name
+                ),
+                None,
+            ),
+        });
+    };
+
+    let key = path_key(&worktree);
+    let is_registered = registered.contains(&key);
+    if !path_exists_or_symlink(&worktree) {
+        if is_registered {
+            reported.insert(key);
+            return Some(finding(
+                DoctorFindingCode::StaleRegistration,
+                format!(
+                    "worktree directory is gone but still registered in {}",
+                    repo.source
+                ),
+                Some(FixPlan::Prune {
+                    source: repo.source.to_path_buf(),
+                }),
+            ));
+        }
+        return Some(finding(
+            DoctorFindingCode::MissingWorktree,
+            format!(
+                "worktree is missing\n  hint: drop it with `{}`",
+                drop_repo_command(forest.meta.name.as_str(), repo.name.as_str())
+            ),
+            None,
+        ));
+    }
+    if !is_registered {
+        return Some(finding(
+            DoctorFindingCode::UnregisteredWorktree,
+            format!("worktree is not registered in {}", repo.source),
+            Some(FixPlan::Reregister {
+                source: repo.source.to_path_buf(),
+                worktree: worktree.clone(),
+            }),
+        ));
+    }
+
+    let branch_state = WorktreeBranchState::read(&worktree, &repo.branch);
+    branch_state.drift_message().map(|message| {
+        finding(
+            DoctorFindingCode::BranchDrift,
+            format!(
+                "{}\n  hint: check out {} in {}",
+                message,
+                repo.branch,
+                worktree.display()
+            ),
+            None,
+        )
+    })
+}
+
+/// Template repo with the same name at a different, existing location. Only
+/// an unambiguous match is used.
+fn relocated_source(repo: &RepoMeta, template_repos: &[&ResolvedRepo]) -> Option<AbsolutePath> {
+    let candidates: BTreeMap<PathBuf, &AbsolutePath> = template_repos
+        .iter()
+        .filter(|candidate| candidate.name == repo.name && candidate.path != repo.source)
+        .filter(|candidate| candidate.path.is_dir())
+        .map(|candidate| (path_key(&candidate.path), &candidate.path))
+        .collect();
+    if candidates.len() != 1 {
+        return None;
+    }
+    candidates.into_values().next().cloned()
+}
+
+/// Registered worktrees under a worktree base whose directories are gone and
+/// which no forest repo accounts for, e.g. leftovers of a deleted forest.
+fn stale_registrations(
+    worktree_bases: &[&Path],
+    forests: &[DiscoveredForest],
+    template_repos: &[&ResolvedRepo],
+    registries: &mut Registries,
+    reported: &BTreeSet<PathBuf>,
+) -> Vec<PendingFinding> {
+    let base_keys: Vec<PathBuf> = worktree_bases.iter().map(|b| path_key(b)).collect();
+    let sources: BTreeSet<PathBuf> = forests
+        .iter()
+        .flat_map(|forest| {
+            forest
+                .meta
+                .repos
+                .iter()
+                .map(|repo| repo.source.to_path_buf())
+        })
+        .chain(template_repos.iter().map(|repo| repo.path.to_path_buf()))
+        .collect();
+
+    let mut findings = Vec::new();
+    let mut seen = reported.clone();
+    for source in sources {
+        let Some(registered) = registries.get(&source) else {
+            continue;
+        };
+        for worktree in registered.clone() {
+            let under_base = base_keys.iter().any(|base| worktree.starts_with(base));
+            if !under_base || path_exists_or_symlink(&worktree) || !seen.insert(worktree.clone()) {
+                continue;
+            }
+            findings.push(PendingFinding {
+                code: DoctorFindingCode::StaleRegistration,
+                forest: None,
+                repo: None,
+                message: format!(
+                    "worktree directory is gone but still registered in {}",
+                    source.display()
+                ),
+                path: worktree,
+                fix: Some(FixPlan::Prune {
+                    source: source.clone(),
+                }),
+            });
+        }
+    }
+    findings
+}
+
+/// Cached `git worktree list` output per source repo; `None` when the source
+/// is not a readable git repository.
+#[derive(Default)]
+struct Registries(BTreeMap<PathBuf, Option<BTreeSet<PathBuf>>>);
+
+impl Registries {
+    fn get(&mut self, source: &Path) -> Option<&BTreeSet<PathBuf>> {
+        self.0
+            .entry(source.to_path_buf())
+            .or_insert_with(|| registered_worktrees(source).ok())
+            .as_ref()
+    }
+}
+
+fn registered_worktrees(source: &Path) -> Result<BTreeSet<PathBuf>> {
+    let output = crate::git::git(source, &["worktree", "list", "--porcelain"])?;
+    Ok(parse_worktree_list(&output)
+        .into_iter()
+        .map(|path| path_key(&path))
+        .collect())
+}
+
+/// Linked worktree paths from `git worktree list --porcelain`, skipping the
+/// main worktree that is always listed first.
+pub(super) fn parse_worktree_list(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("worktree "))
+        .skip(1)
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Canonical form of a path that may no longer exist: the deepest existing
+/// ancestor is canonicalized and the missing components re-appended.
+pub(super) fn path_key(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => path_key(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+// --- Repair ---
+
+impl FixPlan {
+    fn action(&self) -> DoctorFixAction {
+        match self {
+            FixPlan::Prune { .. } => DoctorFixAction::Prune,
+            FixPlan::Reregister { .. } => DoctorFixAction::Reregister,
+            FixPlan::RepointSource { .. } => DoctorFixAction::RepointSource,
+            FixPlan::RestoreMetadata { .. } => DoctorFixAction::RestoreMetadata,
+            FixPlan::RemoveStagedMetadata { .. } => DoctorFixAction::RemoveStagedMetadata,
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            FixPlan::Prune { source } => {
+                format!("run `git worktree prune` in {}", source.display())
+            }
+            FixPlan::Reregister { source, worktree } => format!(
+                "run `git worktree repair {}` in {}",
+                worktree.display(),
+                source.display()
+            ),
+            FixPlan::RepointSource {
+                repo, new_source, ..
+            } => format!(
+                "record {} as the source of {} and repair its worktree",
+                new_source, repo
+            ),
+            FixPlan::RestoreMetadata { meta_path, .. } => {
+                format!("move the staged metadata back to {}", meta_path.display())
+            }
+            FixPlan::RemoveStagedMetadata { .. } => "delete the staged metadata".to_string(),
+        }
+    }
+}
+
+fn apply_fix(plan: &FixPlan) -> Result<()> {
+    match plan {
+        FixPlan::Prune { source } => {
+            crate::git::git(source, &["worktree", "prune"])?;
+        }
+        FixPlan::Reregister { source, worktree } => {
+            repair_worktree(source, worktree)?;
+        }
+        FixPlan::RepointSource {
+            forest_dir,
+            repo,
+            new_source,
+            worktree,
+        } => {
+            let meta_path = forest_dir.join(META_FILENAME);
+            let mut meta = ForestMeta::read(&meta_path)?;
+            if let Some(entry) = meta.repos.iter_mut().find(|entry| &entry.name == repo) {
+                entry.source = new_source.clone();
+            }
+            meta.write(&meta_path)?;
+            if path_exists_or_symlink(worktree) {
+                repair_worktree(new_source, worktree)?;
+            }
+        }
+        FixPlan::RestoreMetadata { staged, meta_path } => {
+            std::fs::rename(staged, meta_path).map_err(|error| {
+                anyhow::anyhow!(
+                    "failed to move {} to {}: {}",
+                    staged.display(),
+                    meta_path.display(),
+                    error
+                )
+            })?;
+        }
+        FixPlan::RemoveStagedMetadata { staged } => {
+            std::fs::remove_file(staged).map_err(|error| {
+                anyhow::anyhow!("failed to remove {}: {}", staged.display(), error)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn repair_worktree(source: &Path, worktree: &Path) -> Result<()> {
+    let worktree = worktree
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("worktree path is not UTF-8: {}", worktree.display()))?;
+    crate::git::git(source, &["worktree", "repair", worktree])?;
+    Ok(())
+}
+
+// --- Formatting ---
+
+pub fn format_doctor_human(result: &DoctorResult) -> String {
+    let forests = match result.forests_checked {
+        1 => "1 forest".to_string(),
+        n => format!("{} forests", n),
+    };
+    if result.findings.is_empty() {
+        return format!("Checked {}: no problems found.", forests);
+    }
+
+    let mut lines = vec![format!(
+        "Checked {}: {} problem(s) found.",
+        forests,
+        result.findings.len()
+    )];
+    let mut fixable = 0;
+    for finding in &result.findings {
+        let subject = match (&finding.forest, &finding.repo) {
+            (Some(forest), Some(repo)) => format!("{}/{}", forest, repo),
+            _ => finding.path.clone(),
+        };
+        lines.push(format!(
+            "  {} {}: {}",
+            finding.code.as_str(),
+            subject,
+            super::rm::format_error_single_line(&finding.message)
+        ));
+        if let Some(fix) = &finding.fix {
+            let status = match &fix.outcome {
+                None => {
+                    fixable += 1;
+                    "run with --fix".to_string()
+                }
+                Some(DoctorFixOutcome::Success) => "fixed".to_string(),
+                Some(DoctorFixOutcome::Failed { error }) => {
+                    format!("failed: {}", super::rm::format_error_single_line(error))
+                }
+            };
+            lines.push(format!("    fix: {} ({})", fix.description, status));
+        }
+    }
+
+    if fixable > 0 {
+        lines.push(String::new());
+        lines.push(format!(
+            "Run `git forest doctor --fix` to apply {} fix(es).",
+            fixable
+        ));
+    }
+    lines.join("\n")
+}
+
+impl DoctorFindingCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::StagedMetadata => "staged-metadata",
+            Self::MissingMetadata => "missing-metadata",
+            Self::UnreadableMetadata => "unreadable-metadata",
+            Self::SourceMoved => "source-moved",
+            Self::SourceMissing => "source-missing",
+            Self::StaleRegistration => "stale-registration",
+            Self::UnregisteredWorktree => "unregistered-worktree",
+            Self::MissingWorktree => "missing-worktree",
+            Self::BranchDrift => "branch-drift",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{cmd_new, NewInputs};
+    use crate::meta::{ForestMode, STAGED_META_PREFIX};
+    use crate::testutil::TestEnv;
+
+    fn setup_forest(env: &TestEnv, name: &str) -> PathBuf {
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let tmpl = env.default_template(&["foo-api", "foo-web"]);
+        let inputs = NewInputs {
+            name: name.to_string(),
+            mode: ForestMode::Feature,
+            branch_override: None,
+            repo_branches: vec![],
+            pull_requests: vec![],
+            no_fetch: true,
+            dry_run: false,
+            jobs: 1,
+            no_hooks: true,
+            parallel_hooks: false,
+        };
+        cmd_new(inputs, &tmpl).unwrap().forest_dir.into_inner()
+    }
+
+    fn codes(result: &DoctorResult) -> Vec<DoctorFindingCode> {
+        result.findings.iter().map(|f| f.code).collect()
+    }
+
+    #[test]
+    fn doctor_reports_healthy_forest() {
+        let env = TestEnv::new();
+        setup_forest(&env, "healthy");
+        let base = env.worktree_base();
+
+        let result = cmd_doctor(&[&base], &[], false).unwrap();
+        assert_eq!(result.forests_checked, 1);
+        assert!(result.findings.is_empty(), "{:?}", result.findings);
+        assert_eq!(
+            format_doctor_human(&result),
+            "Checked 1 forest: no problems found."
+        );
+    }
+
+    #[test]
+    fn doctor_prunes_deleted_worktree_and_reports_drift() {
+        let env = TestEnv::new();
+        let forest_dir = setup_forest(&env, "broken");
+        std::fs::remove_dir_all(forest_dir.join("foo-api")).unwrap();
+        crate::git::git(&forest_dir.join("foo-web"), &["checkout", "--detach"]).unwrap();
+        let base = env.worktree_base();
+
+        let result = cmd_doctor(&[&base], &[], false).unwrap();
+        assert_eq!(
+            codes(&result),
+            vec![
+                DoctorFindingCode::StaleRegistration,
+                DoctorFindingCode::BranchDrift
+            ]
+        );
+        assert_eq!(result.unresolved_count, 2);
+        let prune = result.findings[0].fix.as_ref().unwrap();
+        assert_eq!(prune.action, DoctorFixAction::Prune);
+        assert!(prune.outcome.is_none());
+        assert!(format_doctor_human(&result).contains("run with --fix"));
+
+        let result = cmd_doctor(&[&base], &[], true).unwrap();
+        let prune = result.findings[0].fix.as_ref().unwrap();
+        assert!(matches!(prune.outcome, Some(DoctorFixOutcome::Success)));
+        assert_eq!(result.unresolved_count, 1);
+
+        let result = cmd_doctor(&[&base], &[], false).unwrap();
+        assert_eq!(
+            codes(&result),
+            vec![
+                DoctorFindingCode::MissingWorktree,
+                DoctorFindingCode::BranchDrift
+            ]
+        );
+    }
+
+    #[test]
+    fn doctor_reregisters_moved_forest_directory() {
+        let env = TestEnv::new();
+        let forest_dir = setup_forest(&env, "moved");
+        let base = env.worktree_base();
+        std::fs::rename(&forest_dir, base.join("moved-by-hand")).unwrap();
+
+        let result = cmd_doctor(&[&base], &[], true).unwrap();
+        // The old paths are still registered until the repair rewrites them.
+        assert_eq!(
+            codes(&result),
+            vec![
+                DoctorFindingCode::UnregisteredWorktree,
+                DoctorFindingCode::UnregisteredWorktree,
+                DoctorFindingCode::StaleRegistration,
+                DoctorFindingCode::StaleRegistration
+            ]
+        );
+        assert_eq!(result.unresolved_count, 0, "{:?}", result.findings);
+
+        let result = cmd_doctor(&[&base], &[], false).unwrap();
+        assert!(result.findings.is_empty(), "{:?}", result.findings);
+        let status = crate::git::git(&base.join("moved-by-hand").join("foo-api"), &["status"]);
+        assert!(status.is_ok());
+    }
+
+    #[test]
+    fn doctor_repoints_moved_source_repo() {
+        let env = TestEnv::new();
+        let forest_dir = setup_forest(&env, "relocated");
+        let base = env.worktree_base();
+        let old_source = env.repo_path("foo-api");
+        let new_source = env.root().join("moved").join("foo-api");
+        std::fs::create_dir_all(new_source.parent().unwrap()).unwrap();
+        std::fs::rename(&old_source, &new_source).unwrap();
+
+        let mut tmpl = env.default_template(&["foo-api", "foo-web"]);
+        tmpl.repos[0].path = AbsolutePath::new(new_source.clone()).unwrap();
+        let template_repos: Vec<&ResolvedRepo> = tmpl.repos.iter().collect();
+
+        let result = cmd_doctor(&[&base], &template_repos, true).unwrap();
+        assert_eq!(codes(&result), vec![DoctorFindingCode::SourceMoved]);
+        let fix = result.findings[0].fix.as_ref().unwrap();
+        assert_eq!(fix.action, DoctorFixAction::RepointSource);
+        assert!(
+            matches!(fix.outcome, Some(DoctorFixOutcome::Success)),
+            "{:?}",
+            fix.outcome
+        );
+
+        let meta = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(*meta.repos[0].source, *new_source);
+        let result = cmd_doctor(&[&base], &template_repos, false).unwrap();
+        assert!(result.findings.is_empty(), "{:?}", result.findings);
+    }
+
+    #[test]
+    fn doctor_restores_staged_metadata_from_interrupted_removal() {
+        let env = TestEnv::new();
+        let forest_dir = setup_forest(&env, "half-removed");
+        let base = env.worktree_base();
+        let staged = base.join(format!("{}4242", STAGED_META_PREFIX));
+        std::fs::rename(forest_dir.join(META_FILENAME), &staged).unwrap();
+
+        let result = cmd_doctor(&[&base], &[], false).unwrap();
+        assert_eq!(codes(&result), vec![DoctorFindingCode::StagedMetadata]);
+        assert_eq!(
+            result.findings[0].fix.as_ref().unwrap().action,
+            DoctorFixAction::RestoreMetadata
+        );
+
+        cmd_doctor(&[&base], &[], true).unwrap();
+        assert!(!staged.exists());
+        assert!(forest_dir.join(META_FILENAME).exists());
+        let result = cmd_doctor(&[&base], &[], false).unwrap();
+        assert_eq!(result.forests_checked, 1);
+        assert!(result.findings.is_empty(), "{:?}", result.findings);
+    }
+
+    #[test]
+    fn parse_worktree_list_skips_main_worktree() {
+        let output = "worktree /src/api\nHEAD abc\nbranch refs/heads/main\n\nworktree /wt/f/api\nHEAD def\ndetached\nprunable gitdir file points to non-existent location\n";
+        assert_eq!(
+            parse_worktree_list(output),
+            vec![PathBuf::from("/wt/f/api")]
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::rm::{
+    current_forest_root_safety_error, delete_branch, format_error_single_line, format_repo_rm_line,
+    plan_pre_remove_outcome, plan_repo_rm, plan_repo_rm_outcomes, remove_worktree_after_hook,
+    run_pre_remove_hook, RepoRmPlan, RepoRmResult, RmOutcome,
+};
+use crate::meta::{ForestMeta, META_FILENAME};
+use crate::paths::ForestName;
+
+pub struct DropRepoPlan {
+    pub forest_name: ForestName,
+    pub forest_dir: PathBuf,
+    pub repo_plan: RepoRmPlan,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DropRepoResult {
+    pub forest_name: ForestName,
+    pub forest_dir: PathBuf,
+    pub dry_run: bool,
+    pub force: bool,
+    pub repo: RepoRmResult,
+    /// Whether the repo's entry was (or would be) removed from `.forest-meta.toml`.
+    pub meta_updated: bool,
+    pub errors: Vec<String>,
+}
+
+/// The command that drops `repo` from `forest`, for hints about missing worktrees.
+pub(super) fn drop_repo_command(forest: &str, repo: &str) -> String {
+    format!("git forest drop-repo {} --repo {}", forest, repo)
+}
+
+// --- Planning (read-only) ---
+
+pub fn plan_drop_repo(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    repo_name: &str,
+) -> Result<DropRepoPlan> {
+    let Some(repo) = meta.repos.iter().find(|r| r.name.as_str() == repo_name) else {
+        let known: Vec<&str> = meta.repos.iter().map(|r| r.name.as_str()).collect();
+        bail!(
+            "repo {} is not in forest {:?}\n  hint: repos in this forest: {}",
+            repo_name,
+            meta.name.as_str(),
+            known.join(", ")
+        );
+    };
+
+    Ok(DropRepoPlan {
+        forest_name: meta.name.clone(),
+        forest_dir: forest_dir.to_path_buf(),
+        repo_plan: plan_repo_rm(forest_dir, meta, repo, "git forest drop-repo --force"),
+    })
+}
+
+/// Outcomes for the hook, worktree, and branch steps, in that order.
+type RepoOutcomes = (RmOutcome, RmOutcome, RmOutcome);
+
+fn dirty_outcomes(rp: &RepoRmPlan, errors: &mut Vec<String>) -> RepoOutcomes {
+    let msg = format!("{}: worktree has uncommitted changes", rp.name);
+    errors.push(msg.clone());
+    errors.push(
+        "hint: commit or stash changes, then retry — or use `git forest drop-repo --force`"
+            .to_string(),
+    );
+    let not_removed = || RmOutcome::Skipped {
+        reason: "worktree not removed".to_string(),
+    };
+    (
+        not_removed(),
+        RmOutcome::Failed { error: msg },
+        not_removed(),
+    )
+}
+
+fn finish(
+    plan: &DropRepoPlan,
+    dry_run: bool,
+    force: bool,
+    (pre_remove_hook, worktree_removed, branch_deleted): RepoOutcomes,
+    meta_updated: bool,
+    errors: Vec<String>,
+) -> DropRepoResult {
+    DropRepoResult {
+        forest_name: plan.forest_name.clone(),
+        forest_dir: plan.forest_dir.clone(),
+        dry_run,
+        force,
+        repo: RepoRmResult {
+            name: plan.repo_plan.name.clone(),
+            branch_state: plan.repo_plan.branch_state.clone(),
+            pre_remove_hook,
+            worktree_removed,
+            branch_deleted,
+        },
+        meta_updated,
+        errors,
+    }
+}
+
+fn root_refused(plan: &DropRepoPlan, dry_run: bool, force: bool, error: String) -> DropRepoResult {
+    let skipped = || RmOutcome::Skipped {
+        reason: "forest root refused".to_string(),
+    };
+    finish(
+        plan,
+        dry_run,
+        force,
+        (skipped(), skipped(), skipped()),
+        false,
+        vec![error],
+    )
+}
+
+fn plan_to_dry_run_result(plan: &DropRepoPlan, force: bool) -> DropRepoResult {
+    if let Some(error) = current_forest_root_safety_error(&plan.forest_dir) {
+        return root_refused(plan, true, force, error);
+    }
+
+    let rp = &plan.repo_plan;
+    let mut errors = Vec::new();
+    let outcomes = if !force && rp.has_dirty_files {
+        dirty_outcomes(rp, &mut errors)
+    } else {
+        let pre_remove_hook = plan_pre_remove_outcome(rp, force);
+        let (worktree_removed, branch_deleted) = plan_repo_rm_outcomes(rp, force, &mut errors);
+        (pre_remove_hook, worktree_removed, branch_deleted)
+    };
+    let meta_updated = errors.is_empty();
+    finish(plan, true, force, outcomes, meta_updated, errors)
+}
+
+// --- Execution (impure) ---
+
+/// Remove the repo's worktree and forest-created branch, then drop its entry
+/// from the forest metadata.
+///
+/// The entry is kept when either step fails so `drop-repo` (or `rm`) can be
+/// retried against the same metadata.
+pub fn execute_drop_repo(plan: &DropRepoPlan, meta: &ForestMeta, force: bool) -> DropRepoResult {
+    if let Some(error) = current_forest_root_safety_error(&plan.forest_dir) {
+        return root_refused(plan, false, force, error);
+    }
+
+    let rp = &plan.repo_plan;
+    let mut errors = Vec::new();
+
+    if !force && rp.has_dirty_files {
+        let outcomes = dirty_outcomes(rp, &mut errors);
+        return finish(plan, false, force, outcomes, false, errors);
+    }
+
+    let pre_remove_hook = run_pre_remove_hook(rp, force, &mut errors);
+    let (worktree_removed, wt_succeeded) =
+        remove_worktree_after_hook(rp, &pre_remove_hook, force, &mut errors);
+    let branch_deleted = delete_branch(rp, force, wt_succeeded, &mut errors);
+
+    let mut meta_updated = false;
+    if errors.is_empty() {
+        let mut updated = meta.clone();
+        updated.repos.retain(|r| r.name != rp.name);
+        match updated.write(&plan.forest_dir.join(META_FILENAME)) {
+            Ok(()) => meta_updated = true,
+            Err(e) => errors.push(format!(
+                "{}: failed to update forest meta: {:#}",
+                rp.name, e
+            )),
+        }
+    }
+
+    finish(
+        plan,
+        false,
+        force,
+        (pre_remove_hook, worktree_removed, branch_deleted),
+        meta_updated,
+        errors,
+    )
+}
+
+pub fn cmd_drop_repo(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    repo_name: &str,
+    force: bool,
+    dry_run: bool,
+) -> Result<DropRepoResult> {
+    let plan = plan_drop_repo(forest_dir, meta, repo_name)?;
+
+    if dry_run {
+        return Ok(plan_to_dry_run_result(&plan, force));
+    }
+
+    Ok(execute_drop_repo(&plan, meta, force))
+}
+
+pub fn format_drop_repo_human(result: &DropRepoResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+        if result.errors.is_empty() {
+            lines.push(format!(
+                "Would drop {} from forest {:?}",
+                result.repo.name,
+                result.forest_name.as_str()
+            ));
+        } else {
+            lines.push(format!(
+                "Drop blocked for {} in forest {:?}",
+                result.repo.name,
+                result.forest_name.as_str()
+            ));
+        }
+    } else if result.errors.is_empty() {
+        lines.push(format!(
+            "Dropped {} from forest {:?}",
+            result.repo.name,
+            result.forest_name.as_str()
+        ));
+    } else {
+        lines.push(format!(
+            "Drop of {} from forest {:?} incomplete",
+            result.repo.name,
+            result.forest_name.as_str()
+        ));
+    }
+
+    lines.push(format_repo_rm_line(&result.repo, result.dry_run));
+
+    if !result.meta_updated && !result.errors.is_empty() {
+        lines.push("  Forest metadata unchanged; retry after resolving the errors.".to_string());
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{cmd_new, NewInputs};
+    use crate::meta::ForestMode;
+    use crate::testutil::TestEnv;
+
+    fn setup(env: &TestEnv, name: &str) -> (PathBuf, ForestMeta) {
+        env.create_repo_with_remote("api");
+        env.create_repo_with_remote("web");
+        let tmpl = env.default_template(&["api", "web"]);
+        let result = cmd_new(
+            NewInputs {
+                name: name.to_string(),
+                mode: ForestMode::Feature,
+                branch_override: None,
+                repo_branches: vec![],
+                pull_requests: vec![],
+                no_fetch: true,
+                dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
+            },
+            &tmpl,
+        )
+        .unwrap();
+        let dir = result.forest_dir.to_path_buf();
+        let meta = read_meta(&dir);
+        (dir, meta)
+    }
+
+    fn read_meta(dir: &Path) -> ForestMeta {
+        ForestMeta::read(&dir.join(META_FILENAME)).unwrap()
+    }
+
+    #[test]
+    fn drop_removes_worktree_branch_and_meta_entry() {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = setup(&env, "drop-basic");
+        let source = meta.repos[0].source.clone();
+        let branch = meta.repos[0].branch.clone();
+
+        let result = cmd_drop_repo(&forest_dir, &meta, "api", false, false).unwrap();
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repo.worktree_removed, RmOutcome::Success);
+        assert_eq!(result.repo.branch_deleted, RmOutcome::Success);
+        assert!(result.meta_updated);
+        assert!(!forest_dir.join("api").exists());
+        assert!(forest_dir.join("web").exists());
+        assert!(!crate::git::ref_exists(&source, &format!("refs/heads/{}", branch)).unwrap());
+
+        let names: Vec<String> = read_meta(&forest_dir)
+            .repos
+            .iter()
+            .map(|r| r.name.to_string())
+            .collect();
+        assert_eq!(names, ["web"]);
+    }
+
+    #[test]
+    fn drop_dry_run_changes_nothing() {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = setup(&env, "drop-dry");
+
+        let result = cmd_drop_repo(&forest_dir, &meta, "api", false, true).unwrap();
+
+        assert!(result.dry_run);
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repo.worktree_removed, RmOutcome::Success);
+        assert_eq!(result.repo.branch_deleted, RmOutcome::Success);
+        assert!(result.meta_updated);
+        assert!(forest_dir.join("api").exists());
+        assert_eq!(read_meta(&forest_dir).repos.len(), 2);
+    }
+
+    #[test]
+    fn drop_dirty_repo_is_blocked_without_force() {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = setup(&env, "drop-dirty");
+        std::fs::write(forest_dir.join("api").join("scratch.txt"), "wip").unwrap();
+
+        let result = cmd_drop_repo(&forest_dir, &meta, "api", false, false).unwrap();
+
+        assert!(matches!(
+            result.repo.worktree_removed,
+            RmOutcome::Failed { .. }
+        ));
+        assert!(!result.meta_updated);
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.contains("git forest drop-repo --force")));
+        assert!(forest_dir.join("api").join("scratch.txt").exists());
+        assert_eq!(read_meta(&forest_dir).repos.len(), 2);
+    }
+
+    #[test]
+    fn drop_dirty_repo_with_force() {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = setup(&env, "drop-force");
+        std::fs::write(forest_dir.join("api").join("scratch.txt"), "wip").unwrap();
+
+        let result = cmd_drop_repo(&forest_dir, &meta, "api", true, false).unwrap();
+
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert!(!forest_dir.join("api").exists());
+        assert_eq!(read_meta(&forest_dir).repos.len(), 1);
+    }
+
+    #[test]
+    fn drop_unmerged_branch_keeps_meta_entry() {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = setup(&env, "drop-unmerged");
+        let wt_dir = forest_dir.join("api");
+        crate::git::git(&wt_dir, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(&wt_dir, &["config", "user.email", "test@test.com"]).unwrap();
+        crate::git::git(&wt_dir, &["commit", "--allow-empty", "-m", "unmerged"]).unwrap();
+
+        let dry = cmd_drop_repo(&forest_dir, &meta, "api", false, true).unwrap();
+        assert!(matches!(dry.repo.branch_deleted, RmOutcome::Failed { .. }));
+        assert!(!dry.meta_updated);
+
+        let result = cmd_drop_repo(&forest_dir, &meta, "api", false, false).unwrap();
+
+        assert_eq!(result.repo.worktree_removed, RmOutcome::Success);
+        assert!(matches!(
+            result.repo.branch_deleted,
+            RmOutcome::Failed { .. }
+        ));
+        assert!(!result.meta_updated);
+        assert_eq!(read_meta(&forest_dir).repos.len(), 2);
+    }
+
+    #[test]
+    fn drop_unknown_repo_errors() {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = setup(&env, "drop-unknown");
+
+        let err = cmd_drop_repo(&forest_dir, &meta, "nope", false, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not in forest"), "error: {}", err);
+        assert!(err.contains("api, web"), "error: {}", err);
+    }
+}
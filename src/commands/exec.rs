@@ -1,57 +1,251 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, ensure, Result};
 use serde::Serialize;
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::Path;
+use std::process::Stdio;
+use std::time::Instant;
 
+use super::repo_filter::RepoFilter;
 use crate::meta::ForestMeta;
-use crate::paths::ForestName;
+use crate::paths::{ForestName, RepoName};
+
+/// Where each repo's child output goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecOutput {
+    /// Child inherits the terminal; repos are introduced by a `=== repo ===` header.
+    Inherit,
+    /// Captured and echoed live, each line prefixed with the repo name.
+    Prefixed,
+    /// Captured silently into the result (for `--json`).
+    Captured,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecOptions {
+    /// Maximum number of repos running the command at once.
+    pub jobs: usize,
+    pub output: ExecOutput,
+    pub filter: RepoFilter,
+}
+
+impl Default for ExecOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 1,
+            output: ExecOutput::Inherit,
+            filter: RepoFilter::default(),
+        }
+    }
+}
 
 #[derive(Debug, Serialize)]
 pub struct ExecResult {
     pub forest_name: ForestName,
     pub failures: Vec<String>,
+    pub repos: Vec<ExecRepoResult>,
 }
 
-pub fn cmd_exec(forest_dir: &Path, meta: &ForestMeta, cmd: &[String]) -> Result<ExecResult> {
+#[derive(Debug, Serialize)]
+pub struct ExecRepoResult {
+    pub name: RepoName,
+    pub success: bool,
+    /// `None` when the command could not be started or was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Captured output; empty when output was inherited by the terminal.
+    pub stdout: String,
+    pub stderr: String,
+    /// Why the command could not be run at all (e.g. missing worktree).
+    pub error: Option<String>,
+}
+
+pub fn cmd_exec(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    cmd: &[String],
+    options: &ExecOptions,
+) -> Result<ExecResult> {
     debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
     if cmd.is_empty() {
         bail!("no command specified");
     }
+    ensure!(
+        options.jobs > 0,
+        "--parallel must be at least 1\n  hint: omit --parallel to run repos one at a time"
+    );
+
+    let selected = options.filter.select(forest_dir, meta)?;
+    let prefix_width = selected
+        .iter()
+        .map(|r| r.name.as_str().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut repos: Vec<Option<ExecRepoResult>> = selected.iter().map(|_| None).collect();
+    crate::parallel::run_bounded(
+        &selected,
+        options.jobs,
+        |repo| {
+            let worktree = forest_dir.join(repo.name.as_str());
+            let env = meta.repo_env(forest_dir, repo);
+            run_in_repo(
+                cmd,
+                &worktree,
+                &env,
+                &repo.name,
+                options.output,
+                prefix_width,
+            )
+        },
+        |index, result| {
+            repos[index] = Some(result);
+            true
+        },
+    );
+
+    let repos: Vec<ExecRepoResult> = repos.into_iter().flatten().collect();
+    let failures = repos
+        .iter()
+        .filter(|r| !r.success)
+        .map(|r| r.name.to_string())
+        .collect();
+
+    Ok(ExecResult {
+        forest_name: meta.name.clone(),
+        failures,
+        repos,
+    })
+}
 
-    let mut failures = Vec::new();
+fn run_in_repo(
+    cmd: &[String],
+    worktree: &Path,
+    env: &[(String, String)],
+    name: &RepoName,
+    output: ExecOutput,
+    prefix_width: usize,
+) -> ExecRepoResult {
+    let start = Instant::now();
+    let prefix = format!("{:<width$} | ", name, width = prefix_width);
 
-    for repo in &meta.repos {
-        let worktree = forest_dir.join(repo.name.as_str());
-        eprintln!("=== {} ===", repo.name);
+    let failed = |error: String| ExecRepoResult {
+        name: name.clone(),
+        success: false,
+        exit_code: None,
+        duration_ms: start.elapsed().as_millis() as u64,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: Some(error),
+    };
 
-        if !worktree.exists() {
-            eprintln!("  warning: worktree missing at {}", worktree.display());
-            failures.push(repo.name.to_string());
-            continue;
+    if output == ExecOutput::Inherit {
+        eprintln!("=== {} ===", name);
+    }
+
+    if !worktree.exists() {
+        let error = format!("worktree missing at {}", worktree.display());
+        match output {
+            ExecOutput::Inherit => eprintln!("  warning: {}", error),
+            ExecOutput::Prefixed => eprintln!("{}warning: {}", prefix, error),
+            ExecOutput::Captured => {}
         }
+        return failed(error);
+    }
 
-        let status = std::process::Command::new(&cmd[0])
-            .args(&cmd[1..])
-            .current_dir(&worktree)
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .status();
+    let mut command = std::process::Command::new(&cmd[0]);
+    command
+        .args(&cmd[1..])
+        .current_dir(worktree)
+        .envs(env.iter().map(|(k, v)| (k, v)));
 
-        match status {
-            Ok(s) if !s.success() => {
-                failures.push(repo.name.to_string());
-            }
+    if output == ExecOutput::Inherit {
+        return match command
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+        {
+            Ok(status) => ExecRepoResult {
+                name: name.clone(),
+                success: status.success(),
+                exit_code: status.code(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                stdout: String::new(),
+                stderr: String::new(),
+                error: None,
+            },
             Err(e) => {
                 eprintln!("  error: {}", e);
-                failures.push(repo.name.to_string());
+                failed(e.to_string())
             }
-            _ => {}
+        };
+    }
+
+    let mut child = match command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            if output == ExecOutput::Prefixed {
+                eprintln!("{}error: {}", prefix, e);
+            }
+            return failed(e.to_string());
         }
+    };
+
+    let echo = (output == ExecOutput::Prefixed).then_some(prefix.as_str());
+    let child_stdout = child.stdout.take().expect("stdout is piped");
+    let child_stderr = child.stderr.take().expect("stderr is piped");
+    let (stdout, stderr) = std::thread::scope(|scope| {
+        let err = scope.spawn(|| collect_lines(child_stderr, echo, true));
+        let out = collect_lines(child_stdout, echo, false);
+        (out, err.join().expect("stderr reader panicked"))
+    });
+
+    match child.wait() {
+        Ok(status) => ExecRepoResult {
+            name: name.clone(),
+            success: status.success(),
+            exit_code: status.code(),
+            duration_ms: start.elapsed().as_millis() as u64,
+            stdout,
+            stderr,
+            error: None,
+        },
+        Err(e) => ExecRepoResult {
+            stdout,
+            stderr,
+            ..failed(e.to_string())
+        },
     }
+}
 
-    Ok(ExecResult {
-        forest_name: meta.name.clone(),
-        failures,
-    })
+/// Read `reader` to the end, echoing each line behind `echo` as it arrives.
+/// Lines are written whole so concurrent repos interleave by line, not by byte.
+fn collect_lines(reader: impl Read, echo: Option<&str>, to_stderr: bool) -> String {
+    let mut collected = String::new();
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let line = String::from_utf8_lossy(&buf);
+        if let Some(prefix) = echo {
+            let text = format!("{}{}\n", prefix, line.trim_end_matches(['\n', '\r']));
+            let _ = if to_stderr {
+                std::io::stderr().lock().write_all(text.as_bytes())
+            } else {
+                std::io::stdout().lock().write_all(text.as_bytes())
+            };
+        }
+        collected.push_str(&line);
+    }
+    collected
 }
 
 pub fn format_exec_human(result: &ExecResult) -> String {
@@ -73,7 +267,7 @@ mod tests {
         let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
 
         let cmd = vec!["echo".to_string(), "hello".to_string()];
-        let result = cmd_exec(&forest_dir, &meta, &cmd).unwrap();
+        let result = cmd_exec(&forest_dir, &meta, &cmd, &ExecOptions::default()).unwrap();
         assert!(result.failures.is_empty());
     }
 
@@ -82,7 +276,107 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
 
-        let result = cmd_exec(&forest_dir, &meta, &[]);
+        let result = cmd_exec(&forest_dir, &meta, &[], &ExecOptions::default());
         assert!(result.is_err());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn cmd_exec_captured_reports_per_repo_output_in_meta_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+
+        // The first repo fails; every repo reports its own output
+        let script = format!(
+            "basename \"$PWD\"; echo oops >&2; [ \"$(basename \"$PWD\")\" != {} ]",
+            meta.repos[0].name
+        );
+        let cmd = vec!["sh".to_string(), "-c".to_string(), script];
+        let options = ExecOptions {
+            jobs: 4,
+            output: ExecOutput::Captured,
+            ..ExecOptions::default()
+        };
+        let result = cmd_exec(&forest_dir, &meta, &cmd, &options).unwrap();
+
+        assert_eq!(result.repos.len(), meta.repos.len());
+        for (repo, meta_repo) in result.repos.iter().zip(&meta.repos) {
+            assert_eq!(repo.name, meta_repo.name);
+            assert_eq!(repo.stdout, format!("{}\n", meta_repo.name));
+            assert_eq!(repo.stderr, "oops\n");
+        }
+        assert!(!result.repos[0].success);
+        assert_eq!(result.repos[0].exit_code, Some(1));
+        assert!(result.repos[1].success);
+        assert_eq!(result.failures, vec![meta.repos[0].name.to_string()]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cmd_exec_sets_forest_env_vars() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, mut meta) = setup_forest_with_git_repos(tmp.path());
+        meta.env
+            .insert("COMPOSE_PROJECT_NAME".to_string(), "demo".to_string());
+
+        let script = "echo $FOREST_NAME $FOREST_REPO $FOREST_REPO_BRANCH $FOREST_MODE $COMPOSE_PROJECT_NAME; echo $FOREST_DIR";
+        let cmd = vec!["sh".to_string(), "-c".to_string(), script.to_string()];
+        let options = ExecOptions {
+            output: ExecOutput::Captured,
+            ..ExecOptions::default()
+        };
+        let result = cmd_exec(&forest_dir, &meta, &cmd, &options).unwrap();
+
+        let repo = &meta.repos[0];
+        assert_eq!(
+            result.repos[0].stdout,
+            format!(
+                "{} {} {} {} demo\n{}\n",
+                meta.name,
+                repo.name,
+                repo.branch,
+                meta.mode,
+                forest_dir.display()
+            )
+        );
+    }
+
+    #[test]
+    fn cmd_exec_missing_worktree_is_a_failure() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+        std::fs::remove_dir_all(forest_dir.join(meta.repos[0].name.as_str())).unwrap();
+
+        let cmd = vec!["git".to_string(), "status".to_string()];
+        let options = ExecOptions {
+            jobs: 2,
+            output: ExecOutput::Captured,
+            ..ExecOptions::default()
+        };
+        let result = cmd_exec(&forest_dir, &meta, &cmd, &options).unwrap();
+
+        assert!(!result.repos[0].success);
+        assert_eq!(result.repos[0].exit_code, None);
+        assert!(result.repos[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("worktree missing"));
+        assert!(result.repos[1].success);
+    }
+
+    #[test]
+    fn cmd_exec_rejects_zero_jobs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+
+        let cmd = vec!["echo".to_string()];
+        let options = ExecOptions {
+            jobs: 0,
+            output: ExecOutput::Prefixed,
+            ..ExecOptions::default()
+        };
+        let err = cmd_exec(&forest_dir, &meta, &cmd, &options).unwrap_err();
+        assert!(err.to_string().contains("--parallel must be at least 1"));
+    }
 }
@@ -0,0 +1,248 @@
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use super::branch_state::{path_exists_or_symlink, short_commit, WorktreeBranchState};
+use super::drop_repo::drop_repo_command;
+use crate::manifest::{ForestManifest, ManifestRepo};
+use crate::meta::{ForestMeta, RepoMeta};
+use crate::paths::ForestName;
+
+#[derive(Debug, Serialize)]
+pub struct ExportResult {
+    pub forest_name: ForestName,
+    pub manifest: ForestManifest,
+    /// Where the manifest was written; `None` when printed to stdout.
+    pub output_path: Option<String>,
+    /// State the manifest cannot carry, such as uncommitted or unpushed work.
+    pub warnings: Vec<String>,
+    #[serde(skip)]
+    pub manifest_toml: String,
+}
+
+pub fn cmd_export(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    output_path: Option<&Path>,
+) -> Result<ExportResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let mut repos = Vec::new();
+    let mut warnings = Vec::new();
+    for repo in &meta.repos {
+        repos.push(export_repo(forest_dir, meta, repo, &mut warnings)?);
+    }
+
+    let manifest = ForestManifest {
+        name: meta.name.clone(),
+        mode: meta.mode.clone(),
+        repos,
+    };
+    let manifest_toml = manifest.to_toml()?;
+    if let Some(path) = output_path {
+        std::fs::write(path, &manifest_toml)
+            .with_context(|| format!("failed to write manifest to {}", path.display()))?;
+    }
+
+    Ok(ExportResult {
+        forest_name: meta.name.clone(),
+        manifest,
+        output_path: output_path.map(|p| p.display().to_string()),
+        warnings,
+        manifest_toml,
+    })
+}
+
+fn export_repo(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    repo: &RepoMeta,
+    warnings: &mut Vec<String>,
+) -> Result<ManifestRepo> {
+    let worktree = forest_dir.join(repo.name.as_str());
+    if !path_exists_or_symlink(&worktree) {
+        bail!(
+            "cannot export {}: worktree missing at {}\n  hint: drop it with `{}` or restore the worktree",
+            repo.name,
+            worktree.display(),
+            drop_repo_command(meta.name.as_str(), repo.name.as_str())
+        );
+    }
+    let branch_state = WorktreeBranchState::read(&worktree, &repo.branch);
+    if let Some(message) = branch_state
+        .drift_message()
+        .or_else(|| branch_state.lookup_error_message())
+    {
+        bail!(
+            "cannot export {}: {}\n  hint: check out {} in {} so the manifest records the right branch",
+            repo.name,
+            message,
+            repo.branch,
+            worktree.display()
+        );
+    }
+
+    let commit = crate::git::git(&worktree, &["rev-parse", "HEAD"])
+        .with_context(|| format!("failed to read the checked-out commit of {}", repo.name))?;
+    let remote_url = repo
+        .remote
+        .as_deref()
+        .and_then(|remote| crate::git::git(&worktree, &["remote", "get-url", remote]).ok());
+
+    if crate::git::git(
+        &worktree,
+        &["status", "--porcelain", "--untracked-files=no"],
+    )
+    .is_ok_and(|out| !out.is_empty())
+    {
+        warnings.push(format!(
+            "{}: uncommitted changes are not included in the manifest",
+            repo.name
+        ));
+    }
+    if let Some(remote) = &repo.remote {
+        let on_remote = crate::git::git(
+            &worktree,
+            &[
+                "branch",
+                "--remotes",
+                "--contains",
+                &commit,
+                "--list",
+                &format!("{}/*", remote),
+            ],
+        )
+        .is_ok_and(|out| !out.is_empty());
+        if !on_remote {
+            warnings.push(format!(
+                "{}: commit {} is not on {}; push it so others can check it out",
+                repo.name,
+                short_commit(&commit),
+                remote
+            ));
+        }
+    }
+
+    Ok(ManifestRepo {
+        name: repo.name.clone(),
+        remote_url,
+        branch: repo.branch.clone(),
+        base_branch: repo.base_branch.clone(),
+        commit,
+    })
+}
+
+pub fn format_export_human(result: &ExportResult) -> String {
+    match &result.output_path {
+        Some(path) => format!(
+            "Exported forest {:?} ({} repos) to {}",
+            result.forest_name.as_str(),
+            result.manifest.repos.len(),
+            path
+        ),
+        None => result.manifest_toml.trim_end().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::ForestMode;
+    use crate::testutil::{new_forest, TestEnv};
+    use std::path::PathBuf;
+
+    fn setup_forest(name: &str) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = new_forest(
+            &env,
+            name,
+            ForestMode::Feature,
+            &["foo-api", "foo-web"],
+            &[],
+        );
+        (env, forest_dir, meta)
+    }
+
+    #[test]
+    fn export_records_checked_out_commits_and_warns_about_local_state() {
+        let (env, forest_dir, meta) = setup_forest("export-me");
+        let api = forest_dir.join("foo-api");
+        for args in [
+            &["config", "user.name", "Test"][..],
+            &["config", "user.email", "test@test.com"],
+            &["commit", "--allow-empty", "-m", "unpushed"],
+        ] {
+            crate::git::git(&api, args).unwrap();
+        }
+        std::fs::write(forest_dir.join("foo-web").join("README"), "edit").unwrap();
+        crate::git::git(&forest_dir.join("foo-web"), &["add", "README"]).unwrap();
+
+        let path = env.root().join("export-me.toml");
+        let result = cmd_export(&forest_dir, &meta, Some(&path)).unwrap();
+
+        let manifest = ForestManifest::read(&path).unwrap();
+        assert_eq!(manifest, result.manifest);
+        assert_eq!(manifest.name.as_str(), "export-me");
+        let api_repo = &manifest.repos[0];
+        assert_eq!(api_repo.branch, "testuser/export-me");
+        assert_eq!(api_repo.base_branch, "main");
+        assert_eq!(
+            api_repo.commit,
+            crate::git::git(&api, &["rev-parse", "HEAD"]).unwrap()
+        );
+        assert_eq!(
+            api_repo.remote_url.as_deref(),
+            env.root().join("bare").join("foo-api.git").to_str()
+        );
+
+        assert_eq!(result.warnings.len(), 2, "warnings: {:?}", result.warnings);
+        assert!(result.warnings[0].starts_with("foo-api: commit "));
+        assert!(result.warnings[0].contains("is not on origin"));
+        assert!(result.warnings[1].starts_with("foo-web: uncommitted changes"));
+        assert!(format_export_human(&result).contains("(2 repos) to "));
+    }
+
+    #[test]
+    fn export_without_output_path_renders_toml() {
+        let (_env, forest_dir, meta) = setup_forest("export-stdout");
+
+        let result = cmd_export(&forest_dir, &meta, None).unwrap();
+        assert!(
+            result.warnings.is_empty(),
+            "warnings: {:?}",
+            result.warnings
+        );
+        let human = format_export_human(&result);
+        assert!(human.starts_with("name = \"export-stdout\""), "{}", human);
+        assert!(human.contains("[[repos]]"));
+    }
+
+    #[test]
+    fn export_refuses_drifted_worktree() {
+        let (_env, forest_dir, meta) = setup_forest("export-drift");
+        crate::git::git(&forest_dir.join("foo-web"), &["checkout", "--detach"]).unwrap();
+
+        let err = cmd_export(&forest_dir, &meta, None)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("cannot export foo-web: branch drift"),
+            "error: {}",
+            err
+        );
+    }
+    #[test]
+    fn export_missing_worktree_hints_drop_repo_for_this_forest() {
+        let (_env, forest_dir, meta) = setup_forest("export-missing");
+        std::fs::remove_dir_all(forest_dir.join("foo-web")).unwrap();
+
+        let err = cmd_export(&forest_dir, &meta, None)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("`git forest drop-repo export-missing --repo foo-web`"),
+            "error: {}",
+            err
+        );
+    }
+}
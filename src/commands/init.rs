@@ -3,25 +3,35 @@ use serde::Serialize;
 use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::config::{ResolvedConfig, ResolvedRepo, ResolvedTemplate};
+use crate::config::{
+    is_repo_url, managed_clone_path, url_repo_name, MultiTemplateConfig, ResolvedRepo,
+    ResolvedTemplate, TemplateConfig,
+};
 use crate::meta::META_FILENAME;
 use crate::paths::{
     expand_tilde, validate_disposable_root_entries, AbsolutePath, DisposableRootEntry, RepoName,
 };
 
+/// Template base branch when neither `--base-branch` nor any repo's remote HEAD says.
+const DEFAULT_BASE_BRANCH: &str = "dev";
+
 pub struct InitInputs {
     pub template_name: String,
     pub worktree_base: String,
-    pub base_branch: String,
+    /// Template default; `None` detects it from the repos' remote HEADs.
+    pub base_branch: Option<String>,
     pub feature_branch_template: String,
     pub disposable_root_entries: Vec<DisposableRootEntry>,
     pub repos: Vec<RepoInput>,
 }
 
 pub struct RepoInput {
+    /// Local path, or a url cloned into the managed repos dir when missing.
     pub path: String,
     pub name: Option<String>,
     pub base_branch: Option<String>,
+    /// `None` detects the remote from the repo's upstream.
+    pub remote: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -38,6 +48,9 @@ pub struct InitRepoSummary {
     pub name: String,
     pub path: AbsolutePath,
     pub base_branch: String,
+    pub remote: String,
+    /// The `url` the repo was cloned from in this run.
+    pub cloned_from: Option<String>,
 }
 
 pub fn validate_init_inputs(inputs: &InitInputs) -> Result<ResolvedTemplate> {
@@ -58,43 +71,29 @@ pub fn validate_init_inputs(inputs: &InitInputs) -> Result<ResolvedTemplate> {
 
     let worktree_base = expand_tilde(&inputs.worktree_base)?;
 
-    let mut resolved_repos = Vec::new();
+    let mut detected = Vec::new();
     let mut names = HashSet::new();
 
     for repo_input in &inputs.repos {
-        let path = expand_tilde(&repo_input.path)?;
-
-        ensure!(
-            path.exists(),
-            "repo path does not exist: {}\n  hint: provide an absolute path to a git repository",
-            path.display()
-        );
-
-        // Verify it's a git repo
-        let git_check = std::process::Command::new("git")
-            .args(["rev-parse", "--git-dir"])
-            .current_dir(&path)
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status();
-
-        match git_check {
-            Ok(s) if !s.success() => {
-                bail!(
-                    "not a git repository: {}\n  hint: provide a path to a git repository",
-                    path.display()
-                );
+        let (path, url) = if is_repo_url(&repo_input.path) {
+            let url = repo_input.path.clone();
+            let path = managed_clone_path(&url)?;
+            if !path.exists() {
+                let remote = repo_input.remote.as_deref().unwrap_or("origin");
+                super::new::clone_source(&url, &path, remote)?;
             }
-            Err(e) => {
-                bail!("failed to check git repo at {}: {}", path.display(), e);
-            }
-            _ => {}
-        }
+            (path, Some(url))
+        } else {
+            (expand_tilde(&repo_input.path)?, None)
+        };
+        validate_repo_path(&path)?;
 
-        let name_str = repo_input.name.clone().unwrap_or_else(|| {
-            path.file_name()
+        let name_str = repo_input.name.clone().unwrap_or_else(|| match &url {
+            Some(url) => url_repo_name(url),
+            None => path
+                .file_name()
                 .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_default()
+                .unwrap_or_default(),
         });
 
         let name = RepoName::new(name_str)
@@ -106,19 +105,51 @@ pub fn validate_init_inputs(inputs: &InitInputs) -> Result<ResolvedTemplate> {
             name
         );
 
-        let base_branch = repo_input
-            .base_branch
-            .clone()
-            .unwrap_or_else(|| inputs.base_branch.clone());
-
-        resolved_repos.push(ResolvedRepo {
-            path,
-            name,
-            base_branch,
-            remote: "origin".to_string(),
-        });
+        let remote = match &repo_input.remote {
+            Some(remote) => {
+                ensure!(
+                    crate::git::git(&path, &["remote", "get-url", remote]).is_ok(),
+                    "remote {:?} not found in {}\n  hint: list remotes with `git -C {} remote`",
+                    remote,
+                    path.display(),
+                    path.display()
+                );
+                remote.clone()
+            }
+            None => detect_remote(&path).unwrap_or_else(|| "origin".to_string()),
+        };
+        let detected_base = detect_base_branch(&path, &remote);
+        detected.push((repo_input, path, url, name, remote, detected_base));
     }
 
+    // Without --base-branch, the template default is the branch most repos
+    // detected, so repos that fail detection inherit what their siblings use.
+    let base_branch = inputs
+        .base_branch
+        .clone()
+        .or_else(|| most_common(detected.iter().filter_map(|d| d.5.as_deref())))
+        .unwrap_or_else(|| DEFAULT_BASE_BRANCH.to_string());
+
+    let resolved_repos: Vec<ResolvedRepo> = detected
+        .into_iter()
+        .map(
+            |(repo_input, path, url, name, remote, detected_base)| ResolvedRepo {
+                path,
+                url,
+                name,
+                base_branch: repo_input
+                    .base_branch
+                    .clone()
+                    .or_else(|| inputs.base_branch.clone())
+                    .or(detected_base)
+                    .unwrap_or_else(|| base_branch.clone()),
+                remote,
+                post_create: None,
+                pre_remove: None,
+            },
+        )
+        .collect();
+
     validate_disposable_root_entries(
         &inputs.disposable_root_entries,
         std::iter::once(META_FILENAME).chain(resolved_repos.iter().map(|repo| repo.name.as_str())),
@@ -126,29 +157,126 @@ pub fn validate_init_inputs(inputs: &InitInputs) -> Result<ResolvedTemplate> {
 
     Ok(ResolvedTemplate {
         worktree_base,
-        base_branch: inputs.base_branch.clone(),
+        base_branch,
         feature_branch_template: inputs.feature_branch_template.clone(),
         disposable_root_entries: inputs.disposable_root_entries.clone(),
+        post_create: None,
+        pre_remove: None,
+        env: BTreeMap::new(),
         repos: resolved_repos,
     })
 }
 
+/// Remote of the checked-out branch's upstream, else `origin` when it exists,
+/// else the repo's only remote.
+pub(super) fn detect_remote(path: &Path) -> Option<String> {
+    let upstream = crate::git::git(path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .ok()
+        .and_then(|branch| {
+            crate::git::git(path, &["config", &format!("branch.{}.remote", branch)]).ok()
+        })
+        .filter(|remote| !remote.is_empty() && remote != ".");
+    if upstream.is_some() {
+        return upstream;
+    }
+
+    let output = crate::git::git(path, &["remote"]).ok()?;
+    let remotes: Vec<&str> = output.lines().collect();
+    if remotes.contains(&"origin") {
+        return Some("origin".to_string());
+    }
+    match remotes.as_slice() {
+        [only] => Some(only.to_string()),
+        _ => None,
+    }
+}
+
+/// Branch `refs/remotes/<remote>/HEAD` points at, as set by `git clone` or
+/// `git remote set-head <remote> --auto`.
+fn detect_base_branch(path: &Path, remote: &str) -> Option<String> {
+    let head = format!("refs/remotes/{}/HEAD", remote);
+    let target = crate::git::git(path, &["symbolic-ref", "--quiet", "--short", &head]).ok()?;
+    target
+        .strip_prefix(&format!("{}/", remote))
+        .filter(|branch| !branch.is_empty())
+        .map(str::to_string)
+}
+
+/// Most frequent item; ties go to the one seen first.
+fn most_common<'a>(items: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for item in items {
+        match counts.iter_mut().find(|(seen, _)| *seen == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+    let max = counts.iter().map(|(_, count)| *count).max()?;
+    counts
+        .into_iter()
+        .find(|(_, count)| *count == max)
+        .map(|(item, _)| item.to_string())
+}
+
+/// Check that a configured repo path exists and is a git repository.
+pub(super) fn validate_repo_path(path: &Path) -> Result<()> {
+    ensure!(
+        path.exists(),
+        "repo path does not exist: {}\n  hint: provide an absolute path to a git repository",
+        path.display()
+    );
+
+    let git_check = std::process::Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .current_dir(path)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+
+    match git_check {
+        Ok(s) if !s.success() => {
+            bail!(
+                "not a git repository: {}\n  hint: provide a path to a git repository",
+                path.display()
+            );
+        }
+        Err(e) => {
+            bail!("failed to check git repo at {}: {}", path.display(), e);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 pub fn cmd_init(inputs: InitInputs, config_path: &Path, force: bool) -> Result<InitResult> {
+    // URL repos without a clone yet; validation clones them
+    let uncloned: Vec<AbsolutePath> = inputs
+        .repos
+        .iter()
+        .filter(|r| is_repo_url(&r.path))
+        .filter_map(|r| managed_clone_path(&r.path).ok())
+        .filter(|path| !path.exists())
+        .collect();
     let template = validate_init_inputs(&inputs)?;
     let template_name = inputs.template_name.clone();
 
+    // Edit the raw config so other templates keep their `extends` and gaps
     let mut config = if config_path.exists() {
-        crate::config::load_config(config_path)?
+        let raw = crate::config::load_raw_config(config_path)?;
+        crate::config::resolve_from_cwd(&raw, config_path)?;
+        raw
     } else {
-        ResolvedConfig {
+        MultiTemplateConfig {
             default_template: template_name.clone(),
-            templates: BTreeMap::new(),
+            template: BTreeMap::new(),
+            project: BTreeMap::new(),
+            trusted_projects: BTreeMap::new(),
             version_check: None,
         }
     };
 
     // Only require --force when overwriting an existing template
-    if config.templates.contains_key(&template_name) && !force {
+    if config.template.contains_key(&template_name) && !force {
         bail!(
             "template {:?} already exists in config\n  hint: use --force to overwrite, or choose a different name",
             template_name
@@ -164,11 +292,17 @@ pub fn cmd_init(inputs: InitInputs, config_path: &Path, force: bool) -> Result<I
             name: r.name.to_string(),
             path: r.path.clone(),
             base_branch: r.base_branch.clone(),
+            remote: r.remote.clone(),
+            cloned_from: r.url.clone().filter(|_| uncloned.contains(&r.path)),
         })
         .collect();
 
-    config.templates.insert(template_name.clone(), template);
-    crate::config::write_config_atomic(config_path, &config)?;
+    config
+        .template
+        .insert(template_name.clone(), TemplateConfig::from(&template));
+    // Templates extending a replaced one must still resolve
+    crate::config::resolve_from_cwd(&config, config_path)?;
+    crate::config::write_raw_config_atomic(config_path, &config)?;
 
     Ok(InitResult {
         config_path: config_path.to_path_buf(),
@@ -201,11 +335,15 @@ pub fn format_init_human(result: &InitResult) -> String {
     lines.push(format!("Repos ({}): ", result.repos.len()));
     for repo in &result.repos {
         lines.push(format!(
-            "  {} ({}, base: {})",
+            "  {} ({}, base: {}, remote: {})",
             repo.name,
             repo.path.display(),
-            repo.base_branch
+            repo.base_branch,
+            repo.remote
         ));
+        if let Some(url) = &repo.cloned_from {
+            lines.push(format!("    cloned from {}", url));
+        }
     }
     lines.join("\n")
 }
@@ -237,7 +375,7 @@ mod tests {
         InitInputs {
             template_name: "default".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos,
@@ -253,6 +391,7 @@ mod tests {
             path: repo.display().to_string(),
             name: None,
             base_branch: None,
+            remote: None,
         }]);
 
         let template = validate_init_inputs(&inputs).unwrap();
@@ -269,6 +408,7 @@ mod tests {
             path: repo.display().to_string(),
             name: None,
             base_branch: None,
+            remote: None,
         }]);
         inputs.disposable_root_entries = vec![
             DisposableRootEntry::new(".idea".to_string()).unwrap(),
@@ -291,6 +431,7 @@ mod tests {
             path: repo.display().to_string(),
             name: None,
             base_branch: None,
+            remote: None,
         }]);
         inputs.disposable_root_entries =
             vec![DisposableRootEntry::new("repo-a".to_string()).unwrap()];
@@ -322,11 +463,13 @@ mod tests {
                 path: repo_a.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             },
             RepoInput {
                 path: repo_b.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             },
         ]);
 
@@ -345,6 +488,7 @@ mod tests {
             path: not_git.display().to_string(),
             name: None,
             base_branch: None,
+            remote: None,
         }]);
 
         let result = validate_init_inputs(&inputs);
@@ -360,13 +504,14 @@ mod tests {
         let inputs = InitInputs {
             template_name: "default".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "user/feature".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
 
@@ -386,13 +531,14 @@ mod tests {
         let inputs = InitInputs {
             template_name: "default".to_string(),
             worktree_base: "~/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
 
@@ -414,6 +560,7 @@ mod tests {
             path: repo.display().to_string(),
             name: None,
             base_branch: None,
+            remote: None,
         }]);
 
         let result = cmd_init(inputs, &config_path, false).unwrap();
@@ -439,6 +586,7 @@ mod tests {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }])
         };
 
@@ -458,6 +606,7 @@ mod tests {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }])
         };
 
@@ -474,6 +623,7 @@ mod tests {
             path: "/nonexistent/repo/path".to_string(),
             name: None,
             base_branch: None,
+            remote: None,
         }]);
 
         let result = validate_init_inputs(&inputs);
@@ -494,13 +644,14 @@ mod tests {
         let inputs_a = InitInputs {
             template_name: "alpha".to_string(),
             worktree_base: "/tmp/worktrees/alpha".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_a.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_a, &config_path, false).unwrap();
@@ -509,13 +660,14 @@ mod tests {
         let inputs_b = InitInputs {
             template_name: "beta".to_string(),
             worktree_base: "/tmp/worktrees/beta".to_string(),
-            base_branch: "main".to_string(),
+            base_branch: Some("main".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_b.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_b, &config_path, false).unwrap();
@@ -536,13 +688,14 @@ mod tests {
         let inputs = InitInputs {
             template_name: "my-project".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs, &config_path, false).unwrap();
@@ -561,13 +714,14 @@ mod tests {
         let inputs_a = InitInputs {
             template_name: "first".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_a.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_a, &config_path, false).unwrap();
@@ -575,13 +729,14 @@ mod tests {
         let inputs_b = InitInputs {
             template_name: "second".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "main".to_string(),
+            base_branch: Some("main".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_b.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_b, &config_path, false).unwrap();
@@ -601,13 +756,14 @@ mod tests {
         let inputs_a = InitInputs {
             template_name: "alpha".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_a.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_a, &config_path, false).unwrap();
@@ -616,13 +772,14 @@ mod tests {
         let inputs_b = InitInputs {
             template_name: "beta".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "main".to_string(),
+            base_branch: Some("main".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_b.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_b, &config_path, false).unwrap();
@@ -631,13 +788,14 @@ mod tests {
         let inputs_replace = InitInputs {
             template_name: "alpha".to_string(),
             worktree_base: "/tmp/worktrees/new".to_string(),
-            base_branch: "main".to_string(),
+            base_branch: Some("main".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo_b.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
         cmd_init(inputs_replace, &config_path, true).unwrap();
@@ -660,13 +818,14 @@ mod tests {
         let inputs = InitInputs {
             template_name: "my-project".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
 
@@ -682,13 +841,14 @@ mod tests {
         let inputs = InitInputs {
             template_name: "".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
 
@@ -705,13 +865,14 @@ mod tests {
         let inputs = InitInputs {
             template_name: " spaces ".to_string(),
             worktree_base: "/tmp/worktrees".to_string(),
-            base_branch: "dev".to_string(),
+            base_branch: Some("dev".to_string()),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
             repos: vec![RepoInput {
                 path: repo.display().to_string(),
                 name: None,
                 base_branch: None,
+                remote: None,
             }],
         };
 
@@ -719,4 +880,65 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("whitespace"));
     }
+
+    #[test]
+    fn validate_init_detects_base_branch_and_remote() {
+        let env = crate::testutil::TestEnv::new();
+        let api = env.create_repo_with_remote("foo-api");
+        crate::git::git(&api, &["branch", "trunk"]).unwrap();
+        crate::git::git(&api, &["push", "origin", "trunk"]).unwrap();
+        crate::git::git(&api, &["remote", "rename", "origin", "upstream"]).unwrap();
+        crate::git::git(&api, &["branch", "--set-upstream-to", "upstream/main"]).unwrap();
+        crate::git::git(&api, &["remote", "set-head", "upstream", "trunk"]).unwrap();
+        let web = env.create_repo("foo-web");
+
+        let mut inputs = make_init_inputs(
+            [&api, &web]
+                .iter()
+                .map(|path| RepoInput {
+                    path: path.display().to_string(),
+                    name: None,
+                    base_branch: None,
+                    remote: None,
+                })
+                .collect(),
+        );
+        inputs.base_branch = None;
+
+        let template = validate_init_inputs(&inputs).unwrap();
+        assert_eq!(template.base_branch, "trunk");
+        assert_eq!(template.repos[0].remote, "upstream");
+        assert_eq!(template.repos[0].base_branch, "trunk");
+        // No remote HEAD to read: inherits the branch its sibling detected
+        assert_eq!(template.repos[1].remote, "origin");
+        assert_eq!(template.repos[1].base_branch, "trunk");
+
+        inputs.base_branch = Some("dev".to_string());
+        let template = validate_init_inputs(&inputs).unwrap();
+        assert_eq!(template.repos[0].base_branch, "dev");
+    }
+
+    #[test]
+    fn validate_init_custom_name_and_remote() {
+        let env = crate::testutil::TestEnv::new();
+        let api = env.create_repo_with_remote("foo-api");
+
+        let mut inputs = make_init_inputs(vec![RepoInput {
+            path: api.display().to_string(),
+            name: Some("api".to_string()),
+            base_branch: None,
+            remote: Some("origin".to_string()),
+        }]);
+        let template = validate_init_inputs(&inputs).unwrap();
+        assert_eq!(template.repos[0].name.as_str(), "api");
+        assert_eq!(template.repos[0].remote, "origin");
+
+        inputs.repos[0].remote = Some("upstream".to_string());
+        let err = validate_init_inputs(&inputs).unwrap_err().to_string();
+        assert!(
+            err.contains("remote \"upstream\" not found"),
+            "error: {}",
+            err
+        );
+    }
 }
@@ -0,0 +1,194 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use super::branch_state::compact_git_error;
+use super::diff::{format_repo_sections, resolve_base, ChangeView, RepoSection};
+use crate::meta::{ForestMeta, RepoMeta};
+use crate::paths::{ForestName, RepoName};
+
+#[derive(Debug, Serialize)]
+pub struct LogResult {
+    pub forest_name: ForestName,
+    pub view: ChangeView,
+    pub repos: Vec<LogRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogRepoResult {
+    pub name: RepoName,
+    pub branch: String,
+    /// What the branch is compared against, e.g. `origin/main`.
+    pub base_ref: Option<String>,
+    /// Commits on the branch that are not on `base_ref`, newest first.
+    pub commits: Vec<LogCommit>,
+    /// `git log` output for the requested view.
+    pub output: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct LogCommit {
+    pub sha: String,
+    pub author: String,
+    pub author_email: String,
+    /// Author date, strict ISO 8601.
+    pub date: String,
+    pub subject: String,
+}
+
+/// Unit separator between fields; subjects can contain anything printable.
+const FIELD_SEP: char = '\x1f';
+
+pub fn cmd_log(forest_dir: &Path, meta: &ForestMeta, view: ChangeView) -> Result<LogResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let repos: Vec<LogRepoResult> = meta
+        .repos
+        .iter()
+        .map(|repo| log_repo(forest_dir, repo, view))
+        .collect();
+    let errors = repos
+        .iter()
+        .filter_map(|r| r.error.as_ref().map(|e| format!("{}: {}", r.name, e)))
+        .collect();
+
+    Ok(LogResult {
+        forest_name: meta.name.clone(),
+        view,
+        repos,
+        errors,
+    })
+}
+
+fn log_repo(forest_dir: &Path, repo: &RepoMeta, view: ChangeView) -> LogRepoResult {
+    let mut result = LogRepoResult {
+        name: repo.name.clone(),
+        branch: repo.branch.clone(),
+        base_ref: None,
+        commits: Vec::new(),
+        output: String::new(),
+        error: None,
+    };
+
+    let (worktree, base) = match resolve_base(forest_dir, repo) {
+        Ok(resolved) => resolved,
+        Err(error) => {
+            result.error = Some(error);
+            return result;
+        }
+    };
+    let range = format!("{}..{}", base, repo.branch);
+    result.base_ref = Some(base);
+
+    let log = |extra: &[&str]| {
+        let mut args = vec!["log", "--no-color"];
+        args.extend_from_slice(extra);
+        args.push(&range);
+        crate::git::git(&worktree, &args).map_err(|e| compact_git_error(&e.to_string()))
+    };
+    let mut human_args = vec!["--format=%h %s"];
+    human_args.extend_from_slice(view.git_args());
+    let outputs = log(&human_args).and_then(|output| {
+        let records = log(&["--format=%H%x1f%an%x1f%ae%x1f%aI%x1f%s"])?;
+        Ok((output, records))
+    });
+    match outputs {
+        Ok((output, records)) => {
+            result.output = output;
+            result.commits = parse_log_records(&records);
+        }
+        Err(error) => result.error = Some(error),
+    }
+    result
+}
+
+fn parse_log_records(records: &str) -> Vec<LogCommit> {
+    records
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, FIELD_SEP);
+            Some(LogCommit {
+                sha: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                author_email: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn format_log_human(result: &LogResult) -> String {
+    let sections = result.repos.iter().map(|r| RepoSection {
+        name: &r.name,
+        branch: &r.branch,
+        base_ref: r.base_ref.as_deref(),
+        output: &r.output,
+        failed: r.error.is_some(),
+    });
+    format_repo_sections(sections, "no commits", &result.errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::setup_forest_with_git_repos;
+
+    #[test]
+    fn log_lists_commits_beyond_base_newest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, mut meta) = setup_forest_with_git_repos(tmp.path());
+        let api = forest_dir.join("api");
+        for args in [
+            &["checkout", "-b", "feature"][..],
+            &["config", "user.name", "Ada"],
+            &["config", "user.email", "ada@example.com"],
+            &["commit", "--allow-empty", "-m", "First change"],
+            &["commit", "--allow-empty", "-m", "Second change"],
+        ] {
+            crate::git::git(&api, args).unwrap();
+        }
+        meta.repos[0].branch = "feature".to_string();
+        for repo in &mut meta.repos {
+            repo.base_branch = "main".to_string();
+        }
+
+        let result = cmd_log(&forest_dir, &meta, ChangeView::Full).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        let repo = &result.repos[0];
+        // No remote recorded: falls back to the local base branch
+        assert_eq!(repo.base_ref.as_deref(), Some("main"));
+        let subjects: Vec<&str> = repo.commits.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Second change", "First change"]);
+        assert_eq!(repo.commits[0].author, "Ada");
+        assert_eq!(repo.commits[0].author_email, "ada@example.com");
+        assert_eq!(
+            repo.commits[0].sha,
+            crate::git::git(&api, &["rev-parse", "HEAD"]).unwrap()
+        );
+        assert!(result.repos[1].commits.is_empty());
+
+        let human = format_log_human(&result);
+        assert!(human.contains("=== api: feature vs main ==="));
+        assert!(human.contains(" Second change\n"));
+        assert!(human.contains("=== web: main vs main ===\n  (no commits)"));
+    }
+
+    #[test]
+    fn parse_log_records_keeps_separators_out_of_fields() {
+        let records =
+            "abc\u{1f}Ada\u{1f}ada@example.com\u{1f}2026-01-01T00:00:00+00:00\u{1f}Fix: a | b";
+        assert_eq!(
+            parse_log_records(records),
+            vec![LogCommit {
+                sha: "abc".to_string(),
+                author: "Ada".to_string(),
+                author_email: "ada@example.com".to_string(),
+                date: "2026-01-01T00:00:00+00:00".to_string(),
+                subject: "Fix: a | b".to_string(),
+            }]
+        );
+    }
+}
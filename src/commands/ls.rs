@@ -138,7 +138,7 @@ fn canonical_path_key(path: &Path) -> PathBuf {
     path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
 }
 
-fn is_dot_prefixed(path: &Path) -> bool {
+pub(super) fn is_dot_prefixed(path: &Path) -> bool {
     path.file_name()
         .is_some_and(|name| name.to_string_lossy().starts_with('.'))
 }
@@ -380,8 +380,12 @@ mod tests {
             mode: ForestMode::Feature,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         }
     }
 
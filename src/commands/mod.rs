@@ -1,19 +1,50 @@
 /// Result structs for command output. Commands return these instead of printing
 /// directly — main.rs formats them as human-readable or JSON based on --json.
 /// See docs/decisions/0002-functional-core-imperative-shell.md.
+mod add_repo;
+mod adopt;
 mod branch_state;
+mod commit;
+mod config;
+mod diff;
+mod doctor;
+mod drop_repo;
 mod exec;
+mod export;
 mod init;
+mod log;
 mod ls;
 mod new;
+mod outcome;
+mod pin;
+mod push;
+mod rename;
+mod repair_drift;
+mod repo_filter;
 mod reset;
 mod rm;
 mod status;
+mod sync;
 
+pub use add_repo::*;
+pub use adopt::*;
+pub use commit::*;
+pub use config::*;
+pub use diff::*;
+pub use doctor::*;
+pub use drop_repo::*;
 pub use exec::*;
+pub use export::*;
 pub use init::*;
+pub use log::*;
 pub use ls::*;
 pub use new::*;
+pub use pin::*;
+pub use push::*;
+pub use rename::*;
+pub use repair_drift::*;
+pub use repo_filter::*;
 pub use reset::*;
 pub use rm::*;
 pub use status::*;
+pub use sync::*;
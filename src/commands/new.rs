@@ -1,10 +1,14 @@
-use anyhow::{bail, ensure, Result};
+use anyhow::{bail, ensure, Context, Result};
 use chrono::Utc;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
 
-use crate::config::ResolvedTemplate;
+use super::branch_state::{base_ref, short_commit};
+use crate::config::{ResolvedRepo, ResolvedTemplate};
 use crate::git::ref_exists;
+use crate::hooks::{format_hook_summary, run_hook, HookResult};
+use crate::manifest::{normalize_remote_url, ForestManifest, ManifestRepo};
 use crate::meta::{ForestMeta, ForestMode, RepoMeta, META_FILENAME};
 use crate::paths::{
     forest_dir, AbsolutePath, BranchName, DisposableRootEntry, ForestName, RepoName,
@@ -15,8 +19,16 @@ pub struct NewInputs {
     pub mode: ForestMode,
     pub branch_override: Option<String>,
     pub repo_branches: Vec<(String, String)>,
+    /// Review mode: check out these pull/merge request heads, as (repo, number).
+    pub pull_requests: Vec<(String, u64)>,
     pub no_fetch: bool,
     pub dry_run: bool,
+    /// Maximum number of repos fetched or checked out concurrently.
+    pub jobs: usize,
+    /// Skip `post_create` hooks.
+    pub no_hooks: bool,
+    /// Run `post_create` hooks across repos concurrently (up to `jobs`).
+    pub parallel_hooks: bool,
 }
 
 #[derive(Debug)]
@@ -25,6 +37,7 @@ pub struct ForestPlan {
     pub forest_dir: AbsolutePath,
     pub mode: ForestMode,
     pub disposable_root_entries: Vec<DisposableRootEntry>,
+    pub env: BTreeMap<String, String>,
     pub repo_plans: Vec<RepoPlan>,
 }
 
@@ -37,6 +50,23 @@ pub struct RepoPlan {
     pub base_branch: String,
     pub remote: String,
     pub checkout: CheckoutKind,
+    /// Commit the branch must point at; set when reproducing a manifest or
+    /// checking out a pull request.
+    pub commit: Option<String>,
+    /// Pull/merge request number whose head the branch starts at.
+    pub pull_request: Option<u64>,
+    pub post_create: Option<String>,
+    pub pre_remove: Option<String>,
+    /// Set when the source repo is cloned from its `url` in this run (or
+    /// would be, on a dry run).
+    pub clone: Option<SourceClone>,
+}
+
+/// A source repo cloned from its configured `url`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceClone {
+    pub url: String,
+    pub path: AbsolutePath,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -48,6 +78,10 @@ pub enum CheckoutKind {
     TrackRemote,
     /// Branch doesn't exist. `git worktree add <dest> -b <branch> <remote>/<base_branch>`
     NewBranch,
+    /// Branch doesn't exist; start it at a manifest's commit. `git worktree add -b <branch> <dest> <commit>`
+    AtCommit,
+    /// Branch doesn't exist; start it at a fetched pull request head. `git worktree add -b <branch> <dest> <commit>`
+    PullRequest,
 }
 
 #[derive(Debug, Serialize)]
@@ -67,10 +101,16 @@ pub struct NewRepoResult {
     pub base_branch: String,
     pub branch_created: bool,
     pub checkout_kind: CheckoutKind,
+    pub pull_request: Option<u64>,
     pub worktree_path: AbsolutePath,
+    /// The clone made for the source repo in this run, or planned on a dry run.
+    pub clone: Option<SourceClone>,
+    pub post_create: Option<String>,
+    /// Set once the hook has run; absent for dry runs and `--no-hooks`.
+    pub post_create_result: Option<HookResult>,
 }
 
-fn compute_target_branch(
+pub(super) fn compute_target_branch(
     repo_name: &str,
     forest_name: &str,
     mode: &ForestMode,
@@ -95,49 +135,227 @@ fn compute_target_branch(
     }
 }
 
-pub fn plan_forest(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<ForestPlan> {
-    // Validate forest name
-    let forest_name = ForestName::new(inputs.name.clone())?;
-
-    // Validate template has repos
-    if tmpl.repos.is_empty() {
-        bail!("no repos configured\n  hint: run `git forest init --repo <path>` to add repos");
+/// Plan the worktree for one repo: guard against shadowing the base branch,
+/// then resolve the branch locally, on the remote, or as a new branch.
+pub(super) fn plan_repo(
+    repo: &ResolvedRepo,
+    branch: BranchName,
+    forest_dir: &AbsolutePath,
+) -> Result<RepoPlan> {
+    // Guard: computed branch must not collide with the repo's base branch
+    if branch.as_str() == repo.base_branch {
+        bail!(
+            "computed branch {:?} for repo {} matches its base branch\n  \
+             hint: this would shadow the base branch and block work outside the forest; \
+             choose a different forest name, or use --branch / --repo-branch to override",
+            branch.as_str(),
+            repo.name,
+        );
     }
 
-    // Validate --repo-branch keys: no duplicates
-    {
-        let mut seen = HashSet::new();
-        for (repo_name, _) in &inputs.repo_branches {
-            ensure!(
-                seen.insert(repo_name.as_str()),
-                "duplicate repo-branch for: {}\n  hint: specify each repo at most once",
-                repo_name
+    // Branch resolution
+    let refs = SourceRefs::read(repo)?;
+    let local_ref = format!("refs/heads/{}", branch);
+    let remote_ref = format!("refs/remotes/{}/{}", repo.remote, branch);
+
+    let checkout = if refs.exists(&local_ref)? {
+        CheckoutKind::ExistingLocal
+    } else if refs.exists(&remote_ref)? {
+        CheckoutKind::TrackRemote
+    } else {
+        // Verify base branch exists on remote
+        let base_ref = format!("refs/remotes/{}/{}", repo.remote, repo.base_branch);
+        if !refs.exists(&base_ref)? {
+            bail!(
+                "{}/{} not found in {}\n  hint: check that base_branch {:?} exists on remote {:?}, or run `git fetch {}` in {}",
+                repo.remote,
+                repo.base_branch,
+                repo.name,
+                repo.base_branch,
+                repo.remote,
+                repo.remote,
+                repo.path.display()
             );
         }
+        CheckoutKind::NewBranch
+    };
+
+    Ok(RepoPlan {
+        name: repo.name.clone(),
+        source: repo.path.clone(),
+        dest: forest_dir.join(repo.name.as_str()),
+        branch,
+        base_branch: repo.base_branch.clone(),
+        remote: repo.remote.clone(),
+        checkout,
+        commit: None,
+        pull_request: None,
+        post_create: repo.post_create.clone(),
+        pre_remove: repo.pre_remove.clone(),
+        clone: refs.pending_clone(repo),
+    })
+}
+
+/// Where planning reads a source repo's refs: its clone, or — for a `url` repo
+/// not cloned yet, which only happens on a dry run — the branches the `url`
+/// advertises, as they will appear under `refs/remotes/<remote>/` once cloned.
+enum SourceRefs<'a> {
+    Clone(&'a Path),
+    Pending {
+        url: &'a str,
+        remote_heads: BTreeMap<String, String>,
+    },
+}
+
+impl<'a> SourceRefs<'a> {
+    fn read(repo: &'a ResolvedRepo) -> Result<Self> {
+        let url = match repo.url.as_deref() {
+            Some(url) if !repo.path.exists() => url,
+            _ => return Ok(SourceRefs::Clone(&repo.path)),
+        };
+        let cwd = repo
+            .path
+            .ancestors()
+            .find(|dir| dir.is_dir())
+            .unwrap_or(Path::new("/"));
+        let advertised = crate::git::git(cwd, &["ls-remote", "--heads", "--", url])
+            .with_context(|| format!("failed to list branches of {}", url))?;
+        let remote_heads = advertised
+            .lines()
+            .filter_map(|line| {
+                let (commit, refname) = line.split_once('\t')?;
+                let branch = refname.strip_prefix("refs/heads/")?;
+                Some((
+                    format!("refs/remotes/{}/{}", repo.remote, branch),
+                    commit.to_string(),
+                ))
+            })
+            .collect();
+        Ok(SourceRefs::Pending { url, remote_heads })
     }
 
-    // Validate --repo-branch keys: all match template repos
-    {
-        let known_names: HashSet<&str> = tmpl.repos.iter().map(|r| r.name.as_str()).collect();
-        for (repo_name, _) in &inputs.repo_branches {
-            if !known_names.contains(repo_name.as_str()) {
-                let known: Vec<&str> = tmpl.repos.iter().map(|r| r.name.as_str()).collect();
-                bail!(
-                    "unknown repo: {}\n  hint: known repos: {}",
-                    repo_name,
-                    known.join(", ")
-                );
+    fn exists(&self, refname: &str) -> Result<bool> {
+        match self {
+            SourceRefs::Clone(path) => ref_exists(path, refname),
+            SourceRefs::Pending { remote_heads, .. } => Ok(remote_heads.contains_key(refname)),
+        }
+    }
+
+    /// The commit `refname` points at, if it exists.
+    fn rev(&self, refname: &str) -> Option<String> {
+        match self {
+            SourceRefs::Clone(path) => {
+                crate::git::git(path, &["rev-parse", "--verify", "--quiet", refname]).ok()
             }
+            SourceRefs::Pending { remote_heads, .. } => remote_heads.get(refname).cloned(),
         }
     }
 
-    // Compute forest directory
-    let fdir = forest_dir(&tmpl.worktree_base, &forest_name);
+    /// The clone a dry run would make for `repo`.
+    fn pending_clone(&self, repo: &ResolvedRepo) -> Option<SourceClone> {
+        match self {
+            SourceRefs::Clone(_) => None,
+            SourceRefs::Pending { url, .. } => Some(SourceClone {
+                url: url.to_string(),
+                path: repo.path.clone(),
+            }),
+        }
+    }
+}
 
-    // Create worktree_base if it doesn't exist (match discover_forests leniency)
-    if !tmpl.worktree_base.exists() {
-        std::fs::create_dir_all(&tmpl.worktree_base)?;
+pub(super) fn template_repo<'a>(
+    tmpl: &'a ResolvedTemplate,
+    repo_name: &str,
+) -> Result<&'a ResolvedRepo> {
+    match tmpl.repos.iter().find(|r| r.name.as_str() == repo_name) {
+        Some(repo) => Ok(repo),
+        None => {
+            let known: Vec<&str> = tmpl.repos.iter().map(|r| r.name.as_str()).collect();
+            bail!(
+                "unknown repo: {}\n  hint: known repos: {}",
+                repo_name,
+                known.join(", ")
+            );
+        }
+    }
+}
+
+/// `--pr` is review-only, names each template repo at most once, and can't be
+/// combined with a `--repo-branch` for the same repo.
+fn validate_pull_requests(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<()> {
+    if inputs.pull_requests.is_empty() {
+        return Ok(());
+    }
+    ensure!(
+        inputs.mode == ForestMode::Review,
+        "--pr requires --mode review\n  hint: feature forests start new branches; use --repo-branch to check out an existing one"
+    );
+    let mut seen = HashSet::new();
+    for (repo_name, _) in &inputs.pull_requests {
+        template_repo(tmpl, repo_name)?;
+        ensure!(
+            seen.insert(repo_name.as_str()),
+            "duplicate --pr for: {}\n  hint: specify each repo at most once",
+            repo_name
+        );
+        ensure!(
+            !inputs.repo_branches.iter().any(|(name, _)| name == repo_name),
+            "both --pr and --repo-branch given for: {}\n  hint: --pr already checks out the pull request head",
+            repo_name
+        );
     }
+    Ok(())
+}
+
+/// Local ref that `--pr` fetches a pull/merge request head into. Kept outside
+/// `refs/remotes/` so `git fetch --prune` leaves it alone.
+pub(super) fn pull_request_ref(remote: &str, number: u64) -> String {
+    format!("refs/forest/{}/pr/{}", remote, number)
+}
+
+/// Plan a review worktree whose new branch starts at a fetched pull request head.
+fn plan_pull_request_repo(
+    repo: &ResolvedRepo,
+    branch: BranchName,
+    number: u64,
+    forest_dir: &AbsolutePath,
+) -> Result<RepoPlan> {
+    let mut plan = plan_repo(repo, branch, forest_dir)?;
+    let rev = |r: &str| crate::git::git(&repo.path, &["rev-parse", "--verify", "--quiet", r]).ok();
+    let Some(head) = rev(&pull_request_ref(&repo.remote, number)) else {
+        bail!(
+            "pull request #{} for {} has not been fetched\n  hint: run without --no-fetch so it is fetched from {}",
+            number,
+            repo.name,
+            repo.remote
+        );
+    };
+
+    plan.checkout = match rev(&format!("refs/heads/{}", plan.branch)) {
+        Some(local) if local == head => CheckoutKind::ExistingLocal,
+        Some(local) => bail!(
+            "branch {} already exists in {} at {}, not at pull request #{} ({})\n  hint: choose a different forest name, or delete the stale branch",
+            plan.branch,
+            repo.path.display(),
+            short_commit(&local),
+            number,
+            short_commit(&head)
+        ),
+        None => CheckoutKind::PullRequest,
+    };
+    plan.commit = Some(head);
+    plan.pull_request = Some(number);
+    Ok(plan)
+}
+
+/// The directory for a new forest under `worktree_base`, refusing names whose
+/// directory exists or that match an existing forest. Read-only: a missing
+/// `worktree_base` is created when the forest is.
+pub(super) fn plan_forest_dir(
+    worktree_base: &AbsolutePath,
+    forest_name: &ForestName,
+) -> Result<AbsolutePath> {
+    let fdir = forest_dir(worktree_base, forest_name);
 
     // Check for directory/name collision
     if fdir.exists() {
@@ -148,7 +366,7 @@ pub fn plan_forest(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<Forest
     }
     // Also check for name collision via meta scan
     if let Some((existing_dir, existing_meta)) =
-        crate::forest::find_forest(&tmpl.worktree_base, forest_name.as_str())?
+        crate::forest::find_forest(worktree_base, forest_name.as_str())?
     {
         bail!(
             "forest name {:?} collides with existing forest {:?} at {}\n  hint: choose a different name",
@@ -158,13 +376,42 @@ pub fn plan_forest(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<Forest
         );
     }
 
+    Ok(fdir)
+}
+
+pub fn plan_forest(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<ForestPlan> {
+    // Validate forest name
+    let forest_name = ForestName::new(inputs.name.clone())?;
+
+    // Validate template has repos
+    if tmpl.repos.is_empty() {
+        bail!("no repos configured\n  hint: run `git forest init --repo <path>` to add repos");
+    }
+
+    // Validate --repo-branch keys: no duplicates
+    {
+        let mut seen = HashSet::new();
+        for (repo_name, _) in &inputs.repo_branches {
+            ensure!(
+                seen.insert(repo_name.as_str()),
+                "duplicate repo-branch for: {}\n  hint: specify each repo at most once",
+                repo_name
+            );
+        }
+    }
+
+    // Validate --repo-branch keys: all match template repos
+    for (repo_name, _) in &inputs.repo_branches {
+        template_repo(tmpl, repo_name)?;
+    }
+
+    validate_pull_requests(inputs, tmpl)?;
+
+    let fdir = plan_forest_dir(&tmpl.worktree_base, &forest_name)?;
+
     // Validate source repos exist and branch names
     for repo in &tmpl.repos {
-        ensure!(
-            repo.path.is_dir(),
-            "source repo not found: {}\n  hint: check that the path exists, or update config with `git forest init --force`",
-            repo.path.display()
-        );
+        ensure_source_repo(repo)?;
     }
 
     // Validate all branch names (global override, per-repo overrides)
@@ -197,55 +444,43 @@ pub fn plan_forest(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<Forest
         );
 
         let branch = BranchName::new(branch_str, &repo.remote)?;
+        let pull_request = inputs
+            .pull_requests
+            .iter()
+            .find(|(name, _)| name == repo.name.as_str());
+        repo_plans.push(match pull_request {
+            Some((_, number)) => plan_pull_request_repo(repo, branch, *number, &fdir)?,
+            None => plan_repo(repo, branch, &fdir)?,
+        });
+    }
 
-        // Guard: computed branch must not collide with the repo's base branch
-        if branch.as_str() == repo.base_branch {
-            bail!(
-                "computed branch {:?} for repo {} matches its base branch\n  \
-                 hint: this would shadow the base branch and block work outside the forest; \
-                 choose a different forest name, or use --branch / --repo-branch to override",
-                branch.as_str(),
-                repo.name,
-            );
-        }
-
-        // Branch resolution
-        let local_ref = format!("refs/heads/{}", branch);
-        let remote_ref = format!("refs/remotes/{}/{}", repo.remote, branch);
-
-        let checkout = if ref_exists(&repo.path, &local_ref)? {
-            CheckoutKind::ExistingLocal
-        } else if ref_exists(&repo.path, &remote_ref)? {
-            CheckoutKind::TrackRemote
-        } else {
-            // Verify base branch exists on remote
-            let base_ref = format!("refs/remotes/{}/{}", repo.remote, repo.base_branch);
-            if !ref_exists(&repo.path, &base_ref)? {
-                bail!(
-                    "{}/{} not found in {}\n  hint: check that base_branch {:?} exists on remote {:?}, or run `git fetch {}` in {}",
-                    repo.remote,
-                    repo.base_branch,
-                    repo.name,
-                    repo.base_branch,
-                    repo.remote,
-                    repo.remote,
-                    repo.path.display()
-                );
-            }
-            CheckoutKind::NewBranch
-        };
+    Ok(ForestPlan {
+        forest_name,
+        forest_dir: fdir,
+        mode: inputs.mode.clone(),
+        disposable_root_entries: tmpl.disposable_root_entries.clone(),
+        env: tmpl.env.clone(),
+        repo_plans,
+    })
+}
 
-        let dest = fdir.join(repo.name.as_str());
+/// Plan a forest that reproduces `manifest`: each manifest repo is matched to a
+/// template repo by name, then by remote URL, and checked out on the recorded
+/// branch at the recorded commit.
+///
+/// `inputs.name` and `inputs.mode` are used as given; branch overrides are ignored.
+pub fn plan_forest_from_manifest(
+    inputs: &NewInputs,
+    tmpl: &ResolvedTemplate,
+    manifest: &ForestManifest,
+) -> Result<ForestPlan> {
+    let forest_name = ForestName::new(inputs.name.clone())?;
+    let matched = match_manifest_repos(tmpl, manifest)?;
+    let fdir = plan_forest_dir(&tmpl.worktree_base, &forest_name)?;
 
-        repo_plans.push(RepoPlan {
-            name: repo.name.clone(),
-            source: repo.path.clone(),
-            dest,
-            branch,
-            base_branch: repo.base_branch.clone(),
-            remote: repo.remote.clone(),
-            checkout,
-        });
+    let mut repo_plans = Vec::new();
+    for (manifest_repo, repo) in manifest.repos.iter().zip(matched) {
+        repo_plans.push(plan_manifest_repo(manifest_repo, repo, &fdir)?);
     }
 
     Ok(ForestPlan {
@@ -253,31 +488,147 @@ pub fn plan_forest(inputs: &NewInputs, tmpl: &ResolvedTemplate) -> Result<Forest
         forest_dir: fdir,
         mode: inputs.mode.clone(),
         disposable_root_entries: tmpl.disposable_root_entries.clone(),
+        env: tmpl.env.clone(),
         repo_plans,
     })
 }
 
-fn branch_created(checkout: &CheckoutKind) -> bool {
+/// The template repo for each manifest repo, in manifest order.
+fn match_manifest_repos<'a>(
+    tmpl: &'a ResolvedTemplate,
+    manifest: &ForestManifest,
+) -> Result<Vec<&'a ResolvedRepo>> {
+    let template_urls: Vec<Option<String>> = tmpl
+        .repos
+        .iter()
+        .map(|repo| {
+            crate::git::git(&repo.path, &["remote", "get-url", &repo.remote])
+                .ok()
+                .map(|url| normalize_remote_url(&url))
+        })
+        .collect();
+
+    let mut matched: Vec<&ResolvedRepo> = Vec::new();
+    for manifest_repo in &manifest.repos {
+        let by_name = tmpl.repos.iter().find(|r| r.name == manifest_repo.name);
+        let by_url = || {
+            let url = normalize_remote_url(manifest_repo.remote_url.as_deref()?);
+            tmpl.repos
+                .iter()
+                .zip(&template_urls)
+                .find(|(_, template_url)| template_url.as_deref() == Some(url.as_str()))
+                .map(|(repo, _)| repo)
+        };
+        let Some(repo) = by_name.or_else(by_url) else {
+            let known: Vec<&str> = tmpl.repos.iter().map(|r| r.name.as_str()).collect();
+            bail!(
+                "manifest repo {} ({}) matches no repo in the template\n  hint: add a clone with `git forest init --repo <path>`; known repos: {}",
+                manifest_repo.name,
+                manifest_repo
+                    .remote_url
+                    .as_deref()
+                    .unwrap_or("no remote URL"),
+                known.join(", ")
+            );
+        };
+        if let Some(i) = matched.iter().position(|m| m.name == repo.name) {
+            bail!(
+                "manifest repos {} and {} both match template repo {}\n  hint: rename one so each manifest repo matches a different template repo",
+                manifest.repos[i].name,
+                manifest_repo.name,
+                repo.name
+            );
+        }
+        matched.push(repo);
+    }
+    Ok(matched)
+}
+
+/// Plan one manifest repo. An existing branch is reused only if it already
+/// points at the recorded commit; otherwise the branch is created there.
+///
+/// For a source not cloned yet (dry run), the commit can't be checked until
+/// the clone exists.
+fn plan_manifest_repo(
+    manifest_repo: &ManifestRepo,
+    repo: &ResolvedRepo,
+    forest_dir: &AbsolutePath,
+) -> Result<RepoPlan> {
+    ensure_source_repo(repo)?;
+    let branch = BranchName::new(manifest_repo.branch.clone(), &repo.remote)?;
+    let commit = manifest_repo.commit.as_str();
+    let refs = SourceRefs::read(repo)?;
+
+    let commit_spec = format!("{}^{{commit}}", commit);
+    if matches!(refs, SourceRefs::Clone(_))
+        && crate::git::git(&repo.path, &["cat-file", "-e", &commit_spec]).is_err()
+    {
+        bail!(
+            "commit {} for {} not found in {}\n  hint: ask for {} to be pushed, then retry (or run `git fetch {}` there if you passed --no-fetch)",
+            short_commit(commit),
+            manifest_repo.name,
+            repo.path.display(),
+            manifest_repo.branch,
+            repo.remote
+        );
+    }
+
+    let local_ref = format!("refs/heads/{}", branch);
+    let remote_ref = format!("refs/remotes/{}/{}", repo.remote, branch);
+    let checkout = match (refs.rev(&local_ref), refs.rev(&remote_ref)) {
+        (Some(local), _) if local == commit => CheckoutKind::ExistingLocal,
+        (Some(local), _) => bail!(
+            "branch {} already exists in {} at {}, but the manifest records {}\n  hint: rename or delete the local branch, or create the forest from a fresh clone",
+            branch,
+            repo.path.display(),
+            short_commit(&local),
+            short_commit(commit)
+        ),
+        (None, Some(remote)) if remote == commit => CheckoutKind::TrackRemote,
+        (None, _) => CheckoutKind::AtCommit,
+    };
+
+    Ok(RepoPlan {
+        name: manifest_repo.name.clone(),
+        source: repo.path.clone(),
+        dest: forest_dir.join(manifest_repo.name.as_str()),
+        branch,
+        base_branch: manifest_repo.base_branch.clone(),
+        remote: repo.remote.clone(),
+        checkout,
+        commit: Some(commit.to_string()),
+        pull_request: None,
+        post_create: repo.post_create.clone(),
+        pre_remove: repo.pre_remove.clone(),
+        clone: refs.pending_clone(repo),
+    })
+}
+
+pub(super) fn branch_created(checkout: &CheckoutKind) -> bool {
     match checkout {
         CheckoutKind::ExistingLocal => false,
         CheckoutKind::TrackRemote => false,
-        CheckoutKind::NewBranch => true,
+        CheckoutKind::NewBranch | CheckoutKind::AtCommit | CheckoutKind::PullRequest => true,
+    }
+}
+
+pub(super) fn repo_plan_result(rp: &RepoPlan) -> NewRepoResult {
+    NewRepoResult {
+        name: rp.name.clone(),
+        branch: rp.branch.to_string(),
+        base_branch: rp.base_branch.clone(),
+        branch_created: branch_created(&rp.checkout),
+        checkout_kind: rp.checkout.clone(),
+        pull_request: rp.pull_request,
+        worktree_path: rp.dest.clone(),
+        clone: rp.clone.clone(),
+        post_create: rp.post_create.clone(),
+        post_create_result: None,
     }
 }
 
 fn plan_to_result(plan: &ForestPlan, dry_run: bool) -> NewResult {
-    let repos = plan
-        .repo_plans
-        .iter()
-        .map(|rp| NewRepoResult {
-            name: rp.name.clone(),
-            branch: rp.branch.to_string(),
-            base_branch: rp.base_branch.clone(),
-            branch_created: branch_created(&rp.checkout),
-            checkout_kind: rp.checkout.clone(),
-            worktree_path: rp.dest.clone(),
-        })
-        .collect();
+    let repos = plan.repo_plans.iter().map(repo_plan_result).collect();
 
     NewResult {
         forest_name: plan.forest_name.clone(),
@@ -289,7 +640,78 @@ fn plan_to_result(plan: &ForestPlan, dry_run: bool) -> NewResult {
     }
 }
 
-pub fn execute_plan(plan: &ForestPlan) -> Result<NewResult> {
+/// Create `repo_plan`'s worktree and return the commit its branch forked from
+/// the base branch, if git can tell.
+pub(super) fn add_worktree(repo_plan: &RepoPlan) -> Result<Option<String>> {
+    checkout_worktree(repo_plan)?;
+    let base = base_ref(
+        &repo_plan.dest,
+        Some(&repo_plan.remote),
+        &repo_plan.base_branch,
+    );
+    let head = format!("refs/heads/{}", repo_plan.branch.as_str());
+    Ok(base.and_then(|base| crate::git::git(&repo_plan.dest, &["merge-base", &base, &head]).ok()))
+}
+
+fn checkout_worktree(repo_plan: &RepoPlan) -> Result<String> {
+    let dest_str = repo_plan.dest.to_string_lossy();
+    let branch_str = repo_plan.branch.as_str();
+    match &repo_plan.checkout {
+        CheckoutKind::ExistingLocal => crate::git::git(
+            &repo_plan.source,
+            &["worktree", "add", &dest_str, branch_str],
+        ),
+        CheckoutKind::TrackRemote => {
+            let start = format!("{}/{}", repo_plan.remote, branch_str);
+            crate::git::git(
+                &repo_plan.source,
+                &["worktree", "add", &dest_str, "-b", branch_str, &start],
+            )
+        }
+        CheckoutKind::NewBranch => {
+            let start = format!("{}/{}", repo_plan.remote, repo_plan.base_branch);
+            // Canonical arg order (options before <path> <commit-ish>) for
+            // compatibility across git versions.
+            crate::git::git(
+                &repo_plan.source,
+                &[
+                    "worktree",
+                    "add",
+                    "-b",
+                    branch_str,
+                    "--no-track",
+                    &dest_str,
+                    &start,
+                ],
+            )
+        }
+        CheckoutKind::AtCommit | CheckoutKind::PullRequest => {
+            let commit = repo_plan
+                .commit
+                .as_deref()
+                .expect("AtCommit and PullRequest plans carry a commit");
+            crate::git::git(
+                &repo_plan.source,
+                &[
+                    "worktree",
+                    "add",
+                    "-b",
+                    branch_str,
+                    "--no-track",
+                    &dest_str,
+                    commit,
+                ],
+            )
+        }
+    }
+}
+
+/// Create the forest directory and worktrees, returning the result and the
+/// written metadata.
+pub fn execute_plan(plan: &ForestPlan, jobs: usize) -> Result<(NewResult, ForestMeta)> {
+    if let Some(worktree_base) = plan.forest_dir.parent() {
+        std::fs::create_dir_all(worktree_base)?;
+    }
     // SAFETY: create_dir (not create_dir_all) is intentional. It fails atomically
     // if the directory already exists, preventing a TOCTOU race between plan_forest
     // (which checks for collisions) and execution. Do not change to create_dir_all.
@@ -301,103 +723,345 @@ pub fn execute_plan(plan: &ForestPlan) -> Result<NewResult> {
         created_at: Utc::now(),
         mode: plan.mode.clone(),
         disposable_root_entries: plan.disposable_root_entries.clone(),
+        env: plan.env.clone(),
         repos: vec![],
+        pins: vec![],
     };
-    // RepoMeta.branch is String, not BranchName, so we use to_string() below
-    let meta_path = plan.forest_dir.join(META_FILENAME);
-    meta.write(&meta_path)?;
+    meta.write(&plan.forest_dir.join(META_FILENAME))?;
 
-    // Create worktrees incrementally, tracking successes for rollback
-    let mut created_worktrees: Vec<(&AbsolutePath, &AbsolutePath)> = Vec::new();
-
-    for repo_plan in &plan.repo_plans {
-        let dest_str = repo_plan.dest.to_string_lossy();
+    if let Err(e) = create_worktrees(&plan.repo_plans, &plan.forest_dir, jobs, &mut meta) {
+        let _ = std::fs::remove_dir_all(&plan.forest_dir);
+        return Err(e);
+    }
 
-        let branch_str = repo_plan.branch.as_str();
-        let result = match &repo_plan.checkout {
-            CheckoutKind::ExistingLocal => crate::git::git(
-                &repo_plan.source,
-                &["worktree", "add", &dest_str, branch_str],
-            ),
-            CheckoutKind::TrackRemote => {
-                let start = format!("{}/{}", repo_plan.remote, branch_str);
-                crate::git::git(
-                    &repo_plan.source,
-                    &["worktree", "add", &dest_str, "-b", branch_str, &start],
-                )
-            }
-            CheckoutKind::NewBranch => {
-                let start = format!("{}/{}", repo_plan.remote, repo_plan.base_branch);
-                // Canonical arg order (options before <path> <commit-ish>) for
-                // compatibility across git versions.
-                crate::git::git(
-                    &repo_plan.source,
-                    &[
-                        "worktree",
-                        "add",
-                        "-b",
-                        branch_str,
-                        "--no-track",
-                        &dest_str,
-                        &start,
-                    ],
-                )
-            }
-        };
+    Ok((plan_to_result(plan, false), meta))
+}
 
+/// Create the planned worktrees on up to `jobs` threads, appending each
+/// success to `meta` and rewriting it as it lands (ADR 0011).
+///
+/// On failure every worktree created here is removed and `meta` is restored to
+/// the repos it held on entry before the first error (in plan order) is returned.
+pub(super) fn create_worktrees(
+    repo_plans: &[RepoPlan],
+    forest_dir: &AbsolutePath,
+    jobs: usize,
+    meta: &mut ForestMeta,
+) -> Result<()> {
+    let meta_path = forest_dir.join(META_FILENAME);
+    let existing_repos = meta.repos.clone();
+
+    // Worktrees are created concurrently, but only this thread touches meta.
+    // Successes are recorded in plan order so the file is stable regardless of
+    // which repo finishes first.
+    let mut created: Vec<Option<RepoMeta>> = vec![None; repo_plans.len()];
+    let mut first_error: Option<(usize, anyhow::Error)> = None;
+
+    crate::parallel::run_bounded(repo_plans, jobs, add_worktree, |index, result| {
         match result {
-            Ok(_) => {
-                created_worktrees.push((&repo_plan.source, &repo_plan.dest));
-
-                // Update meta incrementally
-                meta.repos.push(RepoMeta {
+            Ok(base_commit) => {
+                let repo_plan = &repo_plans[index];
+                created[index] = Some(RepoMeta {
                     name: repo_plan.name.clone(),
                     source: repo_plan.source.clone(),
                     branch: repo_plan.branch.to_string(),
                     base_branch: repo_plan.base_branch.clone(),
                     remote: Some(repo_plan.remote.clone()),
                     branch_created: branch_created(&repo_plan.checkout),
+                    pre_remove: repo_plan.pre_remove.clone(),
+                    base_commit,
                 });
-                meta.write(&meta_path)?;
+
+                // Update meta incrementally
+                if first_error.is_none() {
+                    meta.repos = existing_repos
+                        .iter()
+                        .chain(created.iter().flatten())
+                        .cloned()
+                        .collect();
+                    if let Err(e) = meta.write(&meta_path) {
+                        first_error = Some((index, e));
+                    }
+                }
             }
             Err(e) => {
-                // Rollback: remove successfully-created worktrees
-                for (source, dest) in &created_worktrees {
-                    assert!(
-                        dest.starts_with(&plan.forest_dir),
-                        "rollback target {:?} is not inside forest dir {:?}",
-                        dest,
-                        plan.forest_dir
-                    );
-                    let d = dest.to_string_lossy();
-                    let _ = crate::git::git(source, &["worktree", "remove", "--force", &d]);
+                if first_error.as_ref().is_none_or(|(i, _)| index < *i) {
+                    first_error = Some((index, e));
                 }
-                let _ = std::fs::remove_dir_all(&plan.forest_dir);
-                return Err(e);
             }
         }
+        first_error.is_none()
+    });
+
+    let Some((_, e)) = first_error else {
+        return Ok(());
+    };
+
+    // Rollback: remove every worktree that was created, including ones
+    // that were still in flight when the failure was observed
+    for (repo_plan, _) in repo_plans.iter().zip(&created).filter(|(_, c)| c.is_some()) {
+        assert!(
+            repo_plan.dest.starts_with(forest_dir),
+            "rollback target {:?} is not inside forest dir {:?}",
+            repo_plan.dest,
+            forest_dir
+        );
+        let d = repo_plan.dest.to_string_lossy();
+        let _ = crate::git::git(&repo_plan.source, &["worktree", "remove", "--force", &d]);
     }
+    meta.repos = existing_repos;
+    let _ = meta.write(&meta_path);
+    Err(e)
+}
 
-    Ok(plan_to_result(plan, false))
+/// Fetch each `--pr` head into [`pull_request_ref`], asking the remote for
+/// GitHub's `refs/pull/<n>/head` or GitLab's `refs/merge-requests/<n>/head`.
+/// Uses only the git remote, so no API access or tokens are needed.
+pub(super) fn fetch_pull_requests(
+    tmpl: &ResolvedTemplate,
+    pull_requests: &[(String, u64)],
+) -> Result<()> {
+    for (repo_name, number) in pull_requests {
+        let repo = template_repo(tmpl, repo_name)?;
+        let candidates = [
+            format!("refs/pull/{}/head", number),
+            format!("refs/merge-requests/{}/head", number),
+        ];
+        let mut args = vec!["ls-remote", repo.remote.as_str()];
+        args.extend(candidates.iter().map(String::as_str));
+        let advertised = crate::git::git(&repo.path, &args)?;
+        let Some(remote_ref) = candidates.iter().find(|candidate| {
+            advertised
+                .lines()
+                .any(|line| line.split('\t').nth(1) == Some(candidate.as_str()))
+        }) else {
+            bail!(
+                "pull request #{} not found on {} for {}\n  hint: looked for {} and {}",
+                number,
+                repo.remote,
+                repo.name,
+                candidates[0],
+                candidates[1]
+            );
+        };
+        let refspec = format!(
+            "+{}:{}",
+            remote_ref,
+            pull_request_ref(&repo.remote, *number)
+        );
+        crate::git::git(&repo.path, &["fetch", &repo.remote, &refspec])?;
+    }
+    Ok(())
+}
+
+/// Clone `url` into `path`, naming the remote `remote`.
+pub(super) fn clone_source(url: &str, path: &Path, remote: &str) -> Result<()> {
+    let parent = path.parent().context("clone path has no parent")?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("failed to create {}", parent.display()))?;
+    crate::git::git(
+        parent,
+        &[
+            "clone",
+            "--origin",
+            remote,
+            "--",
+            url,
+            &path.to_string_lossy(),
+        ],
+    )
+    .with_context(|| format!("failed to clone {} into {}", url, path.display()))?;
+    Ok(())
+}
+
+/// Clone every repo configured with a `url` whose source doesn't exist yet,
+/// returning the clones made.
+pub(super) fn clone_missing_repos(repos: &[ResolvedRepo], jobs: usize) -> Result<Vec<SourceClone>> {
+    let repos: Vec<(&ResolvedRepo, &str)> = repos
+        .iter()
+        .filter(|r| !r.path.exists())
+        .filter_map(|r| Some((r, r.url.as_deref()?)))
+        .collect();
+    let mut errors: Vec<Option<anyhow::Error>> = repos.iter().map(|_| None).collect();
+
+    crate::parallel::run_bounded(
+        &repos,
+        jobs,
+        |(repo, url)| clone_source(url, &repo.path, &repo.remote),
+        |index, result| {
+            if let Err(e) = result {
+                errors[index] = Some(e);
+            }
+            true
+        },
+    );
+
+    if let Some(e) = errors.into_iter().flatten().next() {
+        return Err(e);
+    }
+    Ok(repos
+        .iter()
+        .map(|(repo, url)| SourceClone {
+            url: url.to_string(),
+            path: repo.path.clone(),
+        })
+        .collect())
+}
+
+/// Note on each repo plan the clone made for its source in this run.
+pub(super) fn record_clones(repo_plans: &mut [RepoPlan], clones: &[SourceClone]) {
+    for rp in repo_plans {
+        if let Some(clone) = clones.iter().find(|c| c.path == rp.source) {
+            rp.clone = Some(clone.clone());
+        }
+    }
+}
+
+/// Refuse a source repo that doesn't exist. One with a `url` is only missing
+/// on a dry run, which plans the clone instead of making it.
+pub(super) fn ensure_source_repo(repo: &ResolvedRepo) -> Result<()> {
+    ensure!(
+        repo.path.is_dir() || repo.url.is_some(),
+        "source repo not found: {}\n  hint: check that the path exists, or update config with `git forest init --force`",
+        repo.path.display()
+    );
+    Ok(())
+}
+
+pub(super) fn fetch_remotes(repos: &[ResolvedRepo], jobs: usize) -> Result<()> {
+    let repos: Vec<&ResolvedRepo> = repos.iter().filter(|r| r.path.is_dir()).collect();
+    let mut errors: Vec<Option<anyhow::Error>> = repos.iter().map(|_| None).collect();
+
+    crate::parallel::run_bounded(
+        &repos,
+        jobs,
+        |repo| crate::git::git(&repo.path, &["fetch", &repo.remote]),
+        |index, result| {
+            if let Err(e) = result {
+                errors[index] = Some(e);
+            }
+            true
+        },
+    );
+
+    // Report the first failure in config order so output is deterministic
+    match errors.into_iter().flatten().next() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Run each repo's `post_create` hook in its new worktree, recording results
+/// on the matching entry of `results`. Hooks run after the forest is complete;
+/// a failing hook is reported but never rolls anything back.
+///
+/// `meta` is the forest metadata after creation; it supplies each hook's environment.
+pub(super) fn run_post_create_hooks(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    repo_plans: &[RepoPlan],
+    results: &mut [NewRepoResult],
+    jobs: usize,
+) {
+    debug_assert_eq!(repo_plans.len(), results.len());
+    let hooked: Vec<(usize, &str, &RepoPlan)> = repo_plans
+        .iter()
+        .enumerate()
+        .filter_map(|(i, rp)| rp.post_create.as_deref().map(|cmd| (i, cmd, rp)))
+        .collect();
+
+    crate::parallel::run_bounded(
+        &hooked,
+        jobs,
+        |(_, cmd, rp)| {
+            let env = meta
+                .repos
+                .iter()
+                .find(|r| r.name == rp.name)
+                .map(|repo| meta.repo_env(forest_dir, repo))
+                .unwrap_or_default();
+            run_hook(cmd, &rp.dest, &env)
+        },
+        |index, hook| {
+            results[hooked[index].0].post_create_result = Some(hook);
+            true
+        },
+    );
 }
 
 pub fn cmd_new(inputs: NewInputs, tmpl: &ResolvedTemplate) -> Result<NewResult> {
+    ensure!(
+        inputs.jobs > 0,
+        "--jobs must be at least 1\n  hint: use --jobs 1 to create worktrees one at a time"
+    );
+
+    let clones = if inputs.dry_run {
+        Vec::new()
+    } else {
+        clone_missing_repos(&tmpl.repos, inputs.jobs)?
+    };
+
     // Fetch unless --no-fetch
     if !inputs.no_fetch {
-        for repo in &tmpl.repos {
-            if repo.path.is_dir() {
-                crate::git::git(&repo.path, &["fetch", &repo.remote])?;
-            }
-        }
+        fetch_remotes(&tmpl.repos, inputs.jobs)?;
+        validate_pull_requests(&inputs, tmpl)?;
+        fetch_pull_requests(tmpl, &inputs.pull_requests)?;
     }
 
-    let plan = plan_forest(&inputs, tmpl)?;
+    let mut plan = plan_forest(&inputs, tmpl)?;
+    record_clones(&mut plan.repo_plans, &clones);
+    run_plan(&inputs, &plan)
+}
 
+/// `new --from-manifest`: recreate an exported forest from this machine's template.
+pub fn cmd_new_from_manifest(
+    inputs: NewInputs,
+    tmpl: &ResolvedTemplate,
+    manifest: &ForestManifest,
+) -> Result<NewResult> {
+    ensure!(
+        inputs.jobs > 0,
+        "--jobs must be at least 1\n  hint: use --jobs 1 to create worktrees one at a time"
+    );
+
+    let clones = if inputs.dry_run {
+        Vec::new()
+    } else {
+        clone_missing_repos(&tmpl.repos, inputs.jobs)?
+    };
+    if !inputs.no_fetch {
+        fetch_remotes(&tmpl.repos, inputs.jobs)?;
+    }
+
+    let mut plan = plan_forest_from_manifest(&inputs, tmpl, manifest)?;
+    record_clones(&mut plan.repo_plans, &clones);
+    run_plan(&inputs, &plan)
+}
+
+/// Create the planned forest (unless dry run) and run its post_create hooks.
+fn run_plan(inputs: &NewInputs, plan: &ForestPlan) -> Result<NewResult> {
     if inputs.dry_run {
-        return Ok(plan_to_result(&plan, true));
+        return Ok(plan_to_result(plan, true));
     }
 
-    execute_plan(&plan)
+    let (mut result, meta) = execute_plan(plan, inputs.jobs)?;
+
+    if !inputs.no_hooks {
+        let hook_jobs = if inputs.parallel_hooks {
+            inputs.jobs
+        } else {
+            1
+        };
+        run_post_create_hooks(
+            &plan.forest_dir,
+            &meta,
+            &plan.repo_plans,
+            &mut result.repos,
+            hook_jobs,
+        );
+    }
+
+    Ok(result)
 }
 
 pub fn format_new_human(result: &NewResult) -> String {
@@ -428,22 +1092,60 @@ pub fn format_new_human(result: &NewResult) -> String {
     lines.push(String::new());
 
     for repo in &result.repos {
-        let kind = match &repo.checkout_kind {
-            CheckoutKind::ExistingLocal => "existing",
-            CheckoutKind::TrackRemote => "track remote",
-            CheckoutKind::NewBranch => "new branch",
-        };
-        lines.push(format!("  {} → {} ({})", repo.name, repo.branch, kind));
+        lines.push(format_new_repo_line(repo));
+        lines.extend(format_clone_line(repo, result.dry_run));
+        lines.extend(format_post_create_lines(repo, result.dry_run));
     }
 
     lines.join("\n")
 }
 
+pub(super) fn format_clone_line(repo: &NewRepoResult, dry_run: bool) -> Option<String> {
+    let clone = repo.clone.as_ref()?;
+    let verb = if dry_run { "would clone" } else { "cloned" };
+    Some(format!(
+        "    {} {} into {}",
+        verb,
+        clone.url,
+        clone.path.display()
+    ))
+}
+
+pub(super) fn format_post_create_lines(repo: &NewRepoResult, dry_run: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    match (&repo.post_create, &repo.post_create_result) {
+        (_, Some(hook)) => {
+            lines.push(format!("    {}", format_hook_summary("post_create", hook)));
+            if !hook.success {
+                lines.extend(hook.output_tail.lines().map(|l| format!("      {}", l)));
+            }
+        }
+        (Some(cmd), None) if dry_run => {
+            lines.push(format!("    post_create: {}", cmd));
+        }
+        _ => {}
+    }
+    lines
+}
+
+pub(super) fn format_new_repo_line(repo: &NewRepoResult) -> String {
+    let kind = match &repo.checkout_kind {
+        CheckoutKind::ExistingLocal => "existing".to_string(),
+        CheckoutKind::TrackRemote => "track remote".to_string(),
+        CheckoutKind::NewBranch => "new branch".to_string(),
+        CheckoutKind::AtCommit => "new branch at manifest commit".to_string(),
+        CheckoutKind::PullRequest => match repo.pull_request {
+            Some(number) => format!("new branch at pull request #{}", number),
+            None => "new branch at pull request".to_string(),
+        },
+    };
+    format!("  {} → {} ({})", repo.name, repo.branch, kind)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::commands::cmd_ls;
-    use crate::config::{ResolvedRepo, ResolvedTemplate};
     use crate::paths::AbsolutePath;
     use crate::testutil::TestEnv;
     use std::path::PathBuf;
@@ -454,8 +1156,12 @@ mod tests {
             mode,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         }
     }
 
@@ -597,6 +1303,9 @@ mod tests {
             base_branch: "main".to_string(),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
+            post_create: None,
+            pre_remove: None,
+            env: Default::default(),
             repos: vec![],
         };
 
@@ -617,11 +1326,17 @@ mod tests {
             base_branch: "main".to_string(),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
+            post_create: None,
+            pre_remove: None,
+            env: Default::default(),
             repos: vec![crate::config::ResolvedRepo {
                 path: AbsolutePath::new(PathBuf::from("/nonexistent/repo")).unwrap(),
+                url: None,
                 name: RepoName::new("missing".to_string()).unwrap(),
                 base_branch: "main".to_string(),
                 remote: "origin".to_string(),
+                post_create: None,
+                pre_remove: None,
             }],
         };
 
@@ -910,7 +1625,7 @@ mod tests {
 
         let inputs = make_new_inputs("exec-test", ForestMode::Feature);
         let plan = plan_forest(&inputs, &tmpl).unwrap();
-        let result = execute_plan(&plan).unwrap();
+        let (result, _) = execute_plan(&plan, 2).unwrap();
 
         assert_eq!(result.repos.len(), 2);
         assert!(!result.dry_run);
@@ -933,7 +1648,7 @@ mod tests {
 
         let inputs = make_new_inputs("meta-test", ForestMode::Feature);
         let plan = plan_forest(&inputs, &tmpl).unwrap();
-        let result = execute_plan(&plan).unwrap();
+        let (result, _) = execute_plan(&plan, 2).unwrap();
 
         let meta_path = plan.forest_dir.join(META_FILENAME);
         let meta = ForestMeta::read(&meta_path).unwrap();
@@ -950,6 +1665,40 @@ mod tests {
         assert!(meta.repos[0].branch_created);
     }
 
+    #[test]
+    fn execute_parallel_records_meta_in_plan_order() {
+        let env = TestEnv::new();
+        let names = ["foo-api", "foo-web", "foo-worker", "foo-docs"];
+        for name in names {
+            env.create_repo_with_remote(name);
+        }
+        let tmpl = make_template_with_repos(&env, &names);
+
+        let inputs = make_new_inputs("parallel-test", ForestMode::Feature);
+        let plan = plan_forest(&inputs, &tmpl).unwrap();
+        execute_plan(&plan, names.len()).unwrap();
+
+        let meta = ForestMeta::read(&plan.forest_dir.join(META_FILENAME)).unwrap();
+        let recorded: Vec<&str> = meta.repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(recorded, names);
+        for name in names {
+            assert!(plan.forest_dir.join(name).join(".git").exists());
+        }
+    }
+
+    #[test]
+    fn cmd_new_rejects_zero_jobs() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+
+        let mut inputs = make_new_inputs("zero-jobs", ForestMode::Feature);
+        inputs.jobs = 0;
+
+        let err = cmd_new(inputs, &tmpl).unwrap_err().to_string();
+        assert!(err.contains("--jobs must be at least 1"), "error: {}", err);
+    }
+
     // --- cmd_new ---
 
     #[test]
@@ -971,6 +1720,227 @@ mod tests {
         assert!(result.forest_dir.exists());
     }
 
+    #[test]
+    fn cmd_new_clones_missing_url_repos_unless_dry_run() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let mut tmpl = make_template_with_repos(&env, &["foo-api"]);
+        let clone_path = env.root().join("clones").join("foo-api");
+        let bare_path = env.root().join("bare").join("foo-api.git");
+        tmpl.repos[0].path = AbsolutePath::new(clone_path.clone()).unwrap();
+        tmpl.repos[0].url = Some(format!("file://{}", bare_path.display()));
+
+        let mut inputs = make_new_inputs("cloned", ForestMode::Feature);
+        inputs.dry_run = true;
+        let result = cmd_new(inputs, &tmpl).unwrap();
+        let clone = result.repos[0].clone.as_ref().unwrap();
+        assert_eq!(clone.url, tmpl.repos[0].url.clone().unwrap());
+        assert_eq!(*clone.path, *clone_path);
+        assert!(matches!(
+            result.repos[0].checkout_kind,
+            CheckoutKind::NewBranch
+        ));
+        assert!(format_new_human(&result).contains("would clone file://"));
+        assert!(!clone_path.exists());
+
+        let result = cmd_new(make_new_inputs("cloned", ForestMode::Feature), &tmpl).unwrap();
+        assert!(clone_path.join(".git").is_dir());
+        assert_eq!(result.repos[0].branch, "testuser/cloned");
+        assert!(result.repos[0].worktree_path.exists());
+        assert!(result.repos[0].clone.is_some());
+        assert!(format_new_human(&result).contains("    cloned file://"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cmd_new_runs_post_create_hooks_in_worktrees() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let mut tmpl = make_template_with_repos(&env, &["foo-api", "foo-web"]);
+        tmpl.repos[0].post_create = Some("touch hooked && echo done".to_string());
+        tmpl.repos[1].post_create = Some("echo broken >&2; exit 2".to_string());
+
+        let mut inputs = make_new_inputs("hooked", ForestMode::Feature);
+        inputs.parallel_hooks = true;
+        let result = cmd_new(inputs, &tmpl).unwrap();
+
+        let api = result.repos[0].post_create_result.as_ref().unwrap();
+        assert!(api.success);
+        assert_eq!(api.output_tail, "done");
+        assert!(result.repos[0].worktree_path.join("hooked").exists());
+
+        // A failing hook is reported but the forest stays intact
+        let web = result.repos[1].post_create_result.as_ref().unwrap();
+        assert!(!web.success);
+        assert_eq!(web.exit_code, Some(2));
+        assert_eq!(web.output_tail, "broken");
+        assert!(result.repos[1].worktree_path.exists());
+        let meta = ForestMeta::read(&result.forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.repos.len(), 2);
+
+        let human = format_new_human(&result);
+        assert!(human.contains("post_create ok"), "output: {}", human);
+        assert!(
+            human.contains("post_create failed (exit 2"),
+            "output: {}",
+            human
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cmd_new_snapshots_env_and_passes_it_to_hooks() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let mut tmpl = make_template_with_repos(&env, &["foo-api"]);
+        tmpl.env
+            .insert("COMPOSE_PROFILES".to_string(), "dev".to_string());
+        tmpl.repos[0].post_create =
+            Some("echo $FOREST_NAME $FOREST_REPO $COMPOSE_PROFILES".to_string());
+
+        let result = cmd_new(make_new_inputs("env-hook", ForestMode::Feature), &tmpl).unwrap();
+
+        let hook = result.repos[0].post_create_result.as_ref().unwrap();
+        assert_eq!(hook.output_tail, "env-hook foo-api dev");
+        let meta = ForestMeta::read(&result.forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.env, tmpl.env);
+    }
+
+    /// Publish a commit on top of main under `remote_ref` on origin, the way a
+    /// forge exposes pull request heads, and return its SHA.
+    fn push_pull_request_ref(env: &TestEnv, repo: &str, remote_ref: &str) -> String {
+        let src = env.repo_path(repo);
+        crate::git::git(&src, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(&src, &["config", "user.email", "test@test.com"]).unwrap();
+        let sha = crate::git::git(
+            &src,
+            &[
+                "commit-tree",
+                "HEAD^{tree}",
+                "-p",
+                "HEAD",
+                "-m",
+                "PR change",
+            ],
+        )
+        .unwrap();
+        let refspec = format!("{}:{}", sha, remote_ref);
+        crate::git::git(&src, &["push", "origin", &refspec]).unwrap();
+        sha
+    }
+
+    #[test]
+    fn cmd_new_review_checks_out_github_and_gitlab_pull_requests() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let tmpl = make_template_with_repos(&env, &["foo-api", "foo-web"]);
+        let api_head = push_pull_request_ref(&env, "foo-api", "refs/pull/42/head");
+        let web_head = push_pull_request_ref(&env, "foo-web", "refs/merge-requests/7/head");
+
+        let mut inputs = make_new_inputs("review-prs", ForestMode::Review);
+        inputs.no_fetch = false;
+        inputs.pull_requests = vec![("foo-api".to_string(), 42), ("foo-web".to_string(), 7)];
+        let result = cmd_new(inputs, &tmpl).unwrap();
+
+        for (repo, head, number) in [
+            (&result.repos[0], &api_head, 42),
+            (&result.repos[1], &web_head, 7),
+        ] {
+            assert!(matches!(repo.checkout_kind, CheckoutKind::PullRequest));
+            assert_eq!(repo.pull_request, Some(number));
+            assert_eq!(repo.branch, "forest/review-prs");
+            assert_eq!(
+                &crate::git::git(&repo.worktree_path, &["rev-parse", "HEAD"]).unwrap(),
+                head
+            );
+        }
+        let meta = ForestMeta::read(&result.forest_dir.join(META_FILENAME)).unwrap();
+        assert!(meta.repos.iter().all(|r| r.branch_created));
+        assert!(format_new_human(&result).contains("(new branch at pull request #42)"));
+    }
+
+    #[test]
+    fn cmd_new_pr_requires_review_mode_and_an_existing_ref() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+
+        let mut inputs = make_new_inputs("feature-pr", ForestMode::Feature);
+        inputs.pull_requests = vec![("foo-api".to_string(), 1)];
+        let err = cmd_new(inputs, &tmpl).unwrap_err().to_string();
+        assert!(
+            err.contains("--pr requires --mode review"),
+            "error: {}",
+            err
+        );
+
+        let mut inputs = make_new_inputs("missing-pr", ForestMode::Review);
+        inputs.no_fetch = false;
+        inputs.pull_requests = vec![("foo-api".to_string(), 99)];
+        let err = cmd_new(inputs, &tmpl).unwrap_err().to_string();
+        assert!(
+            err.contains("pull request #99 not found on origin for foo-api"),
+            "error: {}",
+            err
+        );
+
+        // --no-fetch can only use a head fetched earlier
+        let mut inputs = make_new_inputs("unfetched-pr", ForestMode::Review);
+        inputs.pull_requests = vec![("foo-api".to_string(), 3)];
+        let err = cmd_new(inputs, &tmpl).unwrap_err().to_string();
+        assert!(err.contains("has not been fetched"), "error: {}", err);
+    }
+
+    #[test]
+    fn cmd_new_records_base_commit() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+
+        let result = cmd_new(make_new_inputs("base-sha", ForestMode::Feature), &tmpl).unwrap();
+
+        let meta = ForestMeta::read(&result.forest_dir.join(META_FILENAME)).unwrap();
+        let expected =
+            crate::git::git(&env.repo_path("foo-api"), &["rev-parse", "origin/main"]).unwrap();
+        assert_eq!(
+            meta.repos[0].base_commit.as_deref(),
+            Some(expected.as_str())
+        );
+    }
+
+    #[test]
+    fn cmd_new_no_hooks_skips_post_create() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let mut tmpl = make_template_with_repos(&env, &["foo-api"]);
+        tmpl.repos[0].post_create = Some("touch hooked".to_string());
+
+        let mut inputs = make_new_inputs("no-hooks", ForestMode::Feature);
+        inputs.no_hooks = true;
+        let result = cmd_new(inputs, &tmpl).unwrap();
+
+        assert!(result.repos[0].post_create_result.is_none());
+        assert!(!result.repos[0].worktree_path.join("hooked").exists());
+    }
+
+    #[test]
+    fn cmd_new_dry_run_lists_post_create_without_running() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let mut tmpl = make_template_with_repos(&env, &["foo-api"]);
+        tmpl.repos[0].post_create = Some("touch hooked".to_string());
+
+        let mut inputs = make_new_inputs("dry-hooks", ForestMode::Feature);
+        inputs.dry_run = true;
+        let result = cmd_new(inputs, &tmpl).unwrap();
+
+        assert_eq!(result.repos[0].post_create.as_deref(), Some("touch hooked"));
+        assert!(result.repos[0].post_create_result.is_none());
+        assert!(format_new_human(&result).contains("post_create: touch hooked"));
+    }
+
     #[test]
     fn feature_branch_has_no_upstream_tracking() {
         let env = TestEnv::new();
@@ -1046,7 +2016,8 @@ mod tests {
     fn cmd_new_dry_run_does_not_create() {
         let env = TestEnv::new();
         env.create_repo_with_remote("foo-api");
-        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+        let mut tmpl = make_template_with_repos(&env, &["foo-api"]);
+        tmpl.worktree_base = AbsolutePath::new(env.root().join("not-yet")).unwrap();
 
         let mut inputs = make_new_inputs("dry-run-test", ForestMode::Feature);
         inputs.dry_run = true;
@@ -1055,8 +2026,12 @@ mod tests {
 
         assert!(result.dry_run);
         assert_eq!(result.repos.len(), 1);
-        // Forest directory should NOT exist
+        // Neither the forest directory nor a missing worktree_base is created
         assert!(!result.forest_dir.exists());
+        assert!(!tmpl.worktree_base.exists());
+
+        let inputs = make_new_inputs("dry-run-test", ForestMode::Feature);
+        assert!(cmd_new(inputs, &tmpl).unwrap().forest_dir.is_dir());
     }
 
     #[test]
@@ -1107,11 +2082,17 @@ mod tests {
             base_branch: "main".to_string(),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
+            post_create: None,
+            pre_remove: None,
+            env: Default::default(),
             repos: vec![ResolvedRepo {
                 path: env.repo_path("beta-api"),
+                url: None,
                 name: RepoName::new("beta-api".to_string()).unwrap(),
                 base_branch: "main".to_string(),
                 remote: "origin".to_string(),
+                post_create: None,
+                pre_remove: None,
             }],
         };
 
@@ -1188,7 +2169,7 @@ mod tests {
         std::fs::remove_dir_all(&**bad_source).unwrap();
 
         // execute_plan should fail on the second repo
-        let result = execute_plan(&plan);
+        let result = execute_plan(&plan, 2);
         assert!(result.is_err(), "execute_plan should fail");
 
         // The forest directory should have been cleaned up
@@ -1209,4 +2190,164 @@ mod tests {
             wt_list
         );
     }
+
+    // --- From manifest ---
+
+    /// Push `sue/shared` with two commits to foo-api's origin, leaving no local
+    /// branch behind. Returns (first, second) commit SHAs.
+    fn push_shared_branch(env: &TestEnv) -> (String, String) {
+        let source = env.repo_path("foo-api");
+        let git = |args: &[&str]| crate::git::git(&source, args).unwrap();
+        git(&["config", "user.name", "Test"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["checkout", "-b", "sue/shared"]);
+        git(&["commit", "--allow-empty", "-m", "first"]);
+        let first = git(&["rev-parse", "HEAD"]);
+        git(&["commit", "--allow-empty", "-m", "second"]);
+        let second = git(&["rev-parse", "HEAD"]);
+        git(&["push", "origin", "sue/shared"]);
+        git(&["checkout", "main"]);
+        git(&["branch", "-D", "sue/shared"]);
+        (first, second)
+    }
+
+    fn manifest_repo(name: &str, remote_url: Option<&str>, commit: &str) -> ManifestRepo {
+        ManifestRepo {
+            name: RepoName::new(name.to_string()).unwrap(),
+            remote_url: remote_url.map(str::to_string),
+            branch: "sue/shared".to_string(),
+            base_branch: "main".to_string(),
+            commit: commit.to_string(),
+        }
+    }
+
+    fn make_manifest(repos: Vec<ManifestRepo>) -> ForestManifest {
+        ForestManifest {
+            name: ForestName::new("shared".to_string()).unwrap(),
+            mode: ForestMode::Feature,
+            repos,
+        }
+    }
+
+    #[test]
+    fn cmd_new_from_manifest_checks_out_recorded_commits() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let tmpl = make_template_with_repos(&env, &["foo-api", "foo-web"]);
+        let (first, second) = push_shared_branch(&env);
+        let web_main =
+            crate::git::git(&env.repo_path("foo-web"), &["rev-parse", "origin/main"]).unwrap();
+        let web_url = env.root().join("bare").join("foo-web.git");
+
+        // foo-api pins an older commit than origin has; foo-web is matched by URL
+        let manifest = make_manifest(vec![
+            manifest_repo("foo-api", None, &first),
+            manifest_repo("web", Some(web_url.to_str().unwrap()), &web_main),
+        ]);
+        let inputs = make_new_inputs("shared", ForestMode::Feature);
+        let result = cmd_new_from_manifest(inputs, &tmpl, &manifest).unwrap();
+
+        assert!(matches!(
+            result.repos[0].checkout_kind,
+            CheckoutKind::AtCommit
+        ));
+        assert_eq!(result.repos[1].name.as_str(), "web");
+        let api = result.forest_dir.join("foo-api");
+        let web = result.forest_dir.join("web");
+        assert_eq!(
+            crate::git::git(&api, &["rev-parse", "HEAD"]).unwrap(),
+            first
+        );
+        assert_ne!(first, second);
+        assert_eq!(
+            crate::git::git(&api, &["symbolic-ref", "--short", "HEAD"]).unwrap(),
+            "sue/shared"
+        );
+        assert_eq!(
+            crate::git::git(&web, &["rev-parse", "HEAD"]).unwrap(),
+            web_main
+        );
+
+        let meta = ForestMeta::read(&result.forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.repos[1].name.as_str(), "web");
+        assert_eq!(meta.repos[1].source, env.repo_path("foo-web"));
+    }
+
+    #[test]
+    fn cmd_new_from_manifest_tracks_remote_branch_at_recorded_commit() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+        let (_, second) = push_shared_branch(&env);
+
+        let manifest = make_manifest(vec![manifest_repo("foo-api", None, &second)]);
+        let mut inputs = make_new_inputs("shared", ForestMode::Feature);
+        inputs.dry_run = true;
+        let result = cmd_new_from_manifest(inputs, &tmpl, &manifest).unwrap();
+
+        assert!(matches!(
+            result.repos[0].checkout_kind,
+            CheckoutKind::TrackRemote
+        ));
+        assert!(!result.repos[0].branch_created);
+    }
+
+    #[test]
+    fn cmd_new_from_manifest_refuses_local_branch_at_other_commit() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+        let (first, second) = push_shared_branch(&env);
+        crate::git::git(
+            &env.repo_path("foo-api"),
+            &["branch", "sue/shared", &second],
+        )
+        .unwrap();
+
+        let manifest = make_manifest(vec![manifest_repo("foo-api", None, &first)]);
+        let inputs = make_new_inputs("shared", ForestMode::Feature);
+        let err = cmd_new_from_manifest(inputs, &tmpl, &manifest)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("branch sue/shared already exists"),
+            "error: {}",
+            err
+        );
+        assert!(!tmpl.worktree_base.join("shared").exists());
+    }
+
+    #[test]
+    fn cmd_new_from_manifest_rejects_unmatched_repo_and_missing_commit() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let tmpl = make_template_with_repos(&env, &["foo-api"]);
+        let head = crate::git::git(&env.repo_path("foo-api"), &["rev-parse", "HEAD"]).unwrap();
+
+        let unmatched = make_manifest(vec![manifest_repo(
+            "billing",
+            Some("git@example.com:org/billing.git"),
+            &head,
+        )]);
+        let err = cmd_new_from_manifest(
+            make_new_inputs("shared", ForestMode::Feature),
+            &tmpl,
+            &unmatched,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("manifest repo billing"), "error: {}", err);
+        assert!(err.contains("known repos: foo-api"), "error: {}", err);
+
+        let missing = make_manifest(vec![manifest_repo("foo-api", None, &"ab".repeat(20))]);
+        let err = cmd_new_from_manifest(
+            make_new_inputs("shared", ForestMode::Feature),
+            &tmpl,
+            &missing,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("not found in"), "error: {}", err);
+    }
 }
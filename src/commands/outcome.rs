@@ -0,0 +1,51 @@
+use serde::Serialize;
+
+use crate::paths::RepoName;
+
+/// What happened to one repo (or one step for it) in a command that acts on
+/// every repo and continues past failures (ADR 0009).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum Outcome {
+    Success,
+    Skipped { reason: String },
+    Failed { error: String },
+}
+
+/// A per-repo entry of a plan, and the result it reports.
+pub(super) trait PlannedRepo {
+    type Outcome;
+    type Result;
+
+    /// Outcome reported without acting: a dry run's preview, or the final
+    /// outcome of a repo skipped or failed during planning.
+    fn planned_outcome(&self) -> Self::Outcome;
+
+    fn result(&self, outcome: Self::Outcome) -> Self::Result;
+
+    fn planned_result(&self) -> Self::Result {
+        self.result(self.planned_outcome())
+    }
+}
+
+/// Dry-run results, in plan order.
+pub(super) fn planned_results<P: PlannedRepo>(repo_plans: &[P]) -> Vec<P::Result> {
+    repo_plans.iter().map(PlannedRepo::planned_result).collect()
+}
+
+/// A per-repo result that reports an [`Outcome`].
+pub(super) trait RepoOutcome {
+    fn name(&self) -> &RepoName;
+    fn outcome(&self) -> &Outcome;
+}
+
+/// `"<repo>: <error>"` for each failed repo, in order.
+pub(super) fn repo_errors<R: RepoOutcome>(repos: &[R]) -> Vec<String> {
+    repos
+        .iter()
+        .filter_map(|repo| match repo.outcome() {
+            Outcome::Failed { error } => Some(format!("{}: {}", repo.name(), error)),
+            Outcome::Success | Outcome::Skipped { .. } => None,
+        })
+        .collect()
+}
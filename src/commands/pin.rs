@@ -0,0 +1,634 @@
+use anyhow::{bail, ensure, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{compact_git_error, path_exists_or_symlink, short_commit};
+use super::drop_repo::drop_repo_command;
+use super::outcome::{planned_results, repo_errors, Outcome, PlannedRepo, RepoOutcome};
+use crate::meta::{ForestMeta, Pin, RepoMeta, META_FILENAME};
+use crate::paths::{ForestName, RepoName};
+
+pub struct PinInputs {
+    pub checkpoint: String,
+    /// Replace an existing checkpoint with the same name.
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PinResult {
+    pub forest_name: ForestName,
+    pub checkpoint: String,
+    pub created_at: DateTime<Utc>,
+    /// Whether an existing checkpoint with this name was overwritten.
+    pub replaced: bool,
+    /// Full commit SHA per repo name.
+    pub commits: BTreeMap<String, String>,
+    /// State the checkpoint cannot carry, such as uncommitted changes.
+    pub warnings: Vec<String>,
+}
+
+pub struct CheckoutPinInputs {
+    pub checkpoint: String,
+    /// Move each forest branch to the pinned commit instead of detaching HEAD.
+    pub on_branch: bool,
+    /// With `on_branch`, move branches even when that leaves commits behind.
+    pub force: bool,
+    pub dry_run: bool,
+}
+
+pub struct CheckoutPinPlan {
+    pub forest_name: ForestName,
+    pub checkpoint: String,
+    pub on_branch: bool,
+    pub repo_plans: Vec<CheckoutPinRepoPlan>,
+}
+
+pub struct CheckoutPinRepoPlan {
+    pub name: RepoName,
+    pub worktree: PathBuf,
+    pub branch: String,
+    pub commit: Option<String>,
+    pub action: CheckoutPinAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckoutPinAction {
+    Checkout,
+    Skip { reason: String },
+    Fail { error: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckoutPinResult {
+    pub forest_name: ForestName,
+    pub checkpoint: String,
+    pub on_branch: bool,
+    pub dry_run: bool,
+    pub repos: Vec<CheckoutPinRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckoutPinRepoResult {
+    pub name: RepoName,
+    pub branch: String,
+    /// Pinned commit; `None` when the repo was added after the checkpoint.
+    pub commit: Option<String>,
+    pub outcome: Outcome,
+}
+
+// --- pin ---
+
+pub fn cmd_pin(forest_dir: &Path, meta: &ForestMeta, inputs: PinInputs) -> Result<PinResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let checkpoint = inputs.checkpoint.trim().to_string();
+    ensure!(!checkpoint.is_empty(), "checkpoint name must not be empty");
+    let replaced = meta.pins.iter().any(|pin| pin.name == checkpoint);
+    if replaced && !inputs.force {
+        bail!(
+            "checkpoint {:?} already exists in forest {:?}\n  hint: use --force to replace it",
+            checkpoint,
+            meta.name.as_str()
+        );
+    }
+
+    let mut commits = BTreeMap::new();
+    let mut warnings = Vec::new();
+    for repo in &meta.repos {
+        let worktree = forest_dir.join(repo.name.as_str());
+        if !path_exists_or_symlink(&worktree) {
+            bail!(
+                "cannot pin {}: worktree missing at {}\n  hint: drop it with `{}` or restore the worktree",
+                repo.name,
+                worktree.display(),
+                drop_repo_command(meta.name.as_str(), repo.name.as_str())
+            );
+        }
+        let commit = crate::git::git(&worktree, &["rev-parse", "HEAD"])
+            .with_context(|| format!("failed to read the checked-out commit of {}", repo.name))?;
+        if has_tracked_changes(&worktree) {
+            warnings.push(format!(
+                "{}: uncommitted changes are not part of the checkpoint",
+                repo.name
+            ));
+        }
+        commits.insert(repo.name.to_string(), commit);
+    }
+
+    let pin = Pin {
+        name: checkpoint.clone(),
+        created_at: Utc::now(),
+        commits: commits.clone(),
+    };
+    let created_at = pin.created_at;
+    let mut updated = meta.clone();
+    updated.pins.retain(|p| p.name != checkpoint);
+    updated.pins.push(pin);
+    updated.write(&forest_dir.join(META_FILENAME))?;
+
+    Ok(PinResult {
+        forest_name: meta.name.clone(),
+        checkpoint,
+        created_at,
+        replaced,
+        commits,
+        warnings,
+    })
+}
+
+pub(super) fn has_tracked_changes(worktree: &Path) -> bool {
+    crate::git::git(worktree, &["status", "--porcelain", "--untracked-files=no"])
+        .is_ok_and(|out| !out.is_empty())
+}
+
+pub fn format_pin_human(result: &PinResult) -> String {
+    let mut lines = vec![format!(
+        "{} checkpoint {:?} in forest {:?}",
+        if result.replaced {
+            "Replaced"
+        } else {
+            "Pinned"
+        },
+        result.checkpoint,
+        result.forest_name.as_str()
+    )];
+    for (name, commit) in &result.commits {
+        lines.push(format!("  {}: {}", name, short_commit(commit)));
+    }
+    lines.join("\n")
+}
+
+// --- checkout-pin: planning (read-only) ---
+
+pub fn plan_checkout_pin(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: &CheckoutPinInputs,
+) -> Result<CheckoutPinPlan> {
+    let Some(pin) = meta.pins.iter().find(|pin| pin.name == inputs.checkpoint) else {
+        let names: Vec<&str> = meta.pins.iter().map(|pin| pin.name.as_str()).collect();
+        if names.is_empty() {
+            bail!(
+                "forest {:?} has no checkpoints\n  hint: record one with `git forest pin <checkpoint>`",
+                meta.name.as_str()
+            );
+        }
+        bail!(
+            "checkpoint {:?} not found in forest {:?}\n  hint: available checkpoints: {}",
+            inputs.checkpoint,
+            meta.name.as_str(),
+            names.join(", ")
+        );
+    };
+
+    let repo_plans = meta
+        .repos
+        .iter()
+        .map(|repo| plan_repo_checkout(forest_dir, repo, pin, inputs))
+        .collect();
+
+    Ok(CheckoutPinPlan {
+        forest_name: meta.name.clone(),
+        checkpoint: pin.name.clone(),
+        on_branch: inputs.on_branch,
+        repo_plans,
+    })
+}
+
+fn plan_repo_checkout(
+    forest_dir: &Path,
+    repo: &RepoMeta,
+    pin: &Pin,
+    inputs: &CheckoutPinInputs,
+) -> CheckoutPinRepoPlan {
+    let mut plan = CheckoutPinRepoPlan {
+        name: repo.name.clone(),
+        worktree: forest_dir.join(repo.name.as_str()),
+        branch: repo.branch.clone(),
+        commit: pin.commits.get(repo.name.as_str()).cloned(),
+        action: CheckoutPinAction::Checkout,
+    };
+    let fail = |error: String| CheckoutPinAction::Fail { error };
+
+    let Some(commit) = plan.commit.clone() else {
+        plan.action = CheckoutPinAction::Skip {
+            reason: "not in checkpoint".to_string(),
+        };
+        return plan;
+    };
+    if !path_exists_or_symlink(&plan.worktree) {
+        plan.action = fail(format!("worktree missing at {}", plan.worktree.display()));
+        return plan;
+    }
+    let object = format!("{}^{{commit}}", commit);
+    if crate::git::git(&plan.worktree, &["cat-file", "-e", &object]).is_err() {
+        plan.action = fail(format!(
+            "commit {} not found\n  hint: fetch it with `git fetch` in {}",
+            short_commit(&commit),
+            plan.worktree.display()
+        ));
+        return plan;
+    }
+    if has_tracked_changes(&plan.worktree) {
+        plan.action = fail(format!(
+            "uncommitted changes\n  hint: commit or stash them in {}",
+            plan.worktree.display()
+        ));
+        return plan;
+    }
+
+    let rev =
+        |r: &str| crate::git::git(&plan.worktree, &["rev-parse", "--verify", "--quiet", r]).ok();
+    let head = rev("HEAD");
+    if !inputs.on_branch {
+        let detached = crate::git::git(&plan.worktree, &["symbolic-ref", "-q", "HEAD"]).is_err();
+        if detached && head.as_deref() == Some(commit.as_str()) {
+            plan.action = CheckoutPinAction::Skip {
+                reason: "already at checkpoint".to_string(),
+            };
+        }
+        return plan;
+    }
+
+    let branch_ref = format!("refs/heads/{}", repo.branch);
+    let Some(tip) = rev(&branch_ref) else {
+        // `checkout -B` recreates the branch at the pinned commit
+        return plan;
+    };
+    let on_branch = crate::git::git(&plan.worktree, &["symbolic-ref", "-q", "HEAD"])
+        .is_ok_and(|head_ref| head_ref == branch_ref);
+    if tip == commit && on_branch {
+        plan.action = CheckoutPinAction::Skip {
+            reason: "already at checkpoint".to_string(),
+        };
+        return plan;
+    }
+    if !inputs.force {
+        let range = format!("{}..{}", commit, tip);
+        match crate::git::git(&plan.worktree, &["rev-list", "--count", &range]) {
+            Ok(count) if count == "0" => {}
+            Ok(count) => {
+                plan.action = fail(format!(
+                    "{} has {} commit(s) not in the checkpoint\n  hint: use --force to move the branch anyway, or omit --on-branch to detach",
+                    repo.branch, count
+                ));
+            }
+            Err(e) => plan.action = fail(compact_git_error(&e.to_string())),
+        }
+    }
+    plan
+}
+
+impl PlannedRepo for CheckoutPinRepoPlan {
+    type Outcome = Outcome;
+    type Result = CheckoutPinRepoResult;
+
+    fn planned_outcome(&self) -> Outcome {
+        match &self.action {
+            CheckoutPinAction::Checkout => Outcome::Success,
+            CheckoutPinAction::Skip { reason } => Outcome::Skipped {
+                reason: reason.clone(),
+            },
+            CheckoutPinAction::Fail { error } => Outcome::Failed {
+                error: error.clone(),
+            },
+        }
+    }
+
+    fn result(&self, outcome: Outcome) -> CheckoutPinRepoResult {
+        CheckoutPinRepoResult {
+            name: self.name.clone(),
+            branch: self.branch.clone(),
+            commit: self.commit.clone(),
+            outcome,
+        }
+    }
+}
+
+impl RepoOutcome for CheckoutPinRepoResult {
+    fn name(&self) -> &RepoName {
+        &self.name
+    }
+
+    fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+}
+
+fn finish(
+    plan: &CheckoutPinPlan,
+    dry_run: bool,
+    repos: Vec<CheckoutPinRepoResult>,
+) -> CheckoutPinResult {
+    CheckoutPinResult {
+        forest_name: plan.forest_name.clone(),
+        checkpoint: plan.checkpoint.clone(),
+        on_branch: plan.on_branch,
+        dry_run,
+        errors: repo_errors(&repos),
+        repos,
+    }
+}
+
+// --- checkout-pin: execution (impure) ---
+
+/// Check out every planned repo, continuing past failures (ADR 0009).
+///
+/// A checkpoint is only useful as a whole, so when any repo fails planning no
+/// repo is touched.
+pub fn execute_checkout_pin(plan: &CheckoutPinPlan) -> CheckoutPinResult {
+    let blocked = plan
+        .repo_plans
+        .iter()
+        .any(|rp| matches!(rp.action, CheckoutPinAction::Fail { .. }));
+    let repos = plan
+        .repo_plans
+        .iter()
+        .map(|rp| {
+            let outcome = match &rp.action {
+                CheckoutPinAction::Checkout if blocked => Outcome::Skipped {
+                    reason: "not attempted; other repos failed checks".to_string(),
+                },
+                CheckoutPinAction::Checkout => checkout_repo(rp, plan.on_branch),
+                _ => rp.planned_outcome(),
+            };
+            rp.result(outcome)
+        })
+        .collect();
+    finish(plan, false, repos)
+}
+
+fn checkout_repo(rp: &CheckoutPinRepoPlan, on_branch: bool) -> Outcome {
+    let commit = rp
+        .commit
+        .as_deref()
+        .expect("planned checkouts have a commit");
+    let args: Vec<&str> = if on_branch {
+        vec!["checkout", "-B", &rp.branch, commit]
+    } else {
+        vec!["checkout", "--detach", commit]
+    };
+    match crate::git::git(&rp.worktree, &args) {
+        Ok(_) => Outcome::Success,
+        Err(e) => Outcome::Failed {
+            error: format!(
+                "{}\n  hint: run `git {}` in {} to see the full error",
+                compact_git_error(&e.to_string()),
+                args.join(" "),
+                rp.worktree.display()
+            ),
+        },
+    }
+}
+
+pub fn cmd_checkout_pin(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: CheckoutPinInputs,
+) -> Result<CheckoutPinResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let plan = plan_checkout_pin(forest_dir, meta, &inputs)?;
+
+    if inputs.dry_run {
+        let repos = planned_results(&plan.repo_plans);
+        return Ok(finish(&plan, true, repos));
+    }
+
+    Ok(execute_checkout_pin(&plan))
+}
+
+pub fn format_checkout_pin_human(result: &CheckoutPinResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!(
+        "Checking out checkpoint {:?} in forest {:?}",
+        result.checkpoint,
+        result.forest_name.as_str()
+    ));
+
+    for repo in &result.repos {
+        let detail = match &repo.outcome {
+            Outcome::Success => {
+                let commit = short_commit(repo.commit.as_deref().unwrap_or_default());
+                match (result.dry_run, result.on_branch) {
+                    (true, true) => format!("would move {} to {}", repo.branch, commit),
+                    (true, false) => format!("would detach at {}", commit),
+                    (false, true) => format!("moved {} to {}", repo.branch, commit),
+                    (false, false) => format!("detached at {}", commit),
+                }
+            }
+            Outcome::Skipped { reason } => format!("skipped ({})", reason),
+            Outcome::Failed { .. } => "FAILED".to_string(),
+        };
+        lines.push(format!("  {}: {}", repo.name, detail));
+    }
+
+    let detached_any = result.repos.iter().any(|r| r.outcome == Outcome::Success);
+    if !result.on_branch && !result.dry_run && detached_any {
+        lines.push(String::new());
+        lines.push(
+            "Detached repos show as drifted in `git forest status` until their forest branch is checked out again."
+                .to_string(),
+        );
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::ForestMode;
+    use crate::testutil::{new_forest, TestEnv};
+
+    fn setup_forest(name: &str) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = new_forest(
+            &env,
+            name,
+            ForestMode::Feature,
+            &["foo-api", "foo-web"],
+            &[],
+        );
+        (env, forest_dir, meta)
+    }
+
+    fn commit(dir: &Path, message: &str) -> String {
+        crate::git::git(dir, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(dir, &["config", "user.email", "test@test.com"]).unwrap();
+        crate::git::git(dir, &["commit", "--allow-empty", "-m", message]).unwrap();
+        head(dir)
+    }
+
+    fn head(dir: &Path) -> String {
+        crate::git::git(dir, &["rev-parse", "HEAD"]).unwrap()
+    }
+
+    fn pin(forest_dir: &Path, meta: &ForestMeta, checkpoint: &str, force: bool) -> ForestMeta {
+        let inputs = PinInputs {
+            checkpoint: checkpoint.to_string(),
+            force,
+        };
+        cmd_pin(forest_dir, meta, inputs).unwrap();
+        ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap()
+    }
+
+    fn checkout_inputs(checkpoint: &str, on_branch: bool, force: bool) -> CheckoutPinInputs {
+        CheckoutPinInputs {
+            checkpoint: checkpoint.to_string(),
+            on_branch,
+            force,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn pin_records_head_commits_and_refuses_to_overwrite() {
+        let (_env, forest_dir, meta) = setup_forest("pin-record");
+        let api = forest_dir.join("foo-api");
+        let api_head = commit(&api, "work");
+        std::fs::write(forest_dir.join("foo-web").join("README"), "edit").unwrap();
+        crate::git::git(&forest_dir.join("foo-web"), &["add", "README"]).unwrap();
+
+        let result = cmd_pin(
+            &forest_dir,
+            &meta,
+            PinInputs {
+                checkpoint: "good".to_string(),
+                force: false,
+            },
+        )
+        .unwrap();
+        assert!(!result.replaced);
+        assert_eq!(result.commits["foo-api"], api_head);
+        assert_eq!(result.warnings.len(), 1, "warnings: {:?}", result.warnings);
+        assert!(result.warnings[0].starts_with("foo-web: uncommitted changes"));
+
+        let meta = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.pins.len(), 1);
+        assert_eq!(meta.pins[0].commits, result.commits);
+
+        let err = cmd_pin(
+            &forest_dir,
+            &meta,
+            PinInputs {
+                checkpoint: "good".to_string(),
+                force: false,
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("already exists"), "error: {}", err);
+
+        let new_head = commit(&api, "more work");
+        let meta = pin(&forest_dir, &meta, "good", true);
+        assert_eq!(meta.pins.len(), 1);
+        assert_eq!(meta.pins[0].commits["foo-api"], new_head);
+    }
+
+    #[test]
+    fn checkout_pin_detaches_every_repo_at_pinned_commit() {
+        let (_env, forest_dir, meta) = setup_forest("pin-detach");
+        let api = forest_dir.join("foo-api");
+        let pinned = commit(&api, "known good");
+        let meta = pin(&forest_dir, &meta, "good", false);
+        commit(&api, "experiment");
+
+        let result =
+            cmd_checkout_pin(&forest_dir, &meta, checkout_inputs("good", false, false)).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repos[0].outcome, Outcome::Success);
+        assert_eq!(head(&api), pinned);
+        assert!(crate::git::git(&api, &["symbolic-ref", "-q", "HEAD"]).is_err());
+        // The forest branch still points at the experiment
+        assert_ne!(
+            crate::git::git(&api, &["rev-parse", "testuser/pin-detach"]).unwrap(),
+            pinned
+        );
+
+        let again =
+            cmd_checkout_pin(&forest_dir, &meta, checkout_inputs("good", false, false)).unwrap();
+        assert_eq!(
+            again.repos[0].outcome,
+            Outcome::Skipped {
+                reason: "already at checkpoint".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn checkout_pin_on_branch_requires_force_to_leave_commits_behind() {
+        let (_env, forest_dir, meta) = setup_forest("pin-branch");
+        let api = forest_dir.join("foo-api");
+        let pinned = commit(&api, "known good");
+        let meta = pin(&forest_dir, &meta, "good", false);
+        commit(&api, "experiment");
+
+        let refused =
+            cmd_checkout_pin(&forest_dir, &meta, checkout_inputs("good", true, false)).unwrap();
+        assert_eq!(refused.errors.len(), 1, "errors: {:?}", refused.errors);
+        assert!(refused.errors[0].contains("1 commit(s) not in the checkpoint"));
+        assert_ne!(head(&api), pinned);
+
+        let forced =
+            cmd_checkout_pin(&forest_dir, &meta, checkout_inputs("good", true, true)).unwrap();
+        assert!(forced.errors.is_empty(), "errors: {:?}", forced.errors);
+        assert_eq!(head(&api), pinned);
+        assert_eq!(
+            crate::git::git(&api, &["symbolic-ref", "--short", "HEAD"]).unwrap(),
+            "testuser/pin-branch"
+        );
+        assert!(
+            format_checkout_pin_human(&forced).contains("foo-api: moved testuser/pin-branch to ")
+        );
+    }
+
+    #[test]
+    fn checkout_pin_touches_nothing_when_a_repo_is_dirty() {
+        let (_env, forest_dir, meta) = setup_forest("pin-dirty");
+        let api = forest_dir.join("foo-api");
+        let pinned = commit(&api, "known good");
+        let meta = pin(&forest_dir, &meta, "good", false);
+        commit(&api, "experiment");
+        let web = forest_dir.join("foo-web");
+        std::fs::write(web.join("README"), "edit").unwrap();
+        crate::git::git(&web, &["add", "README"]).unwrap();
+
+        let result =
+            cmd_checkout_pin(&forest_dir, &meta, checkout_inputs("good", false, false)).unwrap();
+        assert_eq!(result.errors.len(), 1, "errors: {:?}", result.errors);
+        assert!(result.errors[0].starts_with("foo-web: uncommitted changes"));
+        assert_eq!(
+            result.repos[0].outcome,
+            Outcome::Skipped {
+                reason: "not attempted; other repos failed checks".to_string()
+            }
+        );
+        assert_ne!(head(&api), pinned);
+
+        let err = cmd_checkout_pin(&forest_dir, &meta, checkout_inputs("bad", false, false))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("available checkpoints: good"),
+            "error: {}",
+            err
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{
+    base_ref, compact_git_error, path_exists_or_symlink, WorktreeBranchState,
+};
+use super::outcome::{planned_results, repo_errors, Outcome, PlannedRepo, RepoOutcome};
+use super::status::parse_left_right_counts;
+use crate::meta::{ForestMeta, ForestMode, RepoMeta};
+use crate::paths::{ForestName, RepoName};
+
+pub struct PushInputs {
+    /// Also push the `forest/*` branches that review mode creates.
+    pub allow_scratch: bool,
+    pub dry_run: bool,
+}
+
+pub struct PushPlan {
+    pub forest_name: ForestName,
+    pub repo_plans: Vec<PushRepoPlan>,
+}
+
+pub struct PushRepoPlan {
+    pub name: RepoName,
+    pub worktree: PathBuf,
+    pub branch: String,
+    pub remote: Option<String>,
+    /// Commits on the branch that are not on its base.
+    pub ahead: usize,
+    pub action: PushAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushAction {
+    Push,
+    Skip { reason: String },
+    Fail { error: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct PushResult {
+    pub forest_name: ForestName,
+    pub dry_run: bool,
+    pub repos: Vec<PushRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PushRepoResult {
+    pub name: RepoName,
+    pub branch: String,
+    pub remote: Option<String>,
+    pub ahead: usize,
+    pub outcome: Outcome,
+}
+
+// --- Planning (read-only) ---
+
+pub fn plan_push(forest_dir: &Path, meta: &ForestMeta, inputs: &PushInputs) -> PushPlan {
+    let repo_plans = meta
+        .repos
+        .iter()
+        .map(|repo| plan_repo_push(forest_dir, &meta.mode, repo, inputs.allow_scratch))
+        .collect();
+
+    PushPlan {
+        forest_name: meta.name.clone(),
+        repo_plans,
+    }
+}
+
+fn plan_repo_push(
+    forest_dir: &Path,
+    mode: &ForestMode,
+    repo: &RepoMeta,
+    allow_scratch: bool,
+) -> PushRepoPlan {
+    let mut plan = PushRepoPlan {
+        name: repo.name.clone(),
+        worktree: forest_dir.join(repo.name.as_str()),
+        branch: repo.branch.clone(),
+        remote: repo.remote.clone(),
+        ahead: 0,
+        action: PushAction::Push,
+    };
+    let skip = |reason: &str| PushAction::Skip {
+        reason: reason.to_string(),
+    };
+
+    if !path_exists_or_symlink(&plan.worktree) {
+        plan.action = skip("worktree missing");
+        return plan;
+    }
+    let branch_state = WorktreeBranchState::read(&plan.worktree, &repo.branch);
+    if let Some(message) = branch_state
+        .drift_message()
+        .or_else(|| branch_state.lookup_error_message())
+    {
+        plan.action = PushAction::Skip { reason: message };
+        return plan;
+    }
+    let Some(remote) = &repo.remote else {
+        plan.action = skip("no remote recorded");
+        return plan;
+    };
+    // Review mode checks PRs out onto throwaway forest/<name> branches
+    if *mode == ForestMode::Review && repo.branch_created && !allow_scratch {
+        plan.action = skip("review scratch branch; use --allow-scratch");
+        return plan;
+    }
+
+    let base_ref = match base_ref(&plan.worktree, Some(remote), &repo.base_branch) {
+        Some(base_ref) => base_ref,
+        None => {
+            plan.action = PushAction::Fail {
+                error: format!(
+                    "base branch {} not found locally or on {}",
+                    repo.base_branch, remote
+                ),
+            };
+            return plan;
+        }
+    };
+    let range = format!("{}...HEAD", base_ref);
+    match crate::git::git(
+        &plan.worktree,
+        &["rev-list", "--left-right", "--count", &range],
+    )
+    .map(|counts| parse_left_right_counts(&counts))
+    {
+        Ok(Some((_, ahead))) => plan.ahead = ahead,
+        Ok(None) => {
+            plan.action = PushAction::Fail {
+                error: format!("could not count commits against {}", base_ref),
+            };
+            return plan;
+        }
+        Err(e) => {
+            plan.action = PushAction::Fail {
+                error: compact_git_error(&e.to_string()),
+            };
+            return plan;
+        }
+    }
+    if plan.ahead == 0 {
+        plan.action = skip("no commits beyond base branch");
+        return plan;
+    }
+
+    if is_published(&plan.worktree, remote, &repo.branch) {
+        plan.action = skip("already up to date on remote");
+    }
+    plan
+}
+
+fn is_published(worktree: &Path, remote: &str, branch: &str) -> bool {
+    let remote_ref = format!("refs/remotes/{}/{}", remote, branch);
+    let rev = |r: &str| crate::git::git(worktree, &["rev-parse", "--verify", "--quiet", r]).ok();
+    match (rev(&remote_ref), rev("HEAD")) {
+        (Some(remote_sha), Some(head)) => remote_sha == head,
+        _ => false,
+    }
+}
+
+impl PlannedRepo for PushRepoPlan {
+    type Outcome = Outcome;
+    type Result = PushRepoResult;
+
+    fn planned_outcome(&self) -> Outcome {
+        match &self.action {
+            PushAction::Push => Outcome::Success,
+            PushAction::Skip { reason } => Outcome::Skipped {
+                reason: reason.clone(),
+            },
+            PushAction::Fail { error } => Outcome::Failed {
+                error: error.clone(),
+            },
+        }
+    }
+
+    fn result(&self, outcome: Outcome) -> PushRepoResult {
+        PushRepoResult {
+            name: self.name.clone(),
+            branch: self.branch.clone(),
+            remote: self.remote.clone(),
+            ahead: self.ahead,
+            outcome,
+        }
+    }
+}
+
+impl RepoOutcome for PushRepoResult {
+    fn name(&self) -> &RepoName {
+        &self.name
+    }
+
+    fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+}
+
+fn finish(plan: &PushPlan, dry_run: bool, repos: Vec<PushRepoResult>) -> PushResult {
+    PushResult {
+        forest_name: plan.forest_name.clone(),
+        dry_run,
+        errors: repo_errors(&repos),
+        repos,
+    }
+}
+
+// --- Execution (impure) ---
+
+/// Push every planned repo, continuing past failures (ADR 0009).
+pub fn execute_push(plan: &PushPlan) -> PushResult {
+    let repos = plan
+        .repo_plans
+        .iter()
+        .map(|rp| match &rp.action {
+            PushAction::Push => rp.result(push_repo(rp)),
+            _ => rp.planned_result(),
+        })
+        .collect();
+    finish(plan, false, repos)
+}
+
+fn push_repo(rp: &PushRepoPlan) -> Outcome {
+    let remote = rp.remote.as_deref().expect("planned pushes have a remote");
+    match crate::git::git(
+        &rp.worktree,
+        &["push", "--set-upstream", remote, &rp.branch],
+    ) {
+        Ok(_) => Outcome::Success,
+        Err(e) => Outcome::Failed {
+            error: format!(
+                "{}\n  hint: run `git push --set-upstream {} {}` in {} to see the full error",
+                compact_git_error(&e.to_string()),
+                remote,
+                rp.branch,
+                rp.worktree.display()
+            ),
+        },
+    }
+}
+
+pub fn cmd_push(forest_dir: &Path, meta: &ForestMeta, inputs: PushInputs) -> Result<PushResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let plan = plan_push(forest_dir, meta, &inputs);
+
+    if inputs.dry_run {
+        let repos = planned_results(&plan.repo_plans);
+        return Ok(finish(&plan, true, repos));
+    }
+
+    Ok(execute_push(&plan))
+}
+
+pub fn format_push_human(result: &PushResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!("Pushing forest {:?}", result.forest_name.as_str()));
+
+    for repo in &result.repos {
+        let detail = match &repo.outcome {
+            Outcome::Success => format!(
+                "{} {} to {} ({} {})",
+                if result.dry_run {
+                    "would push"
+                } else {
+                    "pushed"
+                },
+                repo.branch,
+                repo.remote.as_deref().unwrap_or_default(),
+                repo.ahead,
+                if repo.ahead == 1 { "commit" } else { "commits" }
+            ),
+            Outcome::Skipped { reason } => format!("skipped ({})", reason),
+            Outcome::Failed { .. } => "FAILED".to_string(),
+        };
+        lines.push(format!("  {}: {}", repo.name, detail));
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{new_forest, TestEnv};
+
+    fn setup_forest(name: &str, mode: ForestMode) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = new_forest(&env, name, mode, &["foo-api", "foo-web"], &[]);
+        (env, forest_dir, meta)
+    }
+
+    fn commit_file(dir: &Path, file: &str) {
+        crate::git::git(dir, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(dir, &["config", "user.email", "test@test.com"]).unwrap();
+        std::fs::write(dir.join(file), file).unwrap();
+        crate::git::git(dir, &["add", file]).unwrap();
+        crate::git::git(dir, &["commit", "-m", file]).unwrap();
+    }
+
+    fn inputs(dry_run: bool) -> PushInputs {
+        PushInputs {
+            allow_scratch: false,
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn push_publishes_changed_repos_and_skips_untouched() {
+        let (_env, forest_dir, meta) = setup_forest("push-feature", ForestMode::Feature);
+        let api = forest_dir.join("foo-api");
+        commit_file(&api, "feature.txt");
+
+        let dry = cmd_push(&forest_dir, &meta, inputs(true)).unwrap();
+        assert_eq!(dry.repos[0].outcome, Outcome::Success);
+        assert_eq!(dry.repos[0].ahead, 1);
+        assert_eq!(
+            dry.repos[1].outcome,
+            Outcome::Skipped {
+                reason: "no commits beyond base branch".to_string()
+            }
+        );
+        let human = format_push_human(&dry);
+        assert!(human.contains("would push testuser/push-feature to origin (1 commit)"));
+        assert!(crate::git::git(&api, &["rev-parse", "--abbrev-ref", "@{upstream}"]).is_err());
+
+        let result = cmd_push(&forest_dir, &meta, inputs(false)).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repos[0].outcome, Outcome::Success);
+        assert_eq!(
+            crate::git::git(&api, &["rev-parse", "--abbrev-ref", "@{upstream}"]).unwrap(),
+            "origin/testuser/push-feature"
+        );
+
+        let again = cmd_push(&forest_dir, &meta, inputs(false)).unwrap();
+        assert_eq!(
+            again.repos[0].outcome,
+            Outcome::Skipped {
+                reason: "already up to date on remote".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn push_refuses_review_scratch_branches_unless_allowed() {
+        let (_env, forest_dir, meta) = setup_forest("push-review", ForestMode::Review);
+        commit_file(&forest_dir.join("foo-api"), "notes.txt");
+
+        let result = cmd_push(&forest_dir, &meta, inputs(true)).unwrap();
+        assert_eq!(
+            result.repos[0].outcome,
+            Outcome::Skipped {
+                reason: "review scratch branch; use --allow-scratch".to_string()
+            }
+        );
+
+        let allowed = cmd_push(
+            &forest_dir,
+            &meta,
+            PushInputs {
+                allow_scratch: true,
+                dry_run: false,
+            },
+        )
+        .unwrap();
+        assert!(allowed.errors.is_empty(), "errors: {:?}", allowed.errors);
+        assert_eq!(allowed.repos[0].outcome, Outcome::Success);
+        assert_eq!(allowed.repos[0].branch, "forest/push-review");
+    }
+
+    #[test]
+    fn push_failure_is_reported_and_others_continue() {
+        let (_env, forest_dir, meta) = setup_forest("push-fail", ForestMode::Feature);
+        let api = forest_dir.join("foo-api");
+        let web = forest_dir.join("foo-web");
+        commit_file(&api, "a.txt");
+        commit_file(&web, "b.txt");
+        crate::git::git(
+            &api,
+            &["remote", "set-url", "origin", "/nonexistent/remote"],
+        )
+        .unwrap();
+
+        let result = cmd_push(&forest_dir, &meta, inputs(false)).unwrap();
+        assert!(matches!(result.repos[0].outcome, Outcome::Failed { .. }));
+        assert_eq!(result.repos[1].outcome, Outcome::Success);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].starts_with("foo-api: "));
+        assert!(result.errors[0].contains("hint: run `git push --set-upstream origin"));
+    }
+}
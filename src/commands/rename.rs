@@ -0,0 +1,524 @@
+use anyhow::{bail, ensure, Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{path_exists_or_symlink, WorktreeBranchState};
+use super::drop_repo::drop_repo_command;
+use crate::meta::{ForestMeta, ForestMode, RepoMeta, META_FILENAME};
+use crate::paths::{forest_dir, AbsolutePath, BranchName, ForestName, RepoName};
+
+pub struct RenameInputs {
+    pub new_name: String,
+    /// Rename forest-created branches to match the feature branch template.
+    pub rename_branches: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Debug)]
+pub struct RenamePlan {
+    pub old_name: ForestName,
+    pub new_name: ForestName,
+    pub old_dir: PathBuf,
+    pub new_dir: AbsolutePath,
+    /// Metadata to carry over, renamed, with the repos already moved by an
+    /// interrupted rename.
+    pub new_meta: ForestMeta,
+    pub repo_plans: Vec<RenameRepoPlan>,
+}
+
+#[derive(Debug)]
+pub struct RenameRepoPlan {
+    pub repo: RepoMeta,
+    pub old_path: PathBuf,
+    pub new_path: PathBuf,
+    /// The worktree is already at `new_path` (resuming an interrupted rename).
+    pub already_moved: bool,
+    pub new_branch: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenameResult {
+    pub old_name: ForestName,
+    pub new_name: ForestName,
+    pub old_dir: PathBuf,
+    pub new_dir: AbsolutePath,
+    pub dry_run: bool,
+    pub repos: Vec<RenameRepoResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenameRepoResult {
+    pub name: RepoName,
+    pub worktree_path: PathBuf,
+    pub branch: String,
+    /// Set when the branch is (or would be) renamed.
+    pub new_branch: Option<String>,
+}
+
+// --- Planning (read-only) ---
+
+pub fn plan_rename(
+    forest_dir_path: &Path,
+    meta: &ForestMeta,
+    inputs: &RenameInputs,
+    feature_branch_template: Option<&str>,
+) -> Result<RenamePlan> {
+    let new_name = ForestName::new(inputs.new_name.clone())?;
+    ensure!(
+        new_name != meta.name,
+        "forest is already named {:?}",
+        meta.name.as_str()
+    );
+    let worktree_base = forest_dir_path
+        .parent()
+        .and_then(|parent| AbsolutePath::new(parent.to_path_buf()))
+        .context("forest directory has no parent")?;
+    let new_dir = forest_dir(&worktree_base, &new_name);
+    ensure!(
+        *new_dir != *forest_dir_path,
+        "{:?} and {:?} map to the same directory {}\n  hint: pick a name that differs in more than punctuation",
+        meta.name.as_str(),
+        new_name.as_str(),
+        new_dir.display()
+    );
+
+    let mut new_meta = meta.clone();
+    new_meta.name = new_name.clone();
+    new_meta.repos = resumed_repos(meta, &new_dir)?;
+    if new_meta.repos.is_empty() && !new_dir.exists() {
+        if let Some((existing_dir, existing_meta)) =
+            crate::forest::find_forest(&worktree_base, new_name.as_str())?
+        {
+            bail!(
+                "forest name {:?} collides with existing forest {:?} at {}\n  hint: choose a different name",
+                new_name.as_str(),
+                existing_meta.name.as_str(),
+                existing_dir.display()
+            );
+        }
+    }
+
+    let branch_template = if inputs.rename_branches {
+        ensure!(
+            meta.mode == ForestMode::Feature,
+            "--rename-branches only applies to feature forests\n  hint: review forests use forest/<name> scratch branches"
+        );
+        Some(feature_branch_template.context("--rename-branches needs a feature branch template")?)
+    } else {
+        None
+    };
+
+    let mut repo_plans = Vec::new();
+    let pending = meta
+        .repos
+        .iter()
+        .filter(|repo| !new_meta.repos.iter().any(|moved| moved.name == repo.name));
+    for repo in pending {
+        repo_plans.push(plan_repo_rename(
+            forest_dir_path,
+            &new_dir,
+            meta.name.as_str(),
+            repo,
+            branch_template.map(|t| (t, meta.name.as_str(), new_name.as_str())),
+        )?);
+    }
+
+    Ok(RenamePlan {
+        old_name: meta.name.clone(),
+        new_name,
+        old_dir: forest_dir_path.to_path_buf(),
+        new_dir,
+        new_meta,
+        repo_plans,
+    })
+}
+
+/// Repos an interrupted rename already recorded under `new_dir`.
+///
+/// The target directory is only reused when it is empty or holds metadata
+/// from the same forest (same `created_at`).
+fn resumed_repos(meta: &ForestMeta, new_dir: &Path) -> Result<Vec<RepoMeta>> {
+    if !new_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let meta_path = new_dir.join(META_FILENAME);
+    if meta_path.exists() {
+        if let Ok(existing) = ForestMeta::read(&meta_path) {
+            if existing.created_at == meta.created_at {
+                return Ok(existing.repos);
+            }
+        }
+    } else if std::fs::read_dir(new_dir).is_ok_and(|mut entries| entries.next().is_none()) {
+        return Ok(Vec::new());
+    }
+    bail!(
+        "forest directory already exists: {}\n  hint: choose a different name, or remove the existing forest with `git forest rm`",
+        new_dir.display()
+    );
+}
+
+fn plan_repo_rename(
+    old_dir: &Path,
+    new_dir: &AbsolutePath,
+    old_name: &str,
+    repo: &RepoMeta,
+    branch_template: Option<(&str, &str, &str)>,
+) -> Result<RenameRepoPlan> {
+    let old_path = old_dir.join(repo.name.as_str());
+    let new_path = new_dir.join(repo.name.as_str()).into_inner();
+    let already_moved = !path_exists_or_symlink(&old_path) && new_path.is_dir();
+    if !already_moved {
+        ensure!(
+            path_exists_or_symlink(&old_path),
+            "cannot rename: worktree for {} missing at {}\n  hint: drop it with `{}` or restore the worktree",
+            repo.name,
+            old_path.display(),
+            drop_repo_command(old_name, repo.name.as_str())
+        );
+        ensure!(
+            !path_exists_or_symlink(&new_path),
+            "cannot rename: {} already exists",
+            new_path.display()
+        );
+    }
+
+    let mut new_branch = None;
+    if let Some((template, old_name, new_name)) = branch_template {
+        // Only branches the forest created from the template follow the new name
+        if repo.branch_created && repo.branch == template.replace("{name}", old_name) {
+            let branch = template.replace("{name}", new_name);
+            BranchName::new(branch.clone(), repo.remote.as_deref().unwrap_or("origin"))?;
+            let exists =
+                |b: &str| crate::git::ref_exists(&repo.source, &format!("refs/heads/{}", b));
+            let (old_exists, new_exists) = (exists(&repo.branch)?, exists(&branch)?);
+            let worktree = if already_moved { &new_path } else { &old_path };
+            let state = WorktreeBranchState::read(worktree, &repo.branch);
+            if !old_exists && new_exists {
+                // Renamed by an interrupted run; only the metadata is behind
+            } else if new_exists {
+                bail!(
+                    "cannot rename branch {} of {}: {} already exists in {}\n  hint: rename or delete it, or rename without --rename-branches",
+                    repo.branch,
+                    repo.name,
+                    branch,
+                    repo.source.display()
+                );
+            } else if let Some(message) = state.drift_message() {
+                bail!(
+                    "cannot rename branch of {}: {}\n  hint: check out {} in {} first, or rename without --rename-branches",
+                    repo.name,
+                    message,
+                    repo.branch,
+                    worktree.display()
+                );
+            }
+            new_branch = Some(branch);
+        }
+    }
+
+    Ok(RenameRepoPlan {
+        repo: repo.clone(),
+        old_path,
+        new_path,
+        already_moved,
+        new_branch,
+    })
+}
+
+fn plan_to_result(plan: &RenamePlan, dry_run: bool) -> RenameResult {
+    let repos = plan
+        .new_meta
+        .repos
+        .iter()
+        .map(|repo| RenameRepoResult {
+            name: repo.name.clone(),
+            worktree_path: plan.new_dir.join(repo.name.as_str()).into_inner(),
+            branch: repo.branch.clone(),
+            new_branch: None,
+        })
+        .chain(plan.repo_plans.iter().map(|rp| RenameRepoResult {
+            name: rp.repo.name.clone(),
+            worktree_path: rp.new_path.clone(),
+            branch: rp.repo.branch.clone(),
+            new_branch: rp.new_branch.clone(),
+        }))
+        .collect();
+
+    RenameResult {
+        old_name: plan.old_name.clone(),
+        new_name: plan.new_name.clone(),
+        old_dir: plan.old_dir.clone(),
+        new_dir: plan.new_dir.clone(),
+        dry_run,
+        repos,
+    }
+}
+
+// --- Execution (impure) ---
+
+/// Move each worktree into the new forest directory, recording every move in
+/// both metadata files as it lands (ADR 0011).
+///
+/// At every step the old and new directories are each a valid forest holding
+/// the repos not yet moved and the repos already moved. Re-running the same
+/// rename picks up where an interrupted one stopped.
+pub fn execute_rename(plan: &RenamePlan) -> Result<RenameResult> {
+    let resume_hint = || {
+        format!(
+            "rename of {:?} to {:?} incomplete\n  hint: fix the error above and re-run `git forest rename {} {}` to finish",
+            plan.old_name.as_str(),
+            plan.new_name.as_str(),
+            plan.old_name,
+            plan.new_name
+        )
+    };
+
+    std::fs::create_dir_all(&plan.new_dir)
+        .with_context(|| format!("failed to create {}", plan.new_dir.display()))?;
+    let new_meta_path = plan.new_dir.join(META_FILENAME);
+    let old_meta_path = plan.old_dir.join(META_FILENAME);
+    let mut new_meta = plan.new_meta.clone();
+    new_meta.write(&new_meta_path)?;
+
+    let mut old_meta = plan.new_meta.clone();
+    old_meta.name = plan.old_name.clone();
+    old_meta.repos = plan.repo_plans.iter().map(|rp| rp.repo.clone()).collect();
+
+    for rp in &plan.repo_plans {
+        move_repo(rp).with_context(resume_hint)?;
+
+        let mut moved = rp.repo.clone();
+        if let Some(branch) = &rp.new_branch {
+            moved.branch = branch.clone();
+        }
+        new_meta.repos.push(moved);
+        new_meta.write(&new_meta_path).with_context(resume_hint)?;
+        old_meta.repos.retain(|r| r.name != rp.repo.name);
+        old_meta.write(&old_meta_path).with_context(resume_hint)?;
+    }
+
+    // Everything left is forest-root content such as disposable entries
+    for entry in std::fs::read_dir(&plan.old_dir)
+        .with_context(|| format!("failed to read {}", plan.old_dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name() == META_FILENAME {
+            continue;
+        }
+        let dest = plan.new_dir.join(entry.file_name());
+        std::fs::rename(entry.path(), &dest)
+            .with_context(|| {
+                format!(
+                    "failed to move {} to {}",
+                    entry.path().display(),
+                    dest.display()
+                )
+            })
+            .with_context(resume_hint)?;
+    }
+    std::fs::remove_file(&old_meta_path)
+        .with_context(|| format!("failed to remove {}", old_meta_path.display()))?;
+    std::fs::remove_dir(&plan.old_dir)
+        .with_context(|| format!("failed to remove {}", plan.old_dir.display()))?;
+
+    Ok(plan_to_result(plan, false))
+}
+
+fn move_repo(rp: &RenameRepoPlan) -> Result<()> {
+    let source = &rp.repo.source;
+    if !rp.already_moved {
+        let old = rp.old_path.to_string_lossy();
+        let new = rp.new_path.to_string_lossy();
+        crate::git::git(source, &["worktree", "move", &old, &new])
+            .with_context(|| format!("failed to move worktree for {}", rp.repo.name))?;
+    }
+    if let Some(branch) = &rp.new_branch {
+        // Resuming: the branch may already carry its new name
+        let old_ref = format!("refs/heads/{}", rp.repo.branch);
+        if crate::git::ref_exists(source, &old_ref)? {
+            crate::git::git(source, &["branch", "-m", &rp.repo.branch, branch])
+                .with_context(|| format!("failed to rename branch for {}", rp.repo.name))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn cmd_rename(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: RenameInputs,
+    feature_branch_template: Option<&str>,
+) -> Result<RenameResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let plan = plan_rename(forest_dir, meta, &inputs, feature_branch_template)?;
+
+    if inputs.dry_run {
+        return Ok(plan_to_result(&plan, true));
+    }
+
+    execute_rename(&plan)
+}
+
+pub fn format_rename_human(result: &RenameResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!(
+        "{} forest {:?} to {:?}",
+        if result.dry_run {
+            "Would rename"
+        } else {
+            "Renamed"
+        },
+        result.old_name.as_str(),
+        result.new_name.as_str()
+    ));
+    lines.push(format!("  {}", result.new_dir.display()));
+    lines.push(String::new());
+
+    for repo in &result.repos {
+        match &repo.new_branch {
+            Some(new_branch) => lines.push(format!(
+                "  {} → {} (was {})",
+                repo.name, new_branch, repo.branch
+            )),
+            None => lines.push(format!("  {} → {}", repo.name, repo.branch)),
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{new_forest, TestEnv};
+
+    fn setup_forest(name: &str) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = new_forest(
+            &env,
+            name,
+            ForestMode::Feature,
+            &["foo-api", "foo-web"],
+            &[("foo-web", "custom/web")],
+        );
+        (env, forest_dir, meta)
+    }
+
+    fn inputs(new_name: &str, rename_branches: bool, dry_run: bool) -> RenameInputs {
+        RenameInputs {
+            new_name: new_name.to_string(),
+            rename_branches,
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn rename_moves_worktrees_and_template_branches() {
+        let (env, old_dir, meta) = setup_forest("java-48");
+        std::fs::create_dir(old_dir.join(".idea")).unwrap();
+
+        let result = cmd_rename(
+            &old_dir,
+            &meta,
+            inputs("java-84", true, false),
+            Some("testuser/{name}"),
+        )
+        .unwrap();
+
+        let new_dir = env.worktree_base().join("java-84");
+        assert_eq!(result.new_dir, new_dir);
+        assert!(!old_dir.exists());
+        assert!(new_dir.join(".idea").is_dir());
+        let meta = ForestMeta::read(&new_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(meta.name.as_str(), "java-84");
+        assert_eq!(meta.repos[0].branch, "testuser/java-84");
+        // --repo-branch overrides don't follow the template, so they keep their name
+        assert_eq!(meta.repos[1].branch, "custom/web");
+
+        let api = new_dir.join("foo-api");
+        assert_eq!(
+            crate::git::git(&api, &["symbolic-ref", "--short", "HEAD"]).unwrap(),
+            "testuser/java-84"
+        );
+        // The source repo's worktree registration follows the move
+        let worktrees = crate::git::git(
+            &env.repo_path("foo-api"),
+            &["worktree", "list", "--porcelain"],
+        )
+        .unwrap();
+        assert!(worktrees.contains(&format!("worktree {}", api.display())));
+        assert!(format_rename_human(&result)
+            .contains("foo-api → testuser/java-84 (was testuser/java-48)"));
+    }
+
+    #[test]
+    fn rename_dry_run_changes_nothing() {
+        let (env, old_dir, meta) = setup_forest("dry-old");
+
+        let result = cmd_rename(&old_dir, &meta, inputs("dry-new", false, true), None).unwrap();
+        assert!(result.dry_run);
+        assert_eq!(result.repos[0].new_branch, None);
+        assert!(old_dir.join(META_FILENAME).exists());
+        assert!(!env.worktree_base().join("dry-new").exists());
+    }
+
+    #[test]
+    fn rename_resumes_after_interruption() {
+        let (env, old_dir, meta) = setup_forest("resume-old");
+        let plan = plan_rename(&old_dir, &meta, &inputs("resume-new", false, false), None).unwrap();
+
+        // Simulate a crash after moving foo-api and recording it in the new forest
+        std::fs::create_dir_all(&plan.new_dir).unwrap();
+        move_repo(&plan.repo_plans[0]).unwrap();
+        let mut partial = plan.new_meta.clone();
+        partial.repos.push(plan.repo_plans[0].repo.clone());
+        partial.write(&plan.new_dir.join(META_FILENAME)).unwrap();
+
+        cmd_rename(&old_dir, &meta, inputs("resume-new", false, false), None).unwrap();
+
+        let new_dir = env.worktree_base().join("resume-new");
+        let meta = ForestMeta::read(&new_dir.join(META_FILENAME)).unwrap();
+        let names: Vec<&str> = meta.repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["foo-api", "foo-web"]);
+        assert!(new_dir.join("foo-web").is_dir());
+        assert!(!old_dir.exists());
+    }
+
+    #[test]
+    fn rename_refuses_existing_target_and_review_branch_renames() {
+        let (env, old_dir, meta) = setup_forest("taken-old");
+        std::fs::create_dir_all(env.worktree_base().join("taken-new")).unwrap();
+        std::fs::write(env.worktree_base().join("taken-new").join("notes.txt"), "").unwrap();
+
+        let err = cmd_rename(&old_dir, &meta, inputs("taken-new", false, false), None)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("forest directory already exists"),
+            "error: {}",
+            err
+        );
+
+        let mut review = meta.clone();
+        review.mode = ForestMode::Review;
+        let err = cmd_rename(
+            &old_dir,
+            &review,
+            inputs("other-new", true, false),
+            Some("testuser/{name}"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("only applies to feature forests"),
+            "error: {}",
+            err
+        );
+    }
+}
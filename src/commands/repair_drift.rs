@@ -0,0 +1,438 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{compact_git_error, ActualBranchState, WorktreeBranchState};
+use super::outcome::{planned_results, repo_errors, Outcome, PlannedRepo, RepoOutcome};
+use super::pin::has_tracked_changes;
+use crate::meta::{ForestMeta, RepoMeta, META_FILENAME};
+use crate::paths::{ForestName, RepoName};
+
+pub struct RepairDriftInputs {
+    /// Record each drifted worktree's actual branch instead of switching back.
+    pub adopt: bool,
+    pub dry_run: bool,
+}
+
+pub struct RepairDriftPlan {
+    pub forest_name: ForestName,
+    pub adopt: bool,
+    pub repo_plans: Vec<RepairDriftRepoPlan>,
+}
+
+pub struct RepairDriftRepoPlan {
+    pub name: RepoName,
+    pub worktree: PathBuf,
+    pub branch_state: WorktreeBranchState,
+    pub action: RepairDriftAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepairDriftAction {
+    /// Check the recorded branch out again.
+    Switch,
+    /// Record `branch` in metadata as a branch the forest did not create.
+    Adopt {
+        branch: String,
+    },
+    Skip {
+        reason: String,
+    },
+    Fail {
+        error: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairDriftResult {
+    pub forest_name: ForestName,
+    pub adopt: bool,
+    pub dry_run: bool,
+    pub repos: Vec<RepairDriftRepoResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairDriftRepoResult {
+    pub name: RepoName,
+    /// State before the repair; `expected_branch` is the recorded branch.
+    pub branch_state: WorktreeBranchState,
+    pub outcome: Outcome,
+}
+
+// --- Planning (read-only) ---
+
+pub fn plan_repair_drift(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: &RepairDriftInputs,
+) -> RepairDriftPlan {
+    let repo_plans = meta
+        .repos
+        .iter()
+        .map(|repo| plan_repo(forest_dir, repo, inputs.adopt))
+        .collect();
+
+    RepairDriftPlan {
+        forest_name: meta.name.clone(),
+        adopt: inputs.adopt,
+        repo_plans,
+    }
+}
+
+fn plan_repo(forest_dir: &Path, repo: &RepoMeta, adopt: bool) -> RepairDriftRepoPlan {
+    let worktree = forest_dir.join(repo.name.as_str());
+    let branch_state = WorktreeBranchState::read(&worktree, &repo.branch);
+    let action = plan_action(&worktree, repo, &branch_state, adopt);
+    RepairDriftRepoPlan {
+        name: repo.name.clone(),
+        worktree,
+        branch_state,
+        action,
+    }
+}
+
+fn plan_action(
+    worktree: &Path,
+    repo: &RepoMeta,
+    branch_state: &WorktreeBranchState,
+    adopt: bool,
+) -> RepairDriftAction {
+    let skip = |reason: &str| RepairDriftAction::Skip {
+        reason: reason.to_string(),
+    };
+    let fail = |error: String| RepairDriftAction::Fail { error };
+
+    if let Some(message) = branch_state.lookup_error_message() {
+        return fail(message);
+    }
+    match &branch_state.actual {
+        ActualBranchState::MissingWorktree => return skip("worktree missing"),
+        _ if !branch_state.branch_drift => return skip("not drifted"),
+        _ => {}
+    }
+
+    if adopt {
+        return match &branch_state.actual {
+            ActualBranchState::Branch { actual_branch } => RepairDriftAction::Adopt {
+                branch: actual_branch.clone(),
+            },
+            _ => fail(format!(
+                "{} has no branch to adopt\n  hint: check out a branch in {}, or omit --adopt to switch back to {}",
+                branch_state.actual_display(),
+                worktree.display(),
+                repo.branch
+            )),
+        };
+    }
+
+    let branch_ref = format!("refs/heads/{}", repo.branch);
+    match crate::git::ref_exists(worktree, &branch_ref) {
+        Ok(true) => {}
+        Ok(false) => {
+            return fail(format!(
+                "branch {} no longer exists\n  hint: use --adopt to record the checked-out branch instead",
+                repo.branch
+            ));
+        }
+        Err(e) => return fail(compact_git_error(&e.to_string())),
+    }
+    if has_tracked_changes(worktree) {
+        return fail(format!(
+            "uncommitted changes\n  hint: commit or stash them in {}, or use --adopt to keep the checked-out branch",
+            worktree.display()
+        ));
+    }
+    RepairDriftAction::Switch
+}
+
+impl PlannedRepo for RepairDriftRepoPlan {
+    type Outcome = Outcome;
+    type Result = RepairDriftRepoResult;
+
+    fn planned_outcome(&self) -> Outcome {
+        match &self.action {
+            RepairDriftAction::Switch | RepairDriftAction::Adopt { .. } => Outcome::Success,
+            RepairDriftAction::Skip { reason } => Outcome::Skipped {
+                reason: reason.clone(),
+            },
+            RepairDriftAction::Fail { error } => Outcome::Failed {
+                error: error.clone(),
+            },
+        }
+    }
+
+    fn result(&self, outcome: Outcome) -> RepairDriftRepoResult {
+        RepairDriftRepoResult {
+            name: self.name.clone(),
+            branch_state: self.branch_state.clone(),
+            outcome,
+        }
+    }
+}
+
+impl RepoOutcome for RepairDriftRepoResult {
+    fn name(&self) -> &RepoName {
+        &self.name
+    }
+
+    fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+}
+
+fn finish(
+    plan: &RepairDriftPlan,
+    dry_run: bool,
+    repos: Vec<RepairDriftRepoResult>,
+) -> RepairDriftResult {
+    RepairDriftResult {
+        forest_name: plan.forest_name.clone(),
+        adopt: plan.adopt,
+        dry_run,
+        errors: repo_errors(&repos),
+        repos,
+    }
+}
+
+// --- Execution (impure) ---
+
+/// Repair every planned repo, continuing past failures (ADR 0009).
+///
+/// Adopted branches are written to metadata after each repo (ADR 0011).
+pub fn execute_repair_drift(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    plan: &RepairDriftPlan,
+) -> RepairDriftResult {
+    let meta_path = forest_dir.join(META_FILENAME);
+    let mut updated = meta.clone();
+    let repos = plan
+        .repo_plans
+        .iter()
+        .map(|rp| {
+            let outcome = match &rp.action {
+                RepairDriftAction::Switch => switch_back(rp),
+                RepairDriftAction::Adopt { branch } => {
+                    adopt_branch(&mut updated, &meta_path, &rp.name, branch)
+                }
+                _ => rp.planned_outcome(),
+            };
+            rp.result(outcome)
+        })
+        .collect();
+    finish(plan, false, repos)
+}
+
+fn switch_back(rp: &RepairDriftRepoPlan) -> Outcome {
+    let branch = &rp.branch_state.expected_branch;
+    match crate::git::git(&rp.worktree, &["checkout", branch]) {
+        Ok(_) => Outcome::Success,
+        Err(e) => Outcome::Failed {
+            error: format!(
+                "{}\n  hint: run `git checkout {}` in {} to see the full error",
+                compact_git_error(&e.to_string()),
+                branch,
+                rp.worktree.display()
+            ),
+        },
+    }
+}
+
+/// The forest did not create the adopted branch, so `rm` must not delete it.
+/// The recorded base commit described the old branch and is dropped.
+fn adopt_branch(meta: &mut ForestMeta, meta_path: &Path, name: &RepoName, branch: &str) -> Outcome {
+    let Some(repo) = meta.repos.iter_mut().find(|repo| &repo.name == name) else {
+        return Outcome::Failed {
+            error: "repo missing from forest metadata".to_string(),
+        };
+    };
+    repo.branch = branch.to_string();
+    repo.branch_created = false;
+    repo.base_commit = None;
+    match meta.write(meta_path) {
+        Ok(()) => Outcome::Success,
+        Err(e) => Outcome::Failed {
+            error: format!("failed to update forest metadata: {:#}", e),
+        },
+    }
+}
+
+pub fn cmd_repair_drift(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: RepairDriftInputs,
+) -> Result<RepairDriftResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let plan = plan_repair_drift(forest_dir, meta, &inputs);
+
+    if inputs.dry_run {
+        let repos = planned_results(&plan.repo_plans);
+        return Ok(finish(&plan, true, repos));
+    }
+
+    Ok(execute_repair_drift(forest_dir, meta, &plan))
+}
+
+pub fn format_repair_drift_human(result: &RepairDriftResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!(
+        "Repairing branch drift in forest {:?}",
+        result.forest_name.as_str()
+    ));
+
+    for repo in &result.repos {
+        let state = &repo.branch_state;
+        let detail = match &repo.outcome {
+            Outcome::Success => match (result.adopt, result.dry_run) {
+                (true, true) => format!(
+                    "would record {} (was {})",
+                    state.actual_display(),
+                    state.expected_branch
+                ),
+                (true, false) => format!(
+                    "recorded {} (was {})",
+                    state.actual_display(),
+                    state.expected_branch
+                ),
+                (false, true) => format!(
+                    "would check out {} (on {})",
+                    state.expected_branch,
+                    state.actual_display()
+                ),
+                (false, false) => format!(
+                    "checked out {} (was on {})",
+                    state.expected_branch,
+                    state.actual_display()
+                ),
+            },
+            Outcome::Skipped { reason } => format!("skipped ({})", reason),
+            Outcome::Failed { .. } => "FAILED".to_string(),
+        };
+        lines.push(format!("  {}: {}", repo.name, detail));
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::ForestMode;
+    use crate::testutil::{new_forest, TestEnv};
+
+    fn setup_forest(name: &str) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = new_forest(
+            &env,
+            name,
+            ForestMode::Feature,
+            &["foo-api", "foo-web"],
+            &[],
+        );
+        (env, forest_dir, meta)
+    }
+
+    fn current_branch(worktree: &Path) -> String {
+        crate::git::git(worktree, &["symbolic-ref", "--short", "HEAD"]).unwrap()
+    }
+
+    #[test]
+    fn repair_drift_switches_clean_worktrees_back() {
+        let (_env, forest_dir, meta) = setup_forest("drifted");
+        let api = forest_dir.join("foo-api");
+        crate::git::git(&api, &["checkout", "-b", "side-quest"]).unwrap();
+
+        let inputs = RepairDriftInputs {
+            adopt: false,
+            dry_run: true,
+        };
+        let result = cmd_repair_drift(&forest_dir, &meta, inputs).unwrap();
+        assert!(result.dry_run);
+        assert_eq!(result.repos[0].outcome, Outcome::Success);
+        assert_eq!(
+            result.repos[1].outcome,
+            Outcome::Skipped {
+                reason: "not drifted".to_string()
+            }
+        );
+        assert_eq!(current_branch(&api), "side-quest");
+        assert!(format_repair_drift_human(&result)
+            .contains("foo-api: would check out testuser/drifted (on side-quest)"));
+
+        let inputs = RepairDriftInputs {
+            adopt: false,
+            dry_run: false,
+        };
+        let result = cmd_repair_drift(&forest_dir, &meta, inputs).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(current_branch(&api), "testuser/drifted");
+    }
+
+    #[test]
+    fn repair_drift_refuses_dirty_worktree() {
+        let (_env, forest_dir, meta) = setup_forest("dirty-drift");
+        let web = forest_dir.join("foo-web");
+        crate::git::git(&web, &["checkout", "-b", "side-quest"]).unwrap();
+        std::fs::write(web.join("README"), "edit").unwrap();
+        crate::git::git(&web, &["add", "README"]).unwrap();
+
+        let inputs = RepairDriftInputs {
+            adopt: false,
+            dry_run: false,
+        };
+        let result = cmd_repair_drift(&forest_dir, &meta, inputs).unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert!(
+            result.errors[0].starts_with("foo-web: uncommitted changes"),
+            "errors: {:?}",
+            result.errors
+        );
+        assert_eq!(current_branch(&web), "side-quest");
+    }
+
+    #[test]
+    fn repair_drift_adopt_records_actual_branch_as_not_created() {
+        let (_env, forest_dir, meta) = setup_forest("adopt-drift");
+        crate::git::git(
+            &forest_dir.join("foo-api"),
+            &["checkout", "-b", "shared-fix"],
+        )
+        .unwrap();
+        crate::git::git(&forest_dir.join("foo-web"), &["checkout", "--detach"]).unwrap();
+
+        let inputs = RepairDriftInputs {
+            adopt: true,
+            dry_run: false,
+        };
+        let result = cmd_repair_drift(&forest_dir, &meta, inputs).unwrap();
+        assert_eq!(result.repos[0].outcome, Outcome::Success);
+        assert_eq!(result.errors.len(), 1);
+        assert!(
+            result.errors[0].contains("has no branch to adopt"),
+            "errors: {:?}",
+            result.errors
+        );
+
+        let updated = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+        assert_eq!(updated.repos[0].branch, "shared-fix");
+        assert!(!updated.repos[0].branch_created);
+        assert_eq!(updated.repos[0].base_commit, None);
+        assert_eq!(updated.repos[1].branch, "testuser/adopt-drift");
+        assert!(updated.repos[1].branch_created);
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use super::branch_state::{path_exists_or_symlink, worktree_has_dirty_files, WorktreeBranchState};
+use crate::meta::{ForestMeta, RepoMeta};
+
+/// Narrows a forest's repos for `exec`, `status`, and `rm`.
+///
+/// `repos` and `exclude_repos` select by name; the predicates must all hold.
+/// An empty filter selects every repo.
+#[derive(Debug, Clone, Default)]
+pub struct RepoFilter {
+    pub repos: Vec<String>,
+    pub exclude_repos: Vec<String>,
+    /// Worktree has uncommitted changes.
+    pub dirty: bool,
+    /// Branch was created by the forest.
+    pub branch_created: bool,
+    /// Worktree is on a different branch (or detached) than recorded.
+    pub drifted: bool,
+    /// Worktree directory is missing.
+    pub missing: bool,
+}
+
+impl RepoFilter {
+    pub fn is_empty(&self) -> bool {
+        self.repos.is_empty()
+            && self.exclude_repos.is_empty()
+            && !self.dirty
+            && !self.branch_created
+            && !self.drifted
+            && !self.missing
+    }
+
+    /// Repos in `meta` that pass the filter, in metadata order.
+    ///
+    /// Fails if `--repo` or `--exclude-repo` names a repo that isn't in the forest.
+    pub fn select<'a>(&self, forest_dir: &Path, meta: &'a ForestMeta) -> Result<Vec<&'a RepoMeta>> {
+        for name in self.repos.iter().chain(&self.exclude_repos) {
+            if !meta.repos.iter().any(|r| r.name.as_str() == name) {
+                let known: Vec<&str> = meta.repos.iter().map(|r| r.name.as_str()).collect();
+                bail!(
+                    "repo {} is not in forest {:?}\n  hint: repos in this forest: {}",
+                    name,
+                    meta.name.as_str(),
+                    known.join(", ")
+                );
+            }
+        }
+
+        Ok(meta
+            .repos
+            .iter()
+            .filter(|repo| self.matches(forest_dir, repo))
+            .collect())
+    }
+
+    fn matches(&self, forest_dir: &Path, repo: &RepoMeta) -> bool {
+        let name = repo.name.as_str();
+        if !self.repos.is_empty() && !self.repos.iter().any(|r| r == name) {
+            return false;
+        }
+        if self.exclude_repos.iter().any(|r| r == name) {
+            return false;
+        }
+        if self.branch_created && !repo.branch_created {
+            return false;
+        }
+
+        // Remaining predicates inspect the worktree; skip the git calls when unused
+        let worktree = forest_dir.join(name);
+        let exists = path_exists_or_symlink(&worktree);
+        if self.missing && exists {
+            return false;
+        }
+        if self.drifted && !WorktreeBranchState::read(&worktree, &repo.branch).branch_drift {
+            return false;
+        }
+        if self.dirty && !worktree_has_dirty_files(&worktree) {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::setup_forest_with_git_repos;
+
+    fn names(selected: &[&RepoMeta]) -> Vec<String> {
+        selected.iter().map(|r| r.name.to_string()).collect()
+    }
+
+    #[test]
+    fn empty_filter_selects_all_repos() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+
+        let filter = RepoFilter::default();
+        assert!(filter.is_empty());
+        let selected = filter.select(&forest_dir, &meta).unwrap();
+        assert_eq!(selected.len(), meta.repos.len());
+    }
+
+    #[test]
+    fn repo_and_exclude_repo_select_by_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+        let first = meta.repos[0].name.to_string();
+        let second = meta.repos[1].name.to_string();
+
+        let only_first = RepoFilter {
+            repos: vec![first.clone()],
+            ..RepoFilter::default()
+        };
+        assert_eq!(
+            names(&only_first.select(&forest_dir, &meta).unwrap()),
+            vec![first.clone()]
+        );
+
+        let without_first = RepoFilter {
+            exclude_repos: vec![first],
+            ..RepoFilter::default()
+        };
+        assert_eq!(
+            names(&without_first.select(&forest_dir, &meta).unwrap()),
+            vec![second]
+        );
+    }
+
+    #[test]
+    fn unknown_repo_name_errors_with_known_repos() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+
+        let filter = RepoFilter {
+            exclude_repos: vec!["nope".to_string()],
+            ..RepoFilter::default()
+        };
+        let err = filter.select(&forest_dir, &meta).unwrap_err().to_string();
+        assert!(err.contains("repo nope is not in forest"), "error: {}", err);
+        assert!(err.contains(meta.repos[0].name.as_str()), "error: {}", err);
+    }
+
+    #[test]
+    fn dirty_predicate_agrees_with_rm_preflight() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+        let first = meta.repos[0].name.as_str();
+        let second = meta.repos[1].name.as_str();
+        std::fs::write(forest_dir.join(first).join("scratch.txt"), "wip").unwrap();
+        // A plain directory in place of a worktree is not dirty to either
+        std::fs::remove_dir_all(forest_dir.join(second)).unwrap();
+        std::fs::create_dir(forest_dir.join(second)).unwrap();
+        std::fs::write(forest_dir.join(second).join("stray.txt"), "x").unwrap();
+
+        let dirty = RepoFilter {
+            dirty: true,
+            ..RepoFilter::default()
+        };
+        let plan = crate::commands::plan_rm(&forest_dir, &meta);
+        let preflight: Vec<String> = plan
+            .repo_plans
+            .iter()
+            .filter(|rp| rp.has_dirty_files)
+            .map(|rp| rp.name.to_string())
+            .collect();
+        assert_eq!(names(&dirty.select(&forest_dir, &meta).unwrap()), preflight);
+        assert_eq!(preflight, vec![first.to_string()]);
+    }
+
+    #[test]
+    fn predicates_match_worktree_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
+        let first = meta.repos[0].name.as_str();
+        let second = meta.repos[1].name.as_str();
+
+        std::fs::write(forest_dir.join(first).join("scratch.txt"), "wip").unwrap();
+        let dirty = RepoFilter {
+            dirty: true,
+            ..RepoFilter::default()
+        };
+        assert_eq!(
+            names(&dirty.select(&forest_dir, &meta).unwrap()),
+            vec![first.to_string()]
+        );
+
+        std::fs::remove_dir_all(forest_dir.join(second)).unwrap();
+        let missing = RepoFilter {
+            missing: true,
+            ..RepoFilter::default()
+        };
+        assert_eq!(
+            names(&missing.select(&forest_dir, &meta).unwrap()),
+            vec![second.to_string()]
+        );
+
+        // Predicates combine with AND
+        let dirty_and_missing = RepoFilter {
+            dirty: true,
+            missing: true,
+            ..RepoFilter::default()
+        };
+        assert!(dirty_and_missing
+            .select(&forest_dir, &meta)
+            .unwrap()
+            .is_empty());
+    }
+}
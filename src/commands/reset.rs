@@ -44,6 +44,7 @@ pub struct RepoResetEntry {
     pub name: RepoName,
     pub branch: String,
     pub branch_created: bool,
+    pub pre_remove_hook: RmOutcome,
     pub worktree_removed: RmOutcome,
     pub branch_deleted: RmOutcome,
 }
@@ -173,9 +174,17 @@ fn execute_reset(plan: &ResetPlan, on_progress: Option<&dyn Fn(ResetProgress)>)
                     repo.worktree_path,
                     forest.path
                 );
+                // Reset removes worktrees regardless, so a failing hook is
+                // reported without blocking (the same as `rm --force`)
+                let pre_remove_hook = rm::run_pre_remove_hook(repo, true, &mut errors);
                 let (worktree_removed, wt_succeeded) = remove_reset_worktree(repo, &mut errors);
                 let branch_deleted = rm::delete_branch(repo, false, wt_succeeded, &mut errors);
-                repos.push(repo_reset_entry(repo, worktree_removed, branch_deleted));
+                repos.push(repo_reset_entry(
+                    repo,
+                    pre_remove_hook,
+                    worktree_removed,
+                    branch_deleted,
+                ));
             }
         }
 
@@ -331,6 +340,7 @@ fn plan_reset_worktree_outcome(repo: &RepoRmPlan, errors: &mut Vec<String>) -> (
 
 fn repo_reset_entry(
     repo: &RepoRmPlan,
+    pre_remove_hook: RmOutcome,
     worktree_removed: RmOutcome,
     branch_deleted: RmOutcome,
 ) -> RepoResetEntry {
@@ -338,6 +348,7 @@ fn repo_reset_entry(
         name: repo.name.clone(),
         branch: repo.branch.clone(),
         branch_created: repo.branch_created,
+        pre_remove_hook,
         worktree_removed,
         branch_deleted,
     }
@@ -395,11 +406,12 @@ fn plan_to_dry_run(plan: &ResetPlan) -> ResetResult {
                 f.repos
                     .iter()
                     .map(|repo| {
+                        let pre_remove_hook = rm::plan_pre_remove_outcome(repo, true);
                         let (worktree_removed, wt_succeeded) =
                             plan_reset_worktree_outcome(repo, &mut errors);
                         let branch_deleted =
                             rm::plan_branch_delete_outcome(repo, false, wt_succeeded, &mut errors);
-                        repo_reset_entry(repo, worktree_removed, branch_deleted)
+                        repo_reset_entry(repo, pre_remove_hook, worktree_removed, branch_deleted)
                     })
                     .collect()
             };
@@ -664,7 +676,13 @@ fn format_repo_reset_status(repo: &RepoResetEntry, is_preview: bool) -> String {
         RmOutcome::Failed { .. } => ", branch FAILED".to_string(),
     };
 
-    format!("{}: {}{}", repo.name, wt, branch)
+    format!(
+        "{}: {}{}{}",
+        repo.name,
+        rm::format_pre_remove_prefix(&repo.pre_remove_hook, is_preview),
+        wt,
+        branch
+    )
 }
 
 fn reset_cleanup_blocked(result: &ResetResult) -> bool {
@@ -988,6 +1006,9 @@ name = "{}"
                     name: RepoName::new("repo-a".to_string()).unwrap(),
                     branch: "forest/reset-repro".to_string(),
                     branch_created: true,
+                    pre_remove_hook: RmOutcome::Skipped {
+                        reason: "no pre_remove hook".to_string(),
+                    },
                     worktree_removed: RmOutcome::Success,
                     branch_deleted: RmOutcome::Success,
                 }],
@@ -1121,7 +1142,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(crate::meta::META_FILENAME))
             .unwrap();
@@ -1172,7 +1195,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         let meta_path = forest_dir.join(crate::meta::META_FILENAME);
         meta.write(&meta_path).unwrap();
@@ -1320,7 +1345,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(crate::meta::META_FILENAME))
             .unwrap();
@@ -1379,7 +1406,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(crate::meta::META_FILENAME))
             .unwrap();
@@ -1435,7 +1464,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(crate::meta::META_FILENAME))
             .unwrap();
@@ -1484,7 +1515,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(crate::meta::META_FILENAME))
             .unwrap();
@@ -1541,8 +1574,12 @@ path = "/tmp/nonexistent-repo"
                 mode: ForestMode::Feature,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -1612,7 +1649,9 @@ path = "/tmp/nonexistent-repo"
             created_at: chrono::Utc::now(),
             mode: crate::meta::ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(crate::meta::META_FILENAME))
             .unwrap();
@@ -1686,8 +1725,12 @@ path = "/tmp/nonexistent-repo"
             mode: ForestMode::Feature,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         };
         let result = cmd_new(inputs, &tmpl).unwrap();
         assert!(!result.dry_run);
@@ -1756,8 +1799,12 @@ name = "repo-a"
                 mode: ForestMode::Review,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -1809,8 +1856,12 @@ name = "repo-a"
                 mode: ForestMode::Review,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -1890,8 +1941,12 @@ name = "repo-a"
                 mode: ForestMode::Review,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -1938,6 +1993,7 @@ name = "repo-a"
             created_at: chrono::Utc::now(),
             mode: ForestMode::Review,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![RepoMeta {
                 name: RepoName::new("repo-missing-source".to_string()).unwrap(),
                 source: missing_source,
@@ -1945,7 +2001,10 @@ name = "repo-a"
                 base_branch: "main".to_string(),
                 remote: Some("origin".to_string()),
                 branch_created: true,
+                pre_remove: None,
+                base_commit: None,
             }],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(META_FILENAME)).unwrap();
 
@@ -2017,6 +2076,7 @@ name = "repo-missing-source"
             created_at: chrono::Utc::now(),
             mode: ForestMode::Review,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![RepoMeta {
                 name: RepoName::new("repo-missing-both".to_string()).unwrap(),
                 source: missing_source,
@@ -2024,7 +2084,10 @@ name = "repo-missing-source"
                 base_branch: "main".to_string(),
                 remote: Some("origin".to_string()),
                 branch_created: true,
+                pre_remove: None,
+                base_commit: None,
             }],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(META_FILENAME)).unwrap();
 
@@ -2085,6 +2148,7 @@ name = "repo-missing-both"
             created_at: chrono::Utc::now(),
             mode: ForestMode::Review,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![RepoMeta {
                 name: RepoName::new("repo-dangling".to_string()).unwrap(),
                 source: missing_source,
@@ -2092,7 +2156,10 @@ name = "repo-missing-both"
                 base_branch: "main".to_string(),
                 remote: Some("origin".to_string()),
                 branch_created: true,
+                pre_remove: None,
+                base_commit: None,
             }],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(META_FILENAME)).unwrap();
 
@@ -2148,6 +2215,7 @@ name = "repo-dangling"
             created_at: chrono::Utc::now(),
             mode: ForestMode::Review,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![RepoMeta {
                 name: RepoName::new("repo-source-present-dangling".to_string()).unwrap(),
                 source: repo.clone(),
@@ -2155,7 +2223,10 @@ name = "repo-dangling"
                 base_branch: "main".to_string(),
                 remote: Some("origin".to_string()),
                 branch_created: true,
+                pre_remove: None,
+                base_commit: None,
             }],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(META_FILENAME)).unwrap();
 
@@ -2201,6 +2272,7 @@ name = "repo-dangling"
             created_at: chrono::Utc::now(),
             mode: ForestMode::Review,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![RepoMeta {
                 name: RepoName::new("repo-source-present-corrupt".to_string()).unwrap(),
                 source: repo.clone(),
@@ -2208,7 +2280,10 @@ name = "repo-dangling"
                 base_branch: "main".to_string(),
                 remote: Some("origin".to_string()),
                 branch_created: true,
+                pre_remove: None,
+                base_commit: None,
             }],
+            pins: vec![],
         };
         meta.write(&forest_dir.join(META_FILENAME)).unwrap();
 
@@ -2252,8 +2327,12 @@ name = "repo-dangling"
                 mode: ForestMode::Review,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -2312,8 +2391,12 @@ name = "repo-dangling"
                 mode: ForestMode::Review,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -2359,8 +2442,12 @@ name = "repo-dangling"
                 mode: ForestMode::Review,
                 branch_override: None,
                 repo_branches: vec![],
+                pull_requests: vec![],
                 no_fetch: true,
                 dry_run: false,
+                jobs: crate::parallel::DEFAULT_JOBS,
+                no_hooks: false,
+                parallel_hooks: false,
             },
             &tmpl,
         )
@@ -2403,8 +2490,12 @@ name = "repo-dangling"
             mode: ForestMode::Feature,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         };
         cmd_new(inputs, &tmpl).unwrap();
 
@@ -2446,8 +2537,12 @@ name = "repo-b"
             mode: ForestMode::Feature,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         };
         let result2 = cmd_new(inputs2, &tmpl);
         assert!(
@@ -2,9 +2,12 @@ use anyhow::{bail, Result};
 use serde::Serialize;
 use std::path::{Component, Path, PathBuf};
 
-use super::branch_state::{compact_git_error, ActualBranchState, WorktreeBranchState};
+use super::branch_state::{
+    compact_git_error, worktree_has_dirty_files, ActualBranchState, WorktreeBranchState,
+};
+use super::repo_filter::RepoFilter;
 use crate::forest::{dedupe_discovered_forests, discover_forests_with_dirs};
-use crate::meta::{ForestMeta, META_FILENAME, STAGED_META_PREFIX};
+use crate::meta::{ForestMeta, RepoMeta, META_FILENAME, STAGED_META_PREFIX};
 use crate::paths::{
     forest_root_entry_comparison_key, validate_disposable_root_entries, AbsolutePath,
     DisposableRootEntry, ForestName, RepoName,
@@ -12,12 +15,16 @@ use crate::paths::{
 
 // --- Types ---
 
+const RM_FORCE_COMMAND: &str = "git forest rm --force";
+
 #[derive(Debug, Clone, Default)]
 pub struct RmOptions {
     pub force: bool,
     pub dry_run: bool,
     pub require_utf8_json_paths: bool,
     pub additional_disposable_root_entries: Vec<DisposableRootEntry>,
+    /// When non-empty, remove only the matching repos and keep the forest.
+    pub filter: RepoFilter,
 }
 
 impl RmOptions {
@@ -27,6 +34,7 @@ impl RmOptions {
             dry_run,
             require_utf8_json_paths: false,
             additional_disposable_root_entries: vec![],
+            filter: RepoFilter::default(),
         }
     }
 }
@@ -36,10 +44,15 @@ pub struct RmPlan {
     pub forest_dir: PathBuf,
     pub repo_plans: Vec<RepoRmPlan>,
     root_plan: ForestRootPlan,
+    /// Full metadata when a repo filter selected the repos; removed repos are
+    /// dropped from it instead of deleting the forest.
+    partial_meta: Option<ForestMeta>,
 }
 
 enum ForestRootPlan {
     Missing,
+    /// A repo filter is active, so the forest root stays in place.
+    Kept,
     Ready {
         cleanup_actions: Vec<ForestRootCleanupAction>,
     },
@@ -70,6 +83,11 @@ pub struct RepoRmPlan {
     pub worktree_exists: bool,
     pub source_exists: bool,
     pub has_dirty_files: bool,
+    pub pre_remove: Option<String>,
+    /// Environment for the `pre_remove` hook (see `ForestMeta::repo_env`).
+    pub env: Vec<(String, String)>,
+    /// Command the safety hints suggest to override them, e.g. `git forest rm --force`.
+    pub force_command: &'static str,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,6 +107,8 @@ pub struct RmResult {
     pub repos: Vec<RepoRmResult>,
     pub forest_root_cleanup: Vec<ForestRootCleanupResult>,
     pub forest_dir_removed: bool,
+    /// Only repos selected by a filter were removed; the forest was kept.
+    pub partial: bool,
     pub errors: Vec<String>,
 }
 
@@ -96,6 +116,7 @@ pub struct RmResult {
 pub struct RepoRmResult {
     pub name: RepoName,
     pub branch_state: WorktreeBranchState,
+    pub pre_remove_hook: RmOutcome,
     pub worktree_removed: RmOutcome,
     pub branch_deleted: RmOutcome,
 }
@@ -114,13 +135,7 @@ pub struct ForestRootCleanupResult {
     pub removal: RmOutcome,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
-#[serde(rename_all = "snake_case", tag = "status")]
-pub enum RmOutcome {
-    Success,
-    Skipped { reason: String },
-    Failed { error: String },
-}
+pub use super::outcome::Outcome as RmOutcome;
 
 pub enum RmProgress<'a> {
     RepoStarting { name: &'a RepoName },
@@ -153,7 +168,7 @@ impl RmPlan {
     pub(crate) fn root_rejection(&self) -> Option<&str> {
         match &self.root_plan {
             ForestRootPlan::Rejected { error } => Some(error),
-            ForestRootPlan::Missing | ForestRootPlan::Ready { .. } => None,
+            ForestRootPlan::Missing | ForestRootPlan::Kept | ForestRootPlan::Ready { .. } => None,
         }
     }
 }
@@ -166,6 +181,9 @@ fn plan_rm_with_options(
     if options.force && !options.additional_disposable_root_entries.is_empty() {
         bail!("--force cannot be combined with --discard-root-entry");
     }
+    if !options.filter.is_empty() {
+        return plan_partial_rm(forest_dir, meta, options);
+    }
 
     let reserved_entries: Vec<&str> = std::iter::once(META_FILENAME)
         .chain(meta.repos.iter().map(|repo| repo.name.as_str()))
@@ -197,41 +215,7 @@ fn plan_rm_with_options(
     } else {
         meta.repos
             .iter()
-            .map(|repo| {
-                let worktree_path = forest_dir.join(repo.name.as_str());
-                assert!(
-                    worktree_path_is_inside_forest(&worktree_path, forest_dir),
-                    "worktree path {:?} is not inside forest dir {:?}",
-                    worktree_path,
-                    forest_dir
-                );
-                let worktree_exists = path_exists_or_symlink(&worktree_path);
-                let worktree_is_symlink = path_is_symlink(&worktree_path);
-                let branch_state = WorktreeBranchState::read(&worktree_path, &repo.branch);
-                let source_exists = repo.source.is_dir();
-                let detached_head_safety =
-                    detached_head_safety(&branch_state, &repo.source, source_exists);
-                let has_dirty_files = worktree_exists
-                    && !worktree_is_symlink
-                    && !matches!(&branch_state.actual, ActualBranchState::Unknown { .. })
-                    && crate::git::git(&worktree_path, &["status", "--porcelain"])
-                        .map(|output| !output.is_empty())
-                        .unwrap_or(false);
-                RepoRmPlan {
-                    name: repo.name.clone(),
-                    worktree_path,
-                    source: repo.source.clone(),
-                    branch: repo.branch.clone(),
-                    base_branch: repo.base_branch.clone(),
-                    remote: repo.remote.clone(),
-                    branch_created: repo.branch_created,
-                    branch_state,
-                    detached_head_safety,
-                    worktree_exists,
-                    source_exists,
-                    has_dirty_files,
-                }
-            })
+            .map(|repo| plan_repo_rm(forest_dir, meta, repo, RM_FORCE_COMMAND))
             .collect()
     };
 
@@ -240,9 +224,82 @@ fn plan_rm_with_options(
         forest_dir: forest_dir.to_path_buf(),
         repo_plans,
         root_plan,
+        partial_meta: None,
+    })
+}
+
+/// Plan removal of only the repos selected by `options.filter`.
+///
+/// The forest directory and its metadata are kept, so forest-root cleanup
+/// does not apply.
+fn plan_partial_rm(forest_dir: &Path, meta: &ForestMeta, options: &RmOptions) -> Result<RmPlan> {
+    if !options.additional_disposable_root_entries.is_empty() {
+        bail!(
+            "--discard-root-entry cannot be combined with repo filters\n  hint: repo filters keep the forest directory, so no root entries are removed"
+        );
+    }
+    validate_json_output_paths(
+        forest_dir,
+        &ForestRootPlan::Kept,
+        options.require_utf8_json_paths,
+    )?;
+
+    let repo_plans = options
+        .filter
+        .select(forest_dir, meta)?
+        .into_iter()
+        .map(|repo| plan_repo_rm(forest_dir, meta, repo, RM_FORCE_COMMAND))
+        .collect();
+
+    Ok(RmPlan {
+        forest_name: meta.name.clone(),
+        forest_dir: forest_dir.to_path_buf(),
+        repo_plans,
+        root_plan: ForestRootPlan::Kept,
+        partial_meta: Some(meta.clone()),
     })
 }
 
+/// Inspect one recorded repo for removal: worktree and source presence,
+/// actual branch state, detached-HEAD reachability, and dirty files.
+/// `force_command` is the command its hints suggest to override a refusal.
+pub(super) fn plan_repo_rm(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    repo: &RepoMeta,
+    force_command: &'static str,
+) -> RepoRmPlan {
+    let worktree_path = forest_dir.join(repo.name.as_str());
+    assert!(
+        worktree_path_is_inside_forest(&worktree_path, forest_dir),
+        "worktree path {:?} is not inside forest dir {:?}",
+        worktree_path,
+        forest_dir
+    );
+    let worktree_exists = path_exists_or_symlink(&worktree_path);
+    let branch_state = WorktreeBranchState::read(&worktree_path, &repo.branch);
+    let source_exists = repo.source.is_dir();
+    let detached_head_safety = detached_head_safety(&branch_state, &repo.source, source_exists);
+    let has_dirty_files = worktree_has_dirty_files(&worktree_path);
+    RepoRmPlan {
+        name: repo.name.clone(),
+        worktree_path,
+        source: repo.source.clone(),
+        branch: repo.branch.clone(),
+        base_branch: repo.base_branch.clone(),
+        remote: repo.remote.clone(),
+        branch_created: repo.branch_created,
+        branch_state,
+        detached_head_safety,
+        worktree_exists,
+        source_exists,
+        has_dirty_files,
+        pre_remove: repo.pre_remove.clone(),
+        env: meta.repo_env(forest_dir, repo),
+        force_command,
+    }
+}
+
 fn validate_json_output_paths(
     forest_dir: &Path,
     root_plan: &ForestRootPlan,
@@ -493,16 +550,17 @@ fn worktree_removal_safety_error(repo_plan: &RepoRmPlan, force: bool) -> Option<
     match &repo_plan.detached_head_safety {
         DetachedHeadSafety::Unpreserved { head } => {
             return Some(format!(
-                "{}: detached HEAD {} has commits not reachable from any branch, remote, or tag ref; use `git forest rm --force` to remove anyway",
-                repo_plan.name, head
+                "{}: detached HEAD {} has commits not reachable from any branch, remote, or tag ref; use `{}` to remove anyway",
+                repo_plan.name, head, repo_plan.force_command
             ));
         }
         DetachedHeadSafety::Unverified { head, error } => {
             return Some(format!(
-                "{}: detached HEAD {} reachability could not be verified: {}; use `git forest rm --force` to remove anyway",
+                "{}: detached HEAD {} reachability could not be verified: {}; use `{}` to remove anyway",
                 repo_plan.name,
                 head,
-                compact_git_error(error)
+                compact_git_error(error),
+                repo_plan.force_command
             ));
         }
         DetachedHeadSafety::NotDetached | DetachedHeadSafety::Preserved => {}
@@ -510,8 +568,8 @@ fn worktree_removal_safety_error(repo_plan: &RepoRmPlan, force: bool) -> Option<
 
     if !repo_plan.source_exists {
         return Some(format!(
-            "{}: source repo missing; refusing to remove unverified worktree without `git forest rm --force`",
-            repo_plan.name
+            "{}: source repo missing; refusing to remove unverified worktree without `{}`",
+            repo_plan.name, repo_plan.force_command
         ));
     }
 
@@ -520,15 +578,15 @@ fn worktree_removal_safety_error(repo_plan: &RepoRmPlan, force: bool) -> Option<
         ActualBranchState::Unknown { .. }
     ) {
         return Some(format!(
-            "{}: branch lookup failed; refusing to remove unverified worktree without `git forest rm --force`",
-            repo_plan.name
+            "{}: branch lookup failed; refusing to remove unverified worktree without `{}`",
+            repo_plan.name, repo_plan.force_command
         ));
     }
 
     if path_is_symlink(&repo_plan.worktree_path) {
         return Some(format!(
-            "{}: worktree path is a symlink; refusing to remove unverified worktree without `git forest rm --force`",
-            repo_plan.name
+            "{}: worktree path is a symlink; refusing to remove unverified worktree without `{}`",
+            repo_plan.name, repo_plan.force_command
         ));
     }
 
@@ -565,31 +623,7 @@ pub fn execute_rm(
                     cb(RmProgress::RepoStarting { name: &rp.name });
                 }
 
-                let (worktree_removed, branch_deleted) = if rp.has_dirty_files {
-                    let msg = format!("{}: worktree has uncommitted changes", rp.name);
-                    errors.push(msg.clone());
-                    (
-                        RmOutcome::Failed { error: msg },
-                        RmOutcome::Skipped {
-                            reason: "worktree not removed".to_string(),
-                        },
-                    )
-                } else {
-                    (
-                        RmOutcome::Skipped {
-                            reason: "blocked by dirty repos".to_string(),
-                        },
-                        RmOutcome::Skipped {
-                            reason: "blocked by dirty repos".to_string(),
-                        },
-                    )
-                };
-                let result = RepoRmResult {
-                    name: rp.name.clone(),
-                    branch_state: rp.branch_state.clone(),
-                    worktree_removed,
-                    branch_deleted,
-                };
+                let result = dirty_preflight_result(rp, &mut errors);
 
                 if let Some(cb) = &on_progress {
                     cb(RmProgress::RepoDone(&result));
@@ -610,6 +644,7 @@ pub fn execute_rm(
                 repos,
                 forest_root_cleanup: vec![],
                 forest_dir_removed: false,
+                partial: plan.partial_meta.is_some(),
                 errors,
             };
         }
@@ -617,6 +652,7 @@ pub fn execute_rm(
 
     let mut repos = Vec::new();
     let mut errors = Vec::new();
+    let mut fully_removed = Vec::new();
 
     for repo_plan in &plan.repo_plans {
         if let Some(cb) = &on_progress {
@@ -624,18 +660,26 @@ pub fn execute_rm(
                 name: &repo_plan.name,
             });
         }
+        let errors_before = errors.len();
 
-        let (worktree_removed, wt_succeeded) = remove_worktree(repo_plan, force, &mut errors);
+        let pre_remove_hook = run_pre_remove_hook(repo_plan, force, &mut errors);
+        let (worktree_removed, wt_succeeded) =
+            remove_worktree_after_hook(repo_plan, &pre_remove_hook, force, &mut errors);
 
         let branch_deleted = delete_branch(repo_plan, force, wt_succeeded, &mut errors);
 
         let result = RepoRmResult {
             name: repo_plan.name.clone(),
             branch_state: repo_plan.branch_state.clone(),
+            pre_remove_hook,
             worktree_removed,
             branch_deleted,
         };
 
+        if errors.len() == errors_before {
+            fully_removed.push(repo_plan.name.clone());
+        }
+
         if let Some(cb) = &on_progress {
             cb(RmProgress::RepoDone(&result));
         }
@@ -643,7 +687,10 @@ pub fn execute_rm(
     }
 
     let mut forest_root_cleanup = Vec::new();
-    let forest_dir_removed = if errors.is_empty() {
+    let forest_dir_removed = if let Some(meta) = &plan.partial_meta {
+        drop_removed_repos_from_meta(plan, meta, &fully_removed, &mut errors);
+        false
+    } else if errors.is_empty() {
         if let Some(error) = current_forest_root_safety_error(&plan.forest_dir) {
             errors.push(error);
             false
@@ -668,14 +715,146 @@ pub fn execute_rm(
         repos,
         forest_root_cleanup,
         forest_dir_removed,
+        partial: plan.partial_meta.is_some(),
         errors,
     }
 }
 
+/// Rewrite the forest metadata without the repos that were fully removed.
+///
+/// Repos with any failed step keep their entry so `rm` can be retried.
+fn drop_removed_repos_from_meta(
+    plan: &RmPlan,
+    meta: &ForestMeta,
+    removed: &[RepoName],
+    errors: &mut Vec<String>,
+) {
+    if removed.is_empty() {
+        return;
+    }
+    let mut updated = meta.clone();
+    updated.repos.retain(|r| !removed.contains(&r.name));
+    if let Err(e) = updated.write(&plan.forest_dir.join(META_FILENAME)) {
+        errors.push(format!("failed to update forest meta: {:#}", e));
+    }
+}
+
+/// Per-repo result when the dirty-files preflight blocks the whole removal.
+pub(super) fn dirty_preflight_result(rp: &RepoRmPlan, errors: &mut Vec<String>) -> RepoRmResult {
+    let blocked = |reason: &str| RmOutcome::Skipped {
+        reason: reason.to_string(),
+    };
+    let (pre_remove_hook, worktree_removed, branch_deleted) = if rp.has_dirty_files {
+        let msg = format!("{}: worktree has uncommitted changes", rp.name);
+        errors.push(msg.clone());
+        (
+            blocked("worktree not removed"),
+            RmOutcome::Failed { error: msg },
+            blocked("worktree not removed"),
+        )
+    } else {
+        (
+            blocked("blocked by dirty repos"),
+            blocked("blocked by dirty repos"),
+            blocked("blocked by dirty repos"),
+        )
+    };
+    RepoRmResult {
+        name: rp.name.clone(),
+        branch_state: rp.branch_state.clone(),
+        pre_remove_hook,
+        worktree_removed,
+        branch_deleted,
+    }
+}
+
+/// Why a repo's `pre_remove` hook won't run, or `None` if it will. The hook
+/// only runs in a real worktree that removal is actually going to touch.
+fn pre_remove_skip_reason(repo_plan: &RepoRmPlan, force: bool) -> Option<&'static str> {
+    if repo_plan.pre_remove.is_none() {
+        Some("no pre_remove hook")
+    } else if !repo_plan.worktree_exists || path_is_symlink(&repo_plan.worktree_path) {
+        Some("worktree missing")
+    } else if worktree_removal_safety_error(repo_plan, force).is_some() {
+        Some("worktree removal refused")
+    } else {
+        None
+    }
+}
+
+/// Dry-run counterpart of [`run_pre_remove_hook`].
+pub(super) fn plan_pre_remove_outcome(repo_plan: &RepoRmPlan, force: bool) -> RmOutcome {
+    match pre_remove_skip_reason(repo_plan, force) {
+        Some(reason) => RmOutcome::Skipped {
+            reason: reason.to_string(),
+        },
+        None => RmOutcome::Success,
+    }
+}
+
+/// Run the repo's snapshotted `pre_remove` hook inside its worktree.
+///
+/// A failure is an error that keeps the worktree in place, unless `force` is
+/// set, in which case it is reported and removal carries on.
+pub(super) fn run_pre_remove_hook(
+    repo_plan: &RepoRmPlan,
+    force: bool,
+    errors: &mut Vec<String>,
+) -> RmOutcome {
+    if let Some(reason) = pre_remove_skip_reason(repo_plan, force) {
+        return RmOutcome::Skipped {
+            reason: reason.to_string(),
+        };
+    }
+    let command = repo_plan
+        .pre_remove
+        .as_deref()
+        .expect("pre_remove_skip_reason checks the hook is configured");
+
+    let hook = crate::hooks::run_hook(command, &repo_plan.worktree_path, &repo_plan.env);
+    if hook.success {
+        return RmOutcome::Success;
+    }
+
+    let mut msg = format!(
+        "{}: {}",
+        repo_plan.name,
+        crate::hooks::format_hook_summary("pre_remove hook", &hook)
+    );
+    if let Some(last) = hook.output_tail.lines().last() {
+        msg.push_str(&format!(": {}", last));
+    }
+    if !force {
+        errors.push(format!(
+            "{}\n  hint: fix the hook's cause and retry, or use `{}` to remove anyway",
+            msg, repo_plan.force_command
+        ));
+    }
+    RmOutcome::Failed { error: msg }
+}
+
+/// Remove the worktree unless its failed `pre_remove` hook holds it in place.
+pub(super) fn remove_worktree_after_hook(
+    repo_plan: &RepoRmPlan,
+    pre_remove_hook: &RmOutcome,
+    force: bool,
+    errors: &mut Vec<String>,
+) -> (RmOutcome, bool) {
+    if !force && matches!(pre_remove_hook, RmOutcome::Failed { .. }) {
+        return (
+            RmOutcome::Skipped {
+                reason: "pre_remove hook failed".to_string(),
+            },
+            false,
+        );
+    }
+    remove_worktree(repo_plan, force, errors)
+}
+
 fn planned_or_current_root_safety_error(plan: &RmPlan) -> Option<String> {
     match &plan.root_plan {
         ForestRootPlan::Rejected { error } => Some(error.clone()),
-        ForestRootPlan::Missing | ForestRootPlan::Ready { .. } => {
+        ForestRootPlan::Missing | ForestRootPlan::Kept | ForestRootPlan::Ready { .. } => {
             current_forest_root_safety_error(&plan.forest_dir)
         }
     }
@@ -710,6 +889,7 @@ fn rejected_rm_result(plan: &RmPlan, dry_run: bool, force: bool, error: String)
         repos: vec![],
         forest_root_cleanup: vec![],
         forest_dir_removed: false,
+        partial: plan.partial_meta.is_some(),
         errors: vec![error],
     }
 }
@@ -883,10 +1063,11 @@ pub(super) fn stale_missing_worktree_metadata_error(
     match worktree_metadata_for_path(repo_plan, target_canonical.as_deref()) {
         Ok(Some(_)) if force => prune_stale_worktree_metadata(repo_plan).err(),
         Ok(Some(_)) => Some(format!(
-            "{}: source repo still lists missing worktree metadata for {}; run `git -C {} worktree prune` or retry with `git forest rm --force`",
+            "{}: source repo still lists missing worktree metadata for {}; run `git -C {} worktree prune` or retry with `{}`",
             repo_plan.name,
             repo_plan.worktree_path.display(),
-            repo_plan.source
+            repo_plan.source,
+            repo_plan.force_command
         )),
         Ok(None) => None,
         Err(e) if force => Some(format!(
@@ -1339,8 +1520,8 @@ fn branch_not_fully_merged_error(repo_plan: &RepoRmPlan, detail: Option<&str>) -
     format!(
         "{}: branch {:?} is not fully merged{}\n  \
          hint: if the branch was merged (e.g. squash-merge) and the remote \
-         branch was deleted, use `git forest rm --force`",
-        repo_plan.name, repo_plan.branch, detail,
+         branch was deleted, use `{}`",
+        repo_plan.name, repo_plan.branch, detail, repo_plan.force_command,
     )
 }
 
@@ -1740,67 +1921,16 @@ fn plan_to_dry_run_result(plan: &RmPlan, force: bool) -> RmResult {
         .map(|rp| {
             if has_dirty {
                 // Dirty preflight would block all removal
-                let (worktree_removed, branch_deleted) = if rp.has_dirty_files {
-                    let msg = format!("{}: worktree has uncommitted changes", rp.name);
-                    errors.push(msg.clone());
-                    (
-                        RmOutcome::Failed { error: msg },
-                        RmOutcome::Skipped {
-                            reason: "worktree not removed".to_string(),
-                        },
-                    )
-                } else {
-                    (
-                        RmOutcome::Skipped {
-                            reason: "blocked by dirty repos".to_string(),
-                        },
-                        RmOutcome::Skipped {
-                            reason: "blocked by dirty repos".to_string(),
-                        },
-                    )
-                };
-                return RepoRmResult {
-                    name: rp.name.clone(),
-                    branch_state: rp.branch_state.clone(),
-                    worktree_removed,
-                    branch_deleted,
-                };
+                return dirty_preflight_result(rp, &mut errors);
             }
 
-            let worktree_removed = if path_is_symlink(&rp.worktree_path) {
-                if force {
-                    if let Some(msg) = symlink_worktree_dry_run_error(rp) {
-                        errors.push(msg.clone());
-                        RmOutcome::Failed { error: msg }
-                    } else {
-                        RmOutcome::Success
-                    }
-                } else {
-                    let msg = worktree_removal_safety_error(rp, force)
-                        .expect("non-force symlink worktree should have a safety error");
-                    errors.push(msg.clone());
-                    RmOutcome::Failed { error: msg }
-                }
-            } else if let Some(msg) = worktree_metadata_dry_run_error(rp, force) {
-                errors.push(msg.clone());
-                RmOutcome::Failed { error: msg }
-            } else if !rp.worktree_exists {
-                RmOutcome::Skipped {
-                    reason: "worktree already missing".to_string(),
-                }
-            } else if let Some(msg) = worktree_removal_safety_error(rp, force) {
-                errors.push(msg.clone());
-                RmOutcome::Failed { error: msg }
-            } else {
-                RmOutcome::Success
-            };
-
-            let wt_succeeded = !matches!(&worktree_removed, RmOutcome::Failed { .. });
-            let branch_deleted = plan_branch_delete_outcome(rp, force, wt_succeeded, &mut errors);
+            let pre_remove_hook = plan_pre_remove_outcome(rp, force);
+            let (worktree_removed, branch_deleted) = plan_repo_rm_outcomes(rp, force, &mut errors);
 
             RepoRmResult {
                 name: rp.name.clone(),
                 branch_state: rp.branch_state.clone(),
+                pre_remove_hook,
                 worktree_removed,
                 branch_deleted,
             }
@@ -1815,14 +1945,14 @@ fn plan_to_dry_run_result(plan: &RmPlan, force: bool) -> RmResult {
     }
 
     let mut forest_root_cleanup = Vec::new();
-    if errors.is_empty() {
+    if errors.is_empty() && plan.partial_meta.is_none() {
         forest_root_cleanup = root_cleanup_results(plan, |_| RmOutcome::Success);
         if let Some(msg) = forest_dir_dry_run_cleanup_error(plan, &repos, force) {
             errors.push(msg);
         }
     }
 
-    let forest_dir_removed = errors.is_empty();
+    let forest_dir_removed = errors.is_empty() && plan.partial_meta.is_none();
 
     RmResult {
         forest_name: plan.forest_name.clone(),
@@ -1832,10 +1962,50 @@ fn plan_to_dry_run_result(plan: &RmPlan, force: bool) -> RmResult {
         repos,
         forest_root_cleanup,
         forest_dir_removed,
+        partial: plan.partial_meta.is_some(),
         errors,
     }
 }
 
+/// Dry-run outcomes for one repo that is not blocked by the dirty preflight.
+pub(super) fn plan_repo_rm_outcomes(
+    rp: &RepoRmPlan,
+    force: bool,
+    errors: &mut Vec<String>,
+) -> (RmOutcome, RmOutcome) {
+    let worktree_removed = if path_is_symlink(&rp.worktree_path) {
+        if force {
+            if let Some(msg) = symlink_worktree_dry_run_error(rp) {
+                errors.push(msg.clone());
+                RmOutcome::Failed { error: msg }
+            } else {
+                RmOutcome::Success
+            }
+        } else {
+            let msg = worktree_removal_safety_error(rp, force)
+                .expect("non-force symlink worktree should have a safety error");
+            errors.push(msg.clone());
+            RmOutcome::Failed { error: msg }
+        }
+    } else if let Some(msg) = worktree_metadata_dry_run_error(rp, force) {
+        errors.push(msg.clone());
+        RmOutcome::Failed { error: msg }
+    } else if !rp.worktree_exists {
+        RmOutcome::Skipped {
+            reason: "worktree already missing".to_string(),
+        }
+    } else if let Some(msg) = worktree_removal_safety_error(rp, force) {
+        errors.push(msg.clone());
+        RmOutcome::Failed { error: msg }
+    } else {
+        RmOutcome::Success
+    };
+
+    let wt_succeeded = !matches!(&worktree_removed, RmOutcome::Failed { .. });
+    let branch_deleted = plan_branch_delete_outcome(rp, force, wt_succeeded, errors);
+    (worktree_removed, branch_deleted)
+}
+
 pub(super) fn worktree_metadata_dry_run_error(
     repo_plan: &RepoRmPlan,
     force: bool,
@@ -1925,7 +2095,7 @@ fn forest_dir_dry_run_cleanup_error(
                 ForestRootCleanupAction::RemoveForceEntry(_) => None,
             })
             .collect(),
-        ForestRootPlan::Missing | ForestRootPlan::Rejected { .. } => {
+        ForestRootPlan::Missing | ForestRootPlan::Kept | ForestRootPlan::Rejected { .. } => {
             std::collections::BTreeSet::new()
         }
     };
@@ -2017,7 +2187,9 @@ fn format_branch_state_warning_suffix(branch_state: &WorktreeBranchState) -> Str
 pub fn format_rm_human(result: &RmResult) -> String {
     let mut lines = Vec::new();
 
-    if result.dry_run {
+    if result.partial {
+        lines.extend(format_partial_rm_header(result));
+    } else if result.dry_run {
         lines.push("Dry run — no changes will be made.".to_string());
         lines.push(String::new());
         if result.errors.is_empty() && result.forest_dir_removed {
@@ -2041,48 +2213,14 @@ pub fn format_rm_human(result: &RmResult) -> String {
     }
 
     for repo in &result.repos {
-        let wt = match &repo.worktree_removed {
-            RmOutcome::Success => {
-                if result.dry_run {
-                    "remove worktree".to_string()
-                } else {
-                    "worktree removed".to_string()
-                }
-            }
-            RmOutcome::Skipped { reason } => format!("worktree skipped ({})", reason),
-            RmOutcome::Failed { .. } => "worktree FAILED".to_string(),
-        };
-
-        let br = match &repo.branch_deleted {
-            RmOutcome::Success => {
-                if result.dry_run {
-                    ", delete branch".to_string()
-                } else {
-                    ", branch deleted".to_string()
-                }
-            }
-            RmOutcome::Skipped { reason } => {
-                if reason == "branch not created by forest" {
-                    " (branch not ours)".to_string()
-                } else {
-                    format!(", branch skipped ({})", reason)
-                }
-            }
-            RmOutcome::Failed { .. } => ", branch FAILED".to_string(),
-        };
-
-        lines.push(format!(
-            "  {}: {}{}{}",
-            repo.name,
-            wt,
-            br,
-            format_branch_state_warning_suffix(&repo.branch_state)
-        ));
+        lines.push(format_repo_rm_line(repo, result.dry_run));
     }
 
     lines.extend(format_forest_root_cleanup(result));
 
-    if result.dry_run {
+    if result.partial {
+        lines.push(format_partial_rm_footer(result));
+    } else if result.dry_run {
         if result.forest_dir_removed {
             lines.push("  Would remove forest directory".to_string());
         } else {
@@ -2105,6 +2243,86 @@ pub fn format_rm_human(result: &RmResult) -> String {
     lines.join("\n")
 }
 
+fn format_partial_rm_header(result: &RmResult) -> Vec<String> {
+    let name = result.forest_name.as_str();
+    let mut lines = Vec::new();
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(if result.repos.is_empty() && result.errors.is_empty() {
+        format!("No repos in forest {:?} matched the filter", name)
+    } else if result.dry_run && result.errors.is_empty() {
+        format!("Would remove repos from forest {:?}", name)
+    } else if result.dry_run {
+        format!("Removal blocked for repos in forest {:?}", name)
+    } else if result.errors.is_empty() {
+        format!("Removed repos from forest {:?}", name)
+    } else {
+        format!("Removed repos from forest {:?} (with errors)", name)
+    });
+    lines
+}
+
+fn format_partial_rm_footer(result: &RmResult) -> String {
+    if result.dry_run {
+        "  Would keep forest directory (repo filter)".to_string()
+    } else {
+        "Forest kept (repo filter).".to_string()
+    }
+}
+
+pub(super) fn format_repo_rm_line(repo: &RepoRmResult, dry_run: bool) -> String {
+    let wt = match &repo.worktree_removed {
+        RmOutcome::Success => {
+            if dry_run {
+                "remove worktree".to_string()
+            } else {
+                "worktree removed".to_string()
+            }
+        }
+        RmOutcome::Skipped { reason } => format!("worktree skipped ({})", reason),
+        RmOutcome::Failed { .. } => "worktree FAILED".to_string(),
+    };
+
+    let br = match &repo.branch_deleted {
+        RmOutcome::Success => {
+            if dry_run {
+                ", delete branch".to_string()
+            } else {
+                ", branch deleted".to_string()
+            }
+        }
+        RmOutcome::Skipped { reason } => {
+            if reason == "branch not created by forest" {
+                " (branch not ours)".to_string()
+            } else {
+                format!(", branch skipped ({})", reason)
+            }
+        }
+        RmOutcome::Failed { .. } => ", branch FAILED".to_string(),
+    };
+
+    format!(
+        "  {}: {}{}{}{}",
+        repo.name,
+        format_pre_remove_prefix(&repo.pre_remove_hook, dry_run),
+        wt,
+        br,
+        format_branch_state_warning_suffix(&repo.branch_state)
+    )
+}
+
+/// Leading `pre_remove` status for a repo line; empty when no hook ran.
+pub(super) fn format_pre_remove_prefix(outcome: &RmOutcome, dry_run: bool) -> &'static str {
+    match outcome {
+        RmOutcome::Success if dry_run => "run pre_remove, ",
+        RmOutcome::Success => "pre_remove ran, ",
+        RmOutcome::Failed { .. } => "pre_remove FAILED, ",
+        RmOutcome::Skipped { .. } => "",
+    }
+}
+
 pub fn format_repo_done(repo: &RepoRmResult) -> String {
     let wt = match &repo.worktree_removed {
         RmOutcome::Success => "worktree removed".to_string(),
@@ -2125,7 +2343,8 @@ pub fn format_repo_done(repo: &RepoRmResult) -> String {
     };
 
     format!(
-        "{}{}{}",
+        "{}{}{}{}",
+        format_pre_remove_prefix(&repo.pre_remove_hook, false),
         wt,
         br,
         format_branch_state_warning_suffix(&repo.branch_state)
@@ -2137,7 +2356,9 @@ pub fn format_rm_summary(result: &RmResult) -> String {
 
     lines.extend(format_forest_root_cleanup(result));
 
-    if result.forest_dir_removed {
+    if result.partial {
+        lines.push(format_partial_rm_footer(result));
+    } else if result.forest_dir_removed {
         lines.push("Forest directory removed.".to_string());
     } else {
         lines.push("Forest directory not removed (not empty).".to_string());
@@ -2181,7 +2402,7 @@ fn format_forest_root_cleanup(result: &RmResult) -> Vec<String> {
         .collect()
 }
 
-fn format_error_single_line(error: &str) -> String {
+pub(super) fn format_error_single_line(error: &str) -> String {
     if error.contains("stderr:") {
         let compact = compact_git_error(error);
         return match compact_hint(error).filter(|hint| !compact.contains(hint)) {
@@ -2248,6 +2469,7 @@ impl RmAllForestPlan {
                 repos: vec![],
                 forest_root_cleanup: vec![],
                 forest_dir_removed: false,
+                partial: false,
                 errors: vec![error.clone()],
             },
         }
@@ -2255,6 +2477,10 @@ impl RmAllForestPlan {
 }
 
 fn plan_rm_all(worktree_bases: &[&Path], options: &RmOptions) -> Result<RmAllPlan> {
+    if !options.filter.is_empty() {
+        bail!("repo filters cannot be combined with --all\n  hint: name a forest to remove only some of its repos");
+    }
+
     let mut forests = Vec::new();
 
     for base in worktree_bases {
@@ -2428,8 +2654,12 @@ mod tests {
             mode,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         }
     }
 
@@ -2444,9 +2674,12 @@ mod tests {
             .iter()
             .map(|name| ResolvedRepo {
                 path: env.repo_path(name),
+                url: None,
                 name: RepoName::new(name.to_string()).unwrap(),
                 base_branch: "main".to_string(),
                 remote: "origin".to_string(),
+                post_create: None,
+                pre_remove: None,
             })
             .collect();
 
@@ -2455,6 +2688,9 @@ mod tests {
             base_branch: "main".to_string(),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
+            post_create: None,
+            pre_remove: None,
+            env: Default::default(),
             repos,
         }
     }
@@ -2915,6 +3151,154 @@ mod tests {
         assert!(!forest_dir.exists());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn execute_rm_runs_pre_remove_hook_before_removal() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let marker = env.worktree_base().join("hook-ran");
+        let mut tmpl = env.default_template(&["foo-api"]);
+        tmpl.repos[0].pre_remove = Some(format!("pwd > '{}'", marker.display()));
+
+        let inputs = make_new_inputs("pre-remove", ForestMode::Feature);
+        let forest_dir = cmd_new(inputs, &tmpl).unwrap().forest_dir;
+        let meta = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+        assert!(meta.repos[0].pre_remove.is_some(), "hook is snapshotted");
+
+        let dry = cmd_rm(&forest_dir, &meta, false, true, None).unwrap();
+        assert_eq!(dry.repos[0].pre_remove_hook, RmOutcome::Success);
+        assert!(!marker.exists(), "dry run must not run the hook");
+
+        let result = cmd_rm(&forest_dir, &meta, false, false, None).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repos[0].pre_remove_hook, RmOutcome::Success);
+        assert!(result.forest_dir_removed);
+        let ran_in = std::fs::read_to_string(&marker).unwrap();
+        assert!(ran_in.trim_end().ends_with("foo-api"), "ran in: {}", ran_in);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn execute_rm_failed_pre_remove_hook_blocks_unless_forced() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        let mut tmpl = env.default_template(&["foo-api"]);
+        tmpl.repos[0].pre_remove = Some("echo compose down failed; exit 1".to_string());
+
+        let inputs = make_new_inputs("pre-remove-fail", ForestMode::Feature);
+        let forest_dir = cmd_new(inputs, &tmpl).unwrap().forest_dir;
+        let meta = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+
+        let result = cmd_rm(&forest_dir, &meta, false, false, None).unwrap();
+        assert!(matches!(
+            &result.repos[0].pre_remove_hook,
+            RmOutcome::Failed { error } if error.contains("compose down failed")
+        ));
+        assert!(matches!(
+            &result.repos[0].worktree_removed,
+            RmOutcome::Skipped { reason } if reason == "pre_remove hook failed"
+        ));
+        assert!(result.errors[0].contains("--force"), "{:?}", result.errors);
+        assert!(forest_dir.join("foo-api").exists());
+        assert!(forest_dir.join(META_FILENAME).exists());
+
+        let forced = cmd_rm(&forest_dir, &meta, true, false, None).unwrap();
+        assert!(matches!(
+            forced.repos[0].pre_remove_hook,
+            RmOutcome::Failed { .. }
+        ));
+        assert!(forced.errors.is_empty(), "errors: {:?}", forced.errors);
+        assert!(forced.forest_dir_removed);
+    }
+
+    // --- repo filter (partial rm) ---
+
+    #[test]
+    fn rm_with_repo_filter_removes_selected_repos_and_keeps_forest() {
+        let env = TestEnv::new();
+        env.create_repo_with_remote("foo-api");
+        env.create_repo_with_remote("foo-web");
+        let tmpl = env.default_template(&["foo-api", "foo-web"]);
+
+        let inputs = make_new_inputs("rm-filter", ForestMode::Feature);
+        let forest_dir = cmd_new(inputs, &tmpl).unwrap().forest_dir;
+        let meta = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+        let options = || RmOptions {
+            filter: RepoFilter {
+                repos: vec!["foo-web".to_string()],
+                ..RepoFilter::default()
+            },
+            ..RmOptions::new(false, false)
+        };
+
+        let dry = cmd_rm_with_options(
+            &forest_dir,
+            &meta,
+            RmOptions {
+                dry_run: true,
+                ..options()
+            },
+            None,
+        )
+        .unwrap();
+        assert!(dry.partial);
+        assert!(!dry.forest_dir_removed);
+        assert_eq!(dry.repos.len(), 1);
+        assert!(format_rm_human(&dry).contains("Would keep forest directory"));
+
+        let result = cmd_rm_with_options(&forest_dir, &meta, options(), None).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert!(result.partial);
+        assert!(!result.forest_dir_removed);
+        assert_eq!(result.repos[0].name.as_str(), "foo-web");
+        assert_eq!(result.repos[0].worktree_removed, RmOutcome::Success);
+        assert!(!forest_dir.join("foo-web").exists());
+        assert!(forest_dir.join("foo-api").exists());
+
+        let updated = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+        let names: Vec<&str> = updated.repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["foo-api"]);
+    }
+
+    #[test]
+    fn rm_with_repo_filter_rejects_all_and_discard_root_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let filter = RepoFilter {
+            dirty: true,
+            ..RepoFilter::default()
+        };
+
+        let all = cmd_rm_all_with_options(
+            &[tmp.path()],
+            RmOptions {
+                filter: filter.clone(),
+                ..RmOptions::new(false, true)
+            },
+            None,
+        )
+        .unwrap_err();
+        assert!(all.to_string().contains("cannot be combined with --all"));
+
+        let (forest_dir, meta) = crate::testutil::setup_forest_with_git_repos(tmp.path());
+        let discard = cmd_rm_with_options(
+            &forest_dir,
+            &meta,
+            RmOptions {
+                filter,
+                additional_disposable_root_entries: vec![DisposableRootEntry::new(
+                    ".idea".to_string(),
+                )
+                .unwrap()],
+                ..RmOptions::new(false, true)
+            },
+            None,
+        )
+        .unwrap_err();
+        assert!(discard
+            .to_string()
+            .contains("--discard-root-entry cannot be combined with repo filters"));
+    }
+
     // --- cmd_rm / format tests ---
 
     #[test]
@@ -3253,7 +3637,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             mode: ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
 
         let plan = plan_rm_with_options(temp.path(), &meta, &RmOptions::new(true, true)).unwrap();
@@ -3399,7 +3785,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             mode: ForestMode::Feature,
             disposable_root_entries: vec![DisposableRootEntry::new(".idea".to_string()).unwrap()],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         let plan = plan_rm_with_options(&forest_dir, &meta, &RmOptions::new(false, true)).unwrap();
 
@@ -3429,7 +3817,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             mode: ForestMode::Feature,
             disposable_root_entries: vec![DisposableRootEntry::new(".idea".to_string()).unwrap()],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         let plan = plan_rm_with_options(temp.path(), &meta, &RmOptions::new(false, true)).unwrap();
 
@@ -3461,7 +3851,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             mode: ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         let plan = plan_rm_with_options(temp.path(), &meta, &RmOptions::new(true, true)).unwrap();
 
@@ -3539,6 +3931,7 @@ mod tests {
                     ForestRootCleanupAction::RemoveForceEntry(PathBuf::from("removable")),
                 ],
             },
+            partial_meta: None,
         };
         let mut errors = Vec::new();
 
@@ -5193,12 +5586,18 @@ mod tests {
                 RepoRmResult {
                     name: RepoName::new("foo-api".to_string()).unwrap(),
                     branch_state: WorktreeBranchState::missing_worktree("dliv/test-forest"),
+                    pre_remove_hook: RmOutcome::Skipped {
+                        reason: "no pre_remove hook".to_string(),
+                    },
                     worktree_removed: RmOutcome::Success,
                     branch_deleted: RmOutcome::Success,
                 },
                 RepoRmResult {
                     name: RepoName::new("foo-web".to_string()).unwrap(),
                     branch_state: WorktreeBranchState::missing_worktree("dliv/test-forest"),
+                    pre_remove_hook: RmOutcome::Skipped {
+                        reason: "no pre_remove hook".to_string(),
+                    },
                     worktree_removed: RmOutcome::Success,
                     branch_deleted: RmOutcome::Skipped {
                         reason: "branch not created by forest".to_string(),
@@ -5207,6 +5606,7 @@ mod tests {
             ],
             forest_root_cleanup: vec![],
             forest_dir_removed: true,
+            partial: false,
             errors: vec![],
         };
 
@@ -5229,11 +5629,15 @@ mod tests {
             repos: vec![RepoRmResult {
                 name: RepoName::new("foo-api".to_string()).unwrap(),
                 branch_state: WorktreeBranchState::missing_worktree("dliv/test-forest"),
+                pre_remove_hook: RmOutcome::Skipped {
+                    reason: "no pre_remove hook".to_string(),
+                },
                 worktree_removed: RmOutcome::Success,
                 branch_deleted: RmOutcome::Success,
             }],
             forest_root_cleanup: vec![],
             forest_dir_removed: true,
+            partial: false,
             errors: vec![],
         };
 
@@ -5254,6 +5658,9 @@ mod tests {
             repos: vec![RepoRmResult {
                 name: RepoName::new("foo-api".to_string()).unwrap(),
                 branch_state: WorktreeBranchState::missing_worktree("dliv/test-forest"),
+                pre_remove_hook: RmOutcome::Skipped {
+                    reason: "no pre_remove hook".to_string(),
+                },
                 worktree_removed: RmOutcome::Failed {
                     error: "git worktree remove failed".to_string(),
                 },
@@ -5263,6 +5670,7 @@ mod tests {
             }],
             forest_root_cleanup: vec![],
             forest_dir_removed: false,
+            partial: false,
             errors: vec!["foo-api: git worktree remove failed".to_string()],
         };
 
@@ -5955,8 +6363,12 @@ mod tests {
                 worktree_exists: false,
                 source_exists: false,
                 has_dirty_files: false,
+                pre_remove: None,
+                env: vec![],
+                force_command: RM_FORCE_COMMAND,
             }],
             root_plan: ForestRootPlan::Missing,
+            partial_meta: None,
         };
         execute_rm(&plan, false, None);
     }
@@ -5980,8 +6392,12 @@ mod tests {
                 worktree_exists: false,
                 source_exists: false,
                 has_dirty_files: false,
+                pre_remove: None,
+                env: vec![],
+                force_command: RM_FORCE_COMMAND,
             }],
             root_plan: ForestRootPlan::Missing,
+            partial_meta: None,
         };
         execute_rm(&plan, false, None);
     }
@@ -6005,8 +6421,12 @@ mod tests {
                 worktree_exists: true,
                 source_exists: false,
                 has_dirty_files: true,
+                pre_remove: None,
+                env: vec![],
+                force_command: RM_FORCE_COMMAND,
             }],
             root_plan: ForestRootPlan::Missing,
+            partial_meta: None,
         };
         execute_rm(&plan, false, None);
     }
@@ -6030,8 +6450,12 @@ mod tests {
                 worktree_exists: true,
                 source_exists: false,
                 has_dirty_files: true,
+                pre_remove: None,
+                env: vec![],
+                force_command: RM_FORCE_COMMAND,
             }],
             root_plan: ForestRootPlan::Missing,
+            partial_meta: None,
         };
         plan_to_dry_run_result(&plan, false);
     }
@@ -6140,7 +6564,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             mode: ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         hidden_meta
             .write(&hidden_forest.join(META_FILENAME))
@@ -6190,7 +6616,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             mode: ForestMode::Feature,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![],
+            pins: vec![],
         };
         hidden_meta
             .write(&hidden_forest.join(META_FILENAME))
@@ -5,7 +5,8 @@ use std::path::Path;
 use super::branch_state::{
     compact_git_error, path_exists_or_symlink, ActualBranchState, WorktreeBranchState,
 };
-use crate::meta::ForestMeta;
+use super::repo_filter::RepoFilter;
+use crate::meta::{ForestMeta, RepoMeta};
 use crate::paths::{ForestName, RepoName};
 
 #[derive(Debug, Serialize)]
@@ -24,16 +25,49 @@ pub struct RepoStatus {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type")]
 pub enum RepoStatusKind {
-    Ok { output: String },
-    Missing { path: String },
-    Error { message: String },
+    /// `output` is the raw `git status -sb` text; `summary` is the parsed form.
+    Ok {
+        output: String,
+        summary: StatusSummary,
+    },
+    Missing {
+        path: String,
+    },
+    Error {
+        message: String,
+    },
 }
 
-pub fn cmd_status(forest_dir: &Path, meta: &ForestMeta) -> Result<StatusResult> {
+/// Typed view of `git status --porcelain=v2 --branch`, plus divergence from
+/// the recorded base branch.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct StatusSummary {
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+    /// Upstream branch (e.g. `origin/main`); `None` when none is configured.
+    pub upstream: Option<String>,
+    /// Commits ahead of / behind `upstream`; `None` without an upstream.
+    pub upstream_ahead: Option<usize>,
+    pub upstream_behind: Option<usize>,
+    /// Remote ref of the recorded base branch (e.g. `origin/dev`); `None`
+    /// when the repo has no remote or the ref doesn't exist locally.
+    pub base_ref: Option<String>,
+    /// Commits ahead of / behind `base_ref`.
+    pub base_ahead: Option<usize>,
+    pub base_behind: Option<usize>,
+}
+
+pub fn cmd_status(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    filter: &RepoFilter,
+) -> Result<StatusResult> {
     debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
     let mut repos = Vec::new();
 
-    for repo in &meta.repos {
+    for repo in filter.select(forest_dir, meta)? {
         let worktree = forest_dir.join(repo.name.as_str());
         let branch_state = WorktreeBranchState::read(&worktree, &repo.branch);
 
@@ -49,8 +83,8 @@ pub fn cmd_status(forest_dir: &Path, meta: &ForestMeta) -> Result<StatusResult>
                 message: branch_lookup_error.clone(),
             }
         } else {
-            match crate::git::git(&worktree, &["status", "-sb"]) {
-                Ok(output) => RepoStatusKind::Ok { output },
+            match read_status(&worktree, repo) {
+                Ok((output, summary)) => RepoStatusKind::Ok { output, summary },
                 Err(e) => RepoStatusKind::Error {
                     message: e.to_string(),
                 },
@@ -70,6 +104,81 @@ pub fn cmd_status(forest_dir: &Path, meta: &ForestMeta) -> Result<StatusResult>
     })
 }
 
+fn read_status(worktree: &Path, repo: &RepoMeta) -> Result<(String, StatusSummary)> {
+    let output = crate::git::git(worktree, &["status", "-sb"])?;
+    let porcelain = crate::git::git(worktree, &["status", "--porcelain=v2", "--branch"])?;
+    let mut summary = parse_porcelain_v2(&porcelain);
+
+    if let Some(remote) = &repo.remote {
+        let base_ref = format!("{}/{}", remote, repo.base_branch);
+        let full_ref = format!("refs/remotes/{}", base_ref);
+        if matches!(crate::git::ref_exists(worktree, &full_ref), Ok(true)) {
+            let range = format!("{}...HEAD", full_ref);
+            let counts =
+                crate::git::git(worktree, &["rev-list", "--left-right", "--count", &range])?;
+            if let Some((behind, ahead)) = parse_left_right_counts(&counts) {
+                summary.base_behind = Some(behind);
+                summary.base_ahead = Some(ahead);
+            }
+            summary.base_ref = Some(base_ref);
+        }
+    }
+
+    Ok((output, summary))
+}
+
+/// Count entries in `git status --porcelain=v2 --branch` output.
+///
+/// Ordinary (`1`) and renamed (`2`) entries count toward `staged` and/or
+/// `unstaged` from their `XY` field; `u` is conflicted and `?` untracked.
+fn parse_porcelain_v2(output: &str) -> StatusSummary {
+    let mut summary = StatusSummary::default();
+    for line in output.lines() {
+        if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            summary.upstream = Some(upstream.to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            let mut parts = ab.split_whitespace();
+            summary.upstream_ahead = parts
+                .next()
+                .and_then(|a| a.trim_start_matches('+').parse().ok());
+            summary.upstream_behind = parts
+                .next()
+                .and_then(|b| b.trim_start_matches('-').parse().ok());
+        } else if line.starts_with("1 ") || line.starts_with("2 ") {
+            let xy = line.as_bytes().get(2..4).unwrap_or(b"..");
+            if xy[0] != b'.' {
+                summary.staged += 1;
+            }
+            if xy[1] != b'.' {
+                summary.unstaged += 1;
+            }
+        } else if line.starts_with("u ") {
+            summary.conflicted += 1;
+        } else if line.starts_with("? ") {
+            summary.untracked += 1;
+        }
+    }
+    summary
+}
+
+/// Parse `git rev-list --left-right --count` output: `<left>\t<right>`.
+pub(super) fn parse_left_right_counts(output: &str) -> Option<(usize, usize)> {
+    let mut parts = output.split_whitespace();
+    let left = parts.next()?.parse().ok()?;
+    let right = parts.next()?.parse().ok()?;
+    Some((left, right))
+}
+
+fn format_base_divergence(summary: &StatusSummary) -> Option<String> {
+    let base_ref = summary.base_ref.as_deref()?;
+    Some(format!(
+        "  vs {}: {} ahead, {} behind",
+        base_ref,
+        summary.base_ahead.unwrap_or(0),
+        summary.base_behind.unwrap_or(0)
+    ))
+}
+
 pub fn format_status_human(result: &StatusResult) -> String {
     let mut lines = Vec::new();
     for repo in &result.repos {
@@ -80,7 +189,10 @@ pub fn format_status_human(result: &StatusResult) -> String {
             lines.push(format!("  warning: {}", message));
         }
         match &repo.status {
-            RepoStatusKind::Ok { output } => lines.push(output.clone()),
+            RepoStatusKind::Ok { output, summary } => {
+                lines.push(output.clone());
+                lines.extend(format_base_divergence(summary));
+            }
             RepoStatusKind::Missing { path } => {
                 lines.push(format!("  warning: worktree missing at {}", path));
             }
@@ -118,8 +230,12 @@ mod tests {
             mode: ForestMode::Feature,
             branch_override: None,
             repo_branches: vec![],
+            pull_requests: vec![],
             no_fetch: true,
             dry_run: false,
+            jobs: crate::parallel::DEFAULT_JOBS,
+            no_hooks: false,
+            parallel_hooks: false,
         }
     }
 
@@ -145,12 +261,82 @@ mod tests {
     fn cmd_status_runs_in_each_repo() {
         let tmp = tempfile::tempdir().unwrap();
         let (forest_dir, meta) = setup_forest_with_git_repos(tmp.path());
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         assert_eq!(result.repos.len(), 2);
         assert!(matches!(result.repos[0].status, RepoStatusKind::Ok { .. }));
         assert!(matches!(result.repos[1].status, RepoStatusKind::Ok { .. }));
     }
 
+    #[test]
+    fn parse_porcelain_v2_counts_entries_and_upstream() {
+        let output = "\
+# branch.oid 1234567890abcdef1234567890abcdef12345678
+# branch.head feature
+# branch.upstream origin/feature
+# branch.ab +2 -1
+1 M. N... 100644 100644 100644 aaaa bbbb staged.rs
+1 .M N... 100644 100644 100644 aaaa bbbb unstaged.rs
+1 MM N... 100644 100644 100644 aaaa bbbb both.rs
+2 R. N... 100644 100644 100644 aaaa bbbb R100 new.rs\told.rs
+u UU N... 100644 100644 100644 100644 aaaa bbbb cccc conflict.rs
+? untracked.rs
+! ignored.rs";
+        let summary = parse_porcelain_v2(output);
+        assert_eq!(
+            summary,
+            StatusSummary {
+                staged: 3,
+                unstaged: 2,
+                untracked: 1,
+                conflicted: 1,
+                upstream: Some("origin/feature".to_string()),
+                upstream_ahead: Some(2),
+                upstream_behind: Some(1),
+                ..StatusSummary::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_porcelain_v2_without_upstream() {
+        let summary = parse_porcelain_v2("# branch.oid (initial)\n# branch.head main");
+        assert_eq!(summary, StatusSummary::default());
+    }
+
+    #[test]
+    fn cmd_status_reports_counts_and_base_divergence() {
+        let (_env, forest_dir, meta) = setup_single_repo_forest("status-summary");
+        let worktree = forest_dir.join("foo-api");
+        crate::git::git(&worktree, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(&worktree, &["config", "user.email", "test@test.com"]).unwrap();
+        std::fs::write(worktree.join("new.txt"), "one").unwrap();
+        crate::git::git(&worktree, &["add", "new.txt"]).unwrap();
+        crate::git::git(&worktree, &["commit", "-m", "ahead"]).unwrap();
+        std::fs::write(worktree.join("untracked.txt"), "two").unwrap();
+        std::fs::write(worktree.join("new.txt"), "changed").unwrap();
+
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
+        let RepoStatusKind::Ok { output, summary } = &result.repos[0].status else {
+            panic!("expected Ok status, got {:?}", result.repos[0].status);
+        };
+        assert!(output.starts_with("## testuser/status-summary"));
+        assert_eq!(summary.unstaged, 1);
+        assert_eq!(summary.untracked, 1);
+        assert_eq!(summary.staged, 0);
+        assert_eq!(summary.base_ref.as_deref(), Some("origin/main"));
+        assert_eq!(summary.base_ahead, Some(1));
+        assert_eq!(summary.base_behind, Some(0));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["repos"][0]["status"]["summary"]["base_ahead"], 1);
+        let human = format_status_human(&result);
+        assert!(
+            human.contains("vs origin/main: 1 ahead, 0 behind"),
+            "{}",
+            human
+        );
+    }
+
     #[test]
     fn cmd_status_missing_worktree_continues() {
         let tmp = tempfile::tempdir().unwrap();
@@ -164,7 +350,7 @@ mod tests {
             vec![make_repo("missing-repo", "main")],
         );
 
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         assert_eq!(result.repos.len(), 1);
         assert!(matches!(
             result.repos[0].status,
@@ -185,7 +371,7 @@ mod tests {
         let (_env, forest_dir, meta) = setup_single_repo_forest("status-drift");
         switch_forest_worktree_to_main(&forest_dir, &meta);
 
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         let repo = &result.repos[0];
         assert_eq!(repo.branch_state.expected_branch, "testuser/status-drift");
         assert!(repo.branch_state.branch_drift);
@@ -215,7 +401,7 @@ mod tests {
         let head = crate::git::git(&worktree, &["rev-parse", "HEAD"]).unwrap();
         crate::git::git(&worktree, &["checkout", "--detach", "HEAD"]).unwrap();
 
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         let repo = &result.repos[0];
         assert_eq!(
             repo.branch_state.expected_branch,
@@ -249,7 +435,7 @@ mod tests {
         std::fs::create_dir_all(&worktree).unwrap();
         std::fs::write(worktree.join("README.txt"), "not a git worktree").unwrap();
 
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         let repo = &result.repos[0];
 
         assert!(matches!(
@@ -292,7 +478,7 @@ mod tests {
         std::fs::remove_dir_all(&worktree).unwrap();
         std::os::unix::fs::symlink("/missing/target", &worktree).unwrap();
 
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         let repo = &result.repos[0];
 
         assert!(matches!(
@@ -326,7 +512,7 @@ mod tests {
             vec![make_repo("not-git", "dliv/lookup-parent")],
         );
 
-        let result = cmd_status(&forest_dir, &meta).unwrap();
+        let result = cmd_status(&forest_dir, &meta, &RepoFilter::default()).unwrap();
         let repo = &result.repos[0];
 
         assert!(matches!(
@@ -0,0 +1,593 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::branch_state::{compact_git_error, path_exists_or_symlink, WorktreeBranchState};
+use super::outcome::{planned_results, PlannedRepo};
+use super::status::parse_left_right_counts;
+use crate::meta::{ForestMeta, RepoMeta};
+use crate::paths::{ForestName, RepoName};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStrategy {
+    Rebase,
+    Merge,
+}
+
+pub struct SyncInputs {
+    pub strategy: SyncStrategy,
+    /// Stash uncommitted changes around the update instead of skipping dirty repos.
+    pub autostash: bool,
+    pub no_fetch: bool,
+    pub dry_run: bool,
+}
+
+pub struct SyncPlan {
+    pub forest_name: ForestName,
+    pub forest_dir: PathBuf,
+    pub strategy: SyncStrategy,
+    pub autostash: bool,
+    pub repo_plans: Vec<SyncRepoPlan>,
+}
+
+pub struct SyncRepoPlan {
+    pub name: RepoName,
+    pub worktree: PathBuf,
+    /// Remote-tracking ref of the recorded base branch, e.g. `origin/main`.
+    pub base_ref: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub action: SyncAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncAction {
+    Update,
+    UpToDate,
+    Skip { reason: String },
+    Fail { error: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncResult {
+    pub forest_name: ForestName,
+    pub strategy: SyncStrategy,
+    pub dry_run: bool,
+    pub autostash: bool,
+    pub repos: Vec<SyncRepoResult>,
+    /// Repos whose update stopped on conflicts (and was aborted).
+    pub conflicts: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncRepoResult {
+    pub name: RepoName,
+    pub base_ref: Option<String>,
+    /// Commits on the forest branch that are not on `base_ref`, before syncing.
+    pub ahead: usize,
+    /// Commits on `base_ref` that are not on the forest branch, before syncing.
+    pub behind: usize,
+    pub outcome: SyncOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum SyncOutcome {
+    /// Rebased or merged (or would be, in a dry run).
+    Updated,
+    UpToDate,
+    Skipped {
+        reason: String,
+    },
+    /// The rebase or merge hit conflicts and was aborted.
+    Conflict {
+        files: Vec<String>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+// --- Planning (read-only) ---
+
+pub fn plan_sync(
+    forest_dir: &Path,
+    meta: &ForestMeta,
+    inputs: &SyncInputs,
+    fetch_errors: &[Option<String>],
+) -> SyncPlan {
+    debug_assert_eq!(fetch_errors.len(), meta.repos.len());
+    let repo_plans = meta
+        .repos
+        .iter()
+        .zip(fetch_errors)
+        .map(|(repo, fetch_error)| {
+            plan_repo_sync(forest_dir, repo, inputs.autostash, fetch_error.as_deref())
+        })
+        .collect();
+
+    SyncPlan {
+        forest_name: meta.name.clone(),
+        forest_dir: forest_dir.to_path_buf(),
+        strategy: inputs.strategy,
+        autostash: inputs.autostash,
+        repo_plans,
+    }
+}
+
+fn plan_repo_sync(
+    forest_dir: &Path,
+    repo: &RepoMeta,
+    autostash: bool,
+    fetch_error: Option<&str>,
+) -> SyncRepoPlan {
+    let worktree = forest_dir.join(repo.name.as_str());
+    let mut plan = SyncRepoPlan {
+        name: repo.name.clone(),
+        worktree,
+        base_ref: None,
+        ahead: 0,
+        behind: 0,
+        action: SyncAction::UpToDate,
+    };
+    let skip = |reason: String| SyncAction::Skip { reason };
+
+    if !path_exists_or_symlink(&plan.worktree) {
+        plan.action = skip("worktree missing".to_string());
+        return plan;
+    }
+    if let Some(error) = fetch_error {
+        plan.action = SyncAction::Fail {
+            error: format!("fetch failed: {}", compact_git_error(error)),
+        };
+        return plan;
+    }
+    let branch_state = WorktreeBranchState::read(&plan.worktree, &repo.branch);
+    if let Some(message) = branch_state
+        .drift_message()
+        .or_else(|| branch_state.lookup_error_message())
+    {
+        plan.action = skip(message);
+        return plan;
+    }
+    let Some(remote) = &repo.remote else {
+        plan.action = skip("no remote recorded".to_string());
+        return plan;
+    };
+
+    let base_ref = format!("{}/{}", remote, repo.base_branch);
+    let full_ref = format!("refs/remotes/{}", base_ref);
+    if !matches!(crate::git::ref_exists(&plan.worktree, &full_ref), Ok(true)) {
+        plan.action = skip(format!("{} not found", base_ref));
+        return plan;
+    }
+    plan.base_ref = Some(base_ref);
+
+    let range = format!("{}...HEAD", full_ref);
+    match crate::git::git(
+        &plan.worktree,
+        &["rev-list", "--left-right", "--count", &range],
+    )
+    .map(|counts| parse_left_right_counts(&counts))
+    {
+        Ok(Some((behind, ahead))) => {
+            plan.behind = behind;
+            plan.ahead = ahead;
+        }
+        Ok(None) => {
+            plan.action = SyncAction::Fail {
+                error: format!("could not count commits against {}", full_ref),
+            };
+            return plan;
+        }
+        Err(e) => {
+            plan.action = SyncAction::Fail {
+                error: compact_git_error(&e.to_string()),
+            };
+            return plan;
+        }
+    }
+
+    plan.action = if plan.behind == 0 {
+        SyncAction::UpToDate
+    } else if !autostash && has_tracked_changes(&plan.worktree) {
+        skip("uncommitted changes; use --autostash".to_string())
+    } else {
+        SyncAction::Update
+    };
+    plan
+}
+
+/// Rebase and merge refuse to run over modified tracked files; untracked
+/// files only matter if the update would overwrite them.
+fn has_tracked_changes(worktree: &Path) -> bool {
+    crate::git::git(worktree, &["status", "--porcelain", "--untracked-files=no"])
+        .map(|output| !output.is_empty())
+        .unwrap_or(false)
+}
+
+impl PlannedRepo for SyncRepoPlan {
+    type Outcome = SyncOutcome;
+    type Result = SyncRepoResult;
+
+    fn planned_outcome(&self) -> SyncOutcome {
+        match &self.action {
+            SyncAction::Update => SyncOutcome::Updated,
+            SyncAction::UpToDate => SyncOutcome::UpToDate,
+            SyncAction::Skip { reason } => SyncOutcome::Skipped {
+                reason: reason.clone(),
+            },
+            SyncAction::Fail { error } => SyncOutcome::Failed {
+                error: error.clone(),
+            },
+        }
+    }
+
+    fn result(&self, outcome: SyncOutcome) -> SyncRepoResult {
+        SyncRepoResult {
+            name: self.name.clone(),
+            base_ref: self.base_ref.clone(),
+            ahead: self.ahead,
+            behind: self.behind,
+            outcome,
+        }
+    }
+}
+
+/// Collect conflicts and errors from the per-repo outcomes.
+fn finish(plan: &SyncPlan, dry_run: bool, repos: Vec<SyncRepoResult>) -> SyncResult {
+    let mut conflicts = Vec::new();
+    let mut errors = Vec::new();
+    for repo in &repos {
+        match &repo.outcome {
+            SyncOutcome::Conflict { files } => {
+                conflicts.push(repo.name.to_string());
+                errors.push(format!(
+                    "{}: conflicts {} {} ({}); the {} was aborted\n  hint: run `git {} {}` in {} and resolve the conflicts by hand",
+                    repo.name,
+                    strategy_verb(plan.strategy),
+                    repo.base_ref.as_deref().unwrap_or_default(),
+                    files.join(", "),
+                    strategy_name(plan.strategy),
+                    strategy_name(plan.strategy),
+                    repo.base_ref.as_deref().unwrap_or_default(),
+                    plan.forest_dir.join(repo.name.as_str()).display()
+                ));
+            }
+            SyncOutcome::Failed { error } => {
+                errors.push(format!("{}: {}", repo.name, error));
+            }
+            SyncOutcome::Updated | SyncOutcome::UpToDate | SyncOutcome::Skipped { .. } => {}
+        }
+    }
+
+    SyncResult {
+        forest_name: plan.forest_name.clone(),
+        strategy: plan.strategy,
+        dry_run,
+        autostash: plan.autostash,
+        repos,
+        conflicts,
+        errors,
+    }
+}
+
+fn strategy_name(strategy: SyncStrategy) -> &'static str {
+    match strategy {
+        SyncStrategy::Rebase => "rebase",
+        SyncStrategy::Merge => "merge",
+    }
+}
+
+fn strategy_verb(strategy: SyncStrategy) -> &'static str {
+    match strategy {
+        SyncStrategy::Rebase => "rebasing onto",
+        SyncStrategy::Merge => "merging",
+    }
+}
+
+// --- Execution (impure) ---
+
+/// Fetch each repo's recorded remote, returning per-repo errors in meta order.
+fn fetch_forest_remotes(forest_dir: &Path, meta: &ForestMeta) -> Vec<Option<String>> {
+    let mut errors: Vec<Option<String>> = meta.repos.iter().map(|_| None).collect();
+    crate::parallel::run_bounded(
+        &meta.repos,
+        crate::parallel::DEFAULT_JOBS,
+        |repo| {
+            let worktree = forest_dir.join(repo.name.as_str());
+            match &repo.remote {
+                Some(remote) if worktree.is_dir() => crate::git::git(&worktree, &["fetch", remote])
+                    .err()
+                    .map(|e| e.to_string()),
+                _ => None,
+            }
+        },
+        |index, error| {
+            errors[index] = error;
+            true
+        },
+    );
+    errors
+}
+
+/// Rebase or merge every planned repo. A repo that hits conflicts is aborted
+/// back to where it started; the rest of the forest still syncs (ADR 0009).
+pub fn execute_sync(plan: &SyncPlan) -> SyncResult {
+    let repos = plan
+        .repo_plans
+        .iter()
+        .map(|rp| match &rp.action {
+            SyncAction::Update => rp.result(sync_repo(rp, plan.strategy, plan.autostash)),
+            _ => rp.planned_result(),
+        })
+        .collect();
+    finish(plan, false, repos)
+}
+
+fn sync_repo(rp: &SyncRepoPlan, strategy: SyncStrategy, autostash: bool) -> SyncOutcome {
+    let base_ref = rp
+        .base_ref
+        .as_deref()
+        .expect("planned updates have a base ref");
+    let mut args = match strategy {
+        SyncStrategy::Rebase => vec!["rebase"],
+        SyncStrategy::Merge => vec!["merge", "--no-edit"],
+    };
+    if autostash {
+        args.push("--autostash");
+    }
+    args.push(base_ref);
+
+    let Err(e) = crate::git::git(&rp.worktree, &args) else {
+        return SyncOutcome::Updated;
+    };
+
+    let files: Vec<String> =
+        crate::git::git(&rp.worktree, &["diff", "--name-only", "--diff-filter=U"])
+            .map(|out| out.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+    let _ = crate::git::git(&rp.worktree, &[strategy_name(strategy), "--abort"]);
+
+    if files.is_empty() {
+        SyncOutcome::Failed {
+            error: compact_git_error(&e.to_string()),
+        }
+    } else {
+        SyncOutcome::Conflict { files }
+    }
+}
+
+pub fn cmd_sync(forest_dir: &Path, meta: &ForestMeta, inputs: SyncInputs) -> Result<SyncResult> {
+    debug_assert!(forest_dir.is_absolute(), "forest_dir must be absolute");
+
+    let fetch_errors = if inputs.no_fetch {
+        meta.repos.iter().map(|_| None).collect()
+    } else {
+        fetch_forest_remotes(forest_dir, meta)
+    };
+
+    let plan = plan_sync(forest_dir, meta, &inputs, &fetch_errors);
+
+    if inputs.dry_run {
+        let repos = planned_results(&plan.repo_plans);
+        return Ok(finish(&plan, true, repos));
+    }
+
+    Ok(execute_sync(&plan))
+}
+
+pub fn format_sync_human(result: &SyncResult) -> String {
+    let mut lines = Vec::new();
+
+    if result.dry_run {
+        lines.push("Dry run — no changes will be made.".to_string());
+        lines.push(String::new());
+    }
+    lines.push(format!(
+        "Syncing forest {:?} ({})",
+        result.forest_name.as_str(),
+        strategy_name(result.strategy)
+    ));
+
+    for repo in &result.repos {
+        let base = repo.base_ref.as_deref().unwrap_or("base");
+        let detail = match &repo.outcome {
+            SyncOutcome::Updated => {
+                let verb = match (result.strategy, result.dry_run) {
+                    (SyncStrategy::Rebase, true) => "would rebase onto",
+                    (SyncStrategy::Rebase, false) => "rebased onto",
+                    (SyncStrategy::Merge, true) => "would merge",
+                    (SyncStrategy::Merge, false) => "merged",
+                };
+                format!("{} {} ({} behind)", verb, base, repo.behind)
+            }
+            SyncOutcome::UpToDate => format!("up to date with {}", base),
+            SyncOutcome::Skipped { reason } => format!("skipped ({})", reason),
+            SyncOutcome::Conflict { files } => {
+                format!("CONFLICT in {} (aborted)", files.join(", "))
+            }
+            SyncOutcome::Failed { .. } => "FAILED".to_string(),
+        };
+        lines.push(format!("  {}: {}", repo.name, detail));
+    }
+
+    if !result.errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        for error in &result.errors {
+            lines.push(format!("  {}", super::rm::format_error_single_line(error)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::ForestMode;
+    use crate::testutil::{new_forest, TestEnv};
+
+    fn setup_forest(name: &str) -> (TestEnv, PathBuf, ForestMeta) {
+        let env = TestEnv::new();
+        let (forest_dir, meta) = new_forest(&env, name, ForestMode::Feature, &["foo-api"], &[]);
+        let source = env.repo_path("foo-api");
+        crate::git::git(&source, &["config", "user.name", "Test"]).unwrap();
+        crate::git::git(&source, &["config", "user.email", "test@test.com"]).unwrap();
+        (env, forest_dir, meta)
+    }
+
+    fn commit_file(dir: &Path, file: &str, contents: &str) {
+        std::fs::write(dir.join(file), contents).unwrap();
+        crate::git::git(dir, &["add", file]).unwrap();
+        crate::git::git(dir, &["commit", "-m", file]).unwrap();
+    }
+
+    /// Advance `origin/main` by one commit touching `file`.
+    fn advance_base(meta: &ForestMeta, file: &str, contents: &str) {
+        let source = &meta.repos[0].source;
+        commit_file(source, file, contents);
+        crate::git::git(source, &["push", "origin", "main"]).unwrap();
+    }
+
+    fn inputs(strategy: SyncStrategy, dry_run: bool) -> SyncInputs {
+        SyncInputs {
+            strategy,
+            autostash: false,
+            no_fetch: false,
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn sync_rebases_onto_updated_base_after_dry_run() {
+        let (_env, forest_dir, meta) = setup_forest("sync-rebase");
+        let worktree = forest_dir.join("foo-api");
+        commit_file(&worktree, "feature.txt", "feature");
+        advance_base(&meta, "base.txt", "base");
+        let head_before = crate::git::git(&worktree, &["rev-parse", "HEAD"]).unwrap();
+
+        let dry = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, true)).unwrap();
+        assert_eq!(dry.repos[0].outcome, SyncOutcome::Updated);
+        assert_eq!(dry.repos[0].base_ref.as_deref(), Some("origin/main"));
+        assert_eq!((dry.repos[0].ahead, dry.repos[0].behind), (1, 1));
+        assert!(format_sync_human(&dry).contains("would rebase onto origin/main (1 behind)"));
+        assert_eq!(
+            crate::git::git(&worktree, &["rev-parse", "HEAD"]).unwrap(),
+            head_before
+        );
+
+        let result = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, false)).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repos[0].outcome, SyncOutcome::Updated);
+        assert!(worktree.join("base.txt").exists());
+        // Rebased, not merged: history stays linear
+        let parents =
+            crate::git::git(&worktree, &["rev-list", "--parents", "-n", "1", "HEAD"]).unwrap();
+        assert_eq!(parents.split_whitespace().count(), 2);
+
+        let again = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, false)).unwrap();
+        assert_eq!(again.repos[0].outcome, SyncOutcome::UpToDate);
+    }
+
+    #[test]
+    fn sync_merge_creates_merge_commit() {
+        let (_env, forest_dir, meta) = setup_forest("sync-merge");
+        let worktree = forest_dir.join("foo-api");
+        commit_file(&worktree, "feature.txt", "feature");
+        advance_base(&meta, "base.txt", "base");
+
+        let result = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Merge, false)).unwrap();
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert_eq!(result.repos[0].outcome, SyncOutcome::Updated);
+        let parents =
+            crate::git::git(&worktree, &["rev-list", "--parents", "-n", "1", "HEAD"]).unwrap();
+        assert_eq!(parents.split_whitespace().count(), 3);
+    }
+
+    #[test]
+    fn sync_skips_dirty_worktree_unless_autostash() {
+        let (_env, forest_dir, meta) = setup_forest("sync-dirty");
+        let worktree = forest_dir.join("foo-api");
+        commit_file(&worktree, "notes.txt", "v1");
+        std::fs::write(worktree.join("notes.txt"), "wip").unwrap();
+        advance_base(&meta, "base.txt", "base");
+
+        let skipped = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, false)).unwrap();
+        assert!(matches!(
+            &skipped.repos[0].outcome,
+            SyncOutcome::Skipped { reason } if reason.contains("--autostash")
+        ));
+        assert!(skipped.errors.is_empty());
+        assert!(!worktree.join("base.txt").exists());
+
+        let stashed = cmd_sync(
+            &forest_dir,
+            &meta,
+            SyncInputs {
+                autostash: true,
+                ..inputs(SyncStrategy::Rebase, false)
+            },
+        )
+        .unwrap();
+        assert!(stashed.errors.is_empty(), "errors: {:?}", stashed.errors);
+        assert_eq!(stashed.repos[0].outcome, SyncOutcome::Updated);
+        assert!(worktree.join("base.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("notes.txt")).unwrap(),
+            "wip"
+        );
+    }
+
+    #[test]
+    fn sync_conflict_is_aborted_and_reported() {
+        let (_env, forest_dir, meta) = setup_forest("sync-conflict");
+        let worktree = forest_dir.join("foo-api");
+        commit_file(&worktree, "shared.txt", "forest side");
+        advance_base(&meta, "shared.txt", "base side");
+        let head_before = crate::git::git(&worktree, &["rev-parse", "HEAD"]).unwrap();
+
+        let result = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, false)).unwrap();
+        assert_eq!(
+            result.repos[0].outcome,
+            SyncOutcome::Conflict {
+                files: vec!["shared.txt".to_string()]
+            }
+        );
+        assert_eq!(result.conflicts, vec!["foo-api".to_string()]);
+        assert!(result.errors[0].contains("hint: run `git rebase origin/main`"));
+
+        // Aborted: back on the original commit with no rebase in progress
+        assert_eq!(
+            crate::git::git(&worktree, &["rev-parse", "HEAD"]).unwrap(),
+            head_before
+        );
+        let status = crate::git::git(&worktree, &["status", "--porcelain"]).unwrap();
+        assert!(status.is_empty(), "status: {}", status);
+    }
+
+    #[test]
+    fn sync_skips_missing_worktree_and_drift() {
+        let (_env, forest_dir, meta) = setup_forest("sync-skip");
+        let worktree = forest_dir.join("foo-api");
+        crate::git::git(&worktree, &["checkout", "--detach", "HEAD"]).unwrap();
+
+        let drift = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, true)).unwrap();
+        assert!(matches!(
+            &drift.repos[0].outcome,
+            SyncOutcome::Skipped { reason } if reason.contains("branch drift")
+        ));
+
+        std::fs::remove_dir_all(&worktree).unwrap();
+        let missing = cmd_sync(&forest_dir, &meta, inputs(SyncStrategy::Rebase, true)).unwrap();
+        assert_eq!(
+            missing.repos[0].outcome,
+            SyncOutcome::Skipped {
+                reason: "worktree missing".to_string()
+            }
+        );
+    }
+}
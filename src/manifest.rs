@@ -0,0 +1,164 @@
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::meta::ForestMode;
+use crate::paths::{ForestName, RepoName};
+
+/// Portable snapshot of a forest written by `git forest export` and rebuilt by
+/// `git forest new --from-manifest`.
+///
+/// Unlike `.forest-meta.toml` it records no local paths: repos are matched to
+/// the reader's template by name or remote URL.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForestManifest {
+    pub name: ForestName,
+    pub mode: ForestMode,
+    pub repos: Vec<ManifestRepo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestRepo {
+    pub name: RepoName,
+    /// URL of the repo's recorded remote, used to match repos whose local name differs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_url: Option<String>,
+    pub branch: String,
+    pub base_branch: String,
+    /// Full SHA checked out in the worktree at export time.
+    pub commit: String,
+}
+
+impl ForestManifest {
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("failed to serialize forest manifest")
+    }
+
+    pub fn read(path: &Path) -> Result<ForestManifest> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read manifest from {}", path.display()))?;
+        let manifest: ForestManifest = toml::from_str(&content).with_context(|| {
+            format!(
+                "failed to parse manifest {}\n  hint: manifests are written by `git forest export`",
+                path.display()
+            )
+        })?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(!self.repos.is_empty(), "manifest lists no repos");
+        let mut seen = HashSet::new();
+        for repo in &self.repos {
+            ensure!(
+                seen.insert(repo.name.as_str()),
+                "manifest lists repo {} more than once",
+                repo.name
+            );
+            if !is_full_sha(&repo.commit) {
+                bail!(
+                    "manifest commit {:?} for repo {} is not a full commit SHA\n  hint: re-export the forest with `git forest export`",
+                    repo.commit,
+                    repo.name
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_full_sha(s: &str) -> bool {
+    matches!(s.len(), 40 | 64) && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Reduce a remote URL to `host/path` so SSH and HTTPS spellings of the same
+/// repo compare equal, e.g. `git@github.com:org/api.git` and
+/// `https://github.com/org/api` both become `github.com/org/api`.
+pub fn normalize_remote_url(url: &str) -> String {
+    let url = url.trim();
+    let host_and_path = match url.split_once("://") {
+        Some((_, rest)) => {
+            let rest = rest.split_once('@').map_or(rest, |(_, host)| host);
+            rest.split_once('/').map(|(host, path)| {
+                // Drop an explicit port: host:22/org/api
+                (host.split_once(':').map_or(host, |(h, _)| h), path)
+            })
+        }
+        // scp-like syntax: user@host:org/api
+        None => url
+            .split_once(':')
+            .filter(|(host, _)| !host.contains('/'))
+            .map(|(host, path)| (host.split_once('@').map_or(host, |(_, h)| h), path)),
+    };
+    let normalized = match host_and_path {
+        Some((host, path)) => format!("{}/{}", host.to_lowercase(), path),
+        None => url.to_string(),
+    };
+    normalized
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_manifest() -> ForestManifest {
+        ForestManifest {
+            name: ForestName::new("checkout-flow".to_string()).unwrap(),
+            mode: ForestMode::Feature,
+            repos: vec![ManifestRepo {
+                name: RepoName::new("foo-api".to_string()).unwrap(),
+                remote_url: Some("git@github.com:org/foo-api.git".to_string()),
+                branch: "sue/checkout-flow".to_string(),
+                base_branch: "main".to_string(),
+                commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn manifest_round_trips_through_toml() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("forest.toml");
+        let manifest = sample_manifest();
+        std::fs::write(&path, manifest.to_toml().unwrap()).unwrap();
+
+        assert_eq!(ForestManifest::read(&path).unwrap(), manifest);
+    }
+
+    #[test]
+    fn manifest_rejects_abbreviated_commit_and_duplicate_repos() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("forest.toml");
+
+        let mut short = sample_manifest();
+        short.repos[0].commit = "0123456".to_string();
+        std::fs::write(&path, short.to_toml().unwrap()).unwrap();
+        let err = ForestManifest::read(&path).unwrap_err().to_string();
+        assert!(err.contains("not a full commit SHA"), "error: {}", err);
+
+        let mut duplicate = sample_manifest();
+        duplicate.repos.push(duplicate.repos[0].clone());
+        std::fs::write(&path, duplicate.to_toml().unwrap()).unwrap();
+        let err = ForestManifest::read(&path).unwrap_err().to_string();
+        assert!(err.contains("more than once"), "error: {}", err);
+    }
+
+    #[test]
+    fn normalize_remote_url_matches_ssh_and_https() {
+        let expected = "github.com/org/foo-api";
+        for url in [
+            "git@github.com:org/foo-api.git",
+            "https://github.com/org/foo-api",
+            "https://user@GitHub.com/org/foo-api.git/",
+            "ssh://git@github.com:22/org/foo-api.git",
+        ] {
+            assert_eq!(normalize_remote_url(url), expected, "url: {}", url);
+        }
+        assert_eq!(normalize_remote_url("/srv/git/api.git"), "/srv/git/api");
+    }
+}
@@ -1,12 +1,14 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::Path;
 
 use crate::paths::{
     validate_disposable_root_entries, AbsolutePath, DisposableRootEntry, ForestName, RepoName,
 };
+use crate::schema::{Parsed, Schema};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, clap::ValueEnum)]
 #[serde(rename_all = "lowercase")]
@@ -31,7 +33,12 @@ pub struct ForestMeta {
     pub mode: ForestMode,
     #[serde(default)]
     pub disposable_root_entries: Vec<DisposableRootEntry>,
+    /// Snapshot of the template's `env` table at creation time.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
     pub repos: Vec<RepoMeta>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pins: Vec<Pin>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,26 +50,79 @@ pub struct RepoMeta {
     #[serde(default)]
     pub remote: Option<String>,
     pub branch_created: bool,
+    /// Snapshot of the template's `pre_remove` hook at creation time.
+    #[serde(default)]
+    pub pre_remove: Option<String>,
+    /// Commit the branch forked from its base branch when the worktree was
+    /// created. `None` for forests created before this was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_commit: Option<String>,
+}
+
+/// Named snapshot of every repo's checked-out commit, recorded by
+/// `git forest pin` and restored by `git forest checkout-pin`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Pin {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Full commit SHA per repo name.
+    pub commits: BTreeMap<String, String>,
 }
 
 impl ForestMeta {
     pub fn write(&self, path: &Path) -> Result<()> {
         self.validate()
             .context("forest metadata has invalid disposable root entries")?;
-        let content = toml::to_string_pretty(self).context("failed to serialize forest meta")?;
+        let content = self.to_toml()?;
         std::fs::write(path, content)
             .with_context(|| format!("failed to write forest meta to {}", path.display()))?;
         Ok(())
     }
 
+    /// Serialize as written to disk, stamped with `meta_version`.
+    pub fn to_toml(&self) -> Result<String> {
+        let content = toml::to_string_pretty(self).context("failed to serialize forest meta")?;
+        Ok(META_SCHEMA.stamp(&content))
+    }
+
+    /// Read metadata written by this or an older git-forest. Older layouts
+    /// are upgraded in memory and saved at `META_VERSION` on the next write.
     pub fn read(path: &Path) -> Result<ForestMeta> {
+        Ok(Self::read_versioned(path)?.value)
+    }
+
+    pub fn read_versioned(path: &Path) -> Result<Parsed<ForestMeta>> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("failed to read forest meta from {}", path.display()))?;
-        let meta: ForestMeta =
-            toml::from_str(&content).context("failed to parse forest meta TOML")?;
-        meta.validate()
+        let parsed: Parsed<ForestMeta> = META_SCHEMA.parse(&content)?;
+        parsed
+            .value
+            .validate()
             .context("forest metadata has invalid disposable root entries")?;
-        Ok(meta)
+        Ok(parsed)
+    }
+
+    /// Environment for a command run in `repo`'s worktree (`exec` and hooks):
+    /// the snapshotted template `env`, then the `FOREST_*` variables.
+    ///
+    /// `FOREST_REMOTE` is only set when the repo has a recorded remote.
+    pub fn repo_env(&self, forest_dir: &Path, repo: &RepoMeta) -> Vec<(String, String)> {
+        let mut env: Vec<(String, String)> = self
+            .env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let mut set = |key: &str, value: String| env.push((key.to_string(), value));
+        set("FOREST_NAME", self.name.to_string());
+        set("FOREST_DIR", forest_dir.display().to_string());
+        set("FOREST_MODE", self.mode.to_string());
+        set("FOREST_REPO", repo.name.to_string());
+        set("FOREST_REPO_BRANCH", repo.branch.clone());
+        set("FOREST_BASE_BRANCH", repo.base_branch.clone());
+        if let Some(remote) = &repo.remote {
+            set("FOREST_REMOTE", remote.clone());
+        }
+        env
     }
 
     fn validate(&self) -> Result<()> {
@@ -74,6 +134,18 @@ impl ForestMeta {
 }
 
 pub const META_FILENAME: &str = ".forest-meta.toml";
+
+/// Layout written by this version of git-forest, recorded as `meta_version`.
+/// Metadata without it was written before versioning and is version 1.
+pub const META_VERSION: u32 = 1;
+
+pub const META_SCHEMA: Schema = Schema {
+    what: "forest meta",
+    version_key: "meta_version",
+    current: META_VERSION,
+    detect: |_| 1,
+    migrations: &[],
+};
 pub const STAGED_META_PREFIX: &str = ".git-forest-meta-removing-";
 
 #[cfg(test)]
@@ -88,6 +160,7 @@ mod tests {
             created_at: Utc.with_ymd_and_hms(2026, 2, 7, 14, 30, 0).unwrap(),
             mode: ForestMode::Review,
             disposable_root_entries: vec![DisposableRootEntry::new(".idea".to_string()).unwrap()],
+            env: Default::default(),
             repos: vec![
                 RepoMeta {
                     name: RepoName::new("foo-api".to_string()).unwrap(),
@@ -96,6 +169,8 @@ mod tests {
                     base_branch: "dev".to_string(),
                     remote: Some("origin".to_string()),
                     branch_created: true,
+                    pre_remove: None,
+                    base_commit: None,
                 },
                 RepoMeta {
                     name: RepoName::new("foo-web".to_string()).unwrap(),
@@ -104,8 +179,11 @@ mod tests {
                     base_branch: "dev".to_string(),
                     remote: Some("upstream".to_string()),
                     branch_created: false,
+                    pre_remove: None,
+                    base_commit: None,
                 },
             ],
+            pins: vec![],
         }
     }
 
@@ -117,6 +195,7 @@ mod tests {
         let original = sample_meta();
         original.write(&path).unwrap();
         let serialized = std::fs::read_to_string(&path).unwrap();
+        assert!(serialized.starts_with("meta_version = 1\n"));
         assert!(serialized.contains("disposable_root_entries = [\".idea\"]"));
 
         let loaded = ForestMeta::read(&path).unwrap();
@@ -198,6 +277,29 @@ branch_created = true
         assert!(meta.disposable_root_entries.is_empty());
     }
 
+    #[test]
+    fn read_rejects_meta_from_newer_version() {
+        let toml = r#"
+meta_version = 2
+name = "test-forest"
+created_at = "2026-02-07T14:30:00Z"
+mode = "feature"
+repos = []
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(META_FILENAME);
+        std::fs::write(&path, toml).unwrap();
+
+        let error = ForestMeta::read(&path).unwrap_err().to_string();
+
+        assert!(
+            error.contains("forest meta has meta_version = 2"),
+            "error: {}",
+            error
+        );
+        assert!(error.contains("git forest update"), "error: {}", error);
+    }
+
     #[test]
     fn handles_review_mode() {
         let toml = r#"
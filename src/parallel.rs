@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Default number of concurrent per-repo jobs.
+pub const DEFAULT_JOBS: usize = 4;
+
+/// Run `work` over `items` on at most `jobs` worker threads.
+///
+/// `on_done` runs on the calling thread, once per finished item, in completion
+/// order. Keeping all bookkeeping there gives callers a single writer for
+/// shared state such as forest metadata. Returning `false` from `on_done`
+/// stops workers from starting further items; items already in flight still
+/// finish and are still reported.
+pub fn run_bounded<T, R, W, D>(items: &[T], jobs: usize, work: W, mut on_done: D)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    D: FnMut(usize, R) -> bool,
+{
+    debug_assert!(jobs > 0, "run_bounded requires at least one job");
+    if items.is_empty() {
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let workers = jobs.max(1).min(items.len());
+
+    std::thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, stop, work) = (&next, &stop, &work);
+            scope.spawn(move || loop {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if tx.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        for (index, result) in rx {
+            if !on_done(index, result) {
+                stop.store(true, Ordering::SeqCst);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_every_item_once() {
+        let items: Vec<usize> = (0..10).collect();
+        let mut seen = vec![0; items.len()];
+
+        run_bounded(
+            &items,
+            3,
+            |n| n * 2,
+            |index, doubled| {
+                assert_eq!(doubled, index * 2);
+                seen[index] += 1;
+                true
+            },
+        );
+
+        assert!(seen.iter().all(|&count| count == 1), "seen: {:?}", seen);
+    }
+
+    #[test]
+    fn never_exceeds_job_limit() {
+        let items: Vec<usize> = (0..12).collect();
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        run_bounded(
+            &items,
+            2,
+            |_| {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                active.fetch_sub(1, Ordering::SeqCst);
+            },
+            |_, _| true,
+        );
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
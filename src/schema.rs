@@ -0,0 +1,252 @@
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+
+/// A versioned on-disk format: the key that records its version and the
+/// migrations that upgrade older shapes to the current one.
+pub struct Schema {
+    /// Used in error messages, e.g. "config" or "forest meta".
+    pub what: &'static str,
+    pub version_key: &'static str,
+    pub current: u32,
+    /// Version of a file written before `version_key` existed, from its shape.
+    pub detect: fn(&toml::Table) -> u32,
+    /// `migrations[i]` upgrades version `i + 1` to version `i + 2`.
+    pub migrations: &'static [fn(&mut toml::Table) -> Result<()>],
+}
+
+/// A file parsed into its current shape.
+#[derive(Debug)]
+pub struct Parsed<T> {
+    pub value: T,
+    /// Version the file was written as.
+    pub from: u32,
+    /// Whether the file records its version.
+    pub versioned: bool,
+}
+
+impl<T> Parsed<T> {
+    /// Whether writing `value` back would change more than formatting.
+    pub fn needs_upgrade(&self, schema: &Schema) -> bool {
+        !self.versioned || self.from < schema.current
+    }
+}
+
+impl Schema {
+    pub fn parse<T: DeserializeOwned>(&self, contents: &str) -> Result<Parsed<T>> {
+        let mut table: toml::Table = contents
+            .parse()
+            .with_context(|| format!("failed to parse {} TOML", self.what))?;
+        let (from, versioned) = match table.remove(self.version_key) {
+            Some(value) => (self.version_of(&value)?, true),
+            None => ((self.detect)(&table), false),
+        };
+        debug_assert_eq!(self.migrations.len() as u32, self.current - 1);
+
+        if from == self.current {
+            // Deserialize the original text so errors keep their line numbers
+            let value = toml::from_str(contents)
+                .with_context(|| format!("failed to parse {} TOML", self.what))?;
+            return Ok(Parsed {
+                value,
+                from,
+                versioned,
+            });
+        }
+
+        for (index, migrate) in self.migrations.iter().enumerate().skip(from as usize - 1) {
+            migrate(&mut table).with_context(|| {
+                format!(
+                    "failed to upgrade {} from {} {} to {}",
+                    self.what,
+                    self.version_key,
+                    index + 1,
+                    index + 2
+                )
+            })?;
+        }
+        let value = table.try_into().with_context(|| {
+            format!(
+                "failed to parse {} TOML upgraded from {} {}",
+                self.what, self.version_key, from
+            )
+        })?;
+        Ok(Parsed {
+            value,
+            from,
+            versioned,
+        })
+    }
+
+    /// Record the current version at the top of serialized `contents`.
+    pub fn stamp(&self, contents: &str) -> String {
+        format!("{} = {}\n{}", self.version_key, self.current, contents)
+    }
+
+    fn version_of(&self, value: &toml::Value) -> Result<u32> {
+        let Some(version) = value.as_integer() else {
+            bail!(
+                "{} {} must be an integer, found {}",
+                self.what,
+                self.version_key,
+                value
+            );
+        };
+        if version > self.current as i64 {
+            bail!(
+                "{} has {} = {}, but this git-forest only understands up to {}\n  hint: run `git forest update` to get a newer git-forest",
+                self.what,
+                self.version_key,
+                version,
+                self.current
+            );
+        }
+        if version < 1 {
+            bail!(
+                "{} has invalid {} = {}\n  hint: versions start at 1",
+                self.what,
+                self.version_key,
+                version
+            );
+        }
+        Ok(version as u32)
+    }
+}
+
+/// Line diff of `old` against `new` in unified format, without file headers.
+pub fn unified_diff(old: &str, new: &str) -> String {
+    const CONTEXT: usize = 3;
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut ops: Vec<(char, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', b[j]));
+            j += 1;
+        }
+    }
+
+    let is_change = |index: usize| ops[index].0 != ' ';
+    let mut out = String::new();
+    let mut next = 0;
+    while let Some(first) = (next..ops.len()).find(|&index| is_change(index)) {
+        let mut last = first;
+        while let Some(change) = (last + 1..ops.len()).find(|&index| is_change(index)) {
+            if change - last > 2 * CONTEXT {
+                break;
+            }
+            last = change;
+        }
+        let start = first.saturating_sub(CONTEXT);
+        let stop = (last + CONTEXT + 1).min(ops.len());
+
+        let count = |range: std::ops::Range<usize>, skip: char| {
+            ops[range].iter().filter(|(kind, _)| *kind != skip).count()
+        };
+        let range = |before: usize, len: usize| {
+            let first_line = if len == 0 { before } else { before + 1 };
+            format!("{},{}", first_line, len)
+        };
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(count(0..start, '+'), count(start..stop, '+')),
+            range(count(0..start, '-'), count(start..stop, '-')),
+        ));
+        for (kind, line) in &ops[start..stop] {
+            out.push_str(&format!("{}{}\n", kind, line));
+        }
+        next = stop;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Sample {
+        greeting: String,
+    }
+
+    fn rename_hello(table: &mut toml::Table) -> Result<()> {
+        let hello = table.remove("hello").context("missing hello")?;
+        table.insert("greeting".to_string(), hello);
+        Ok(())
+    }
+
+    const SAMPLE: Schema = Schema {
+        what: "sample",
+        version_key: "sample_version",
+        current: 2,
+        detect: |table| if table.contains_key("hello") { 1 } else { 2 },
+        migrations: &[rename_hello],
+    };
+
+    #[test]
+    fn parse_detects_and_upgrades_unversioned_files() {
+        let parsed: Parsed<Sample> = SAMPLE.parse("hello = \"hi\"\n").unwrap();
+        assert_eq!(parsed.value.greeting, "hi");
+        assert_eq!(parsed.from, 1);
+        assert!(!parsed.versioned);
+        assert!(parsed.needs_upgrade(&SAMPLE));
+
+        let parsed: Parsed<Sample> = SAMPLE
+            .parse("sample_version = 1\nhello = \"hi\"\n")
+            .unwrap();
+        assert_eq!(parsed.value.greeting, "hi");
+        assert!(parsed.versioned);
+
+        let parsed: Parsed<Sample> = SAMPLE.parse(&SAMPLE.stamp("greeting = \"hi\"\n")).unwrap();
+        assert_eq!(parsed.from, 2);
+        assert!(!parsed.needs_upgrade(&SAMPLE));
+    }
+
+    #[test]
+    fn parse_rejects_versions_from_newer_binaries() {
+        let err = SAMPLE
+            .parse::<Sample>("sample_version = 3\ngreeting = \"hi\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("only understands up to 2"), "error: {}", err);
+        assert!(err.contains("git forest update"), "error: {}", err);
+
+        let err = SAMPLE
+            .parse::<Sample>("sample_version = \"2\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("must be an integer"), "error: {}", err);
+    }
+
+    #[test]
+    fn unified_diff_groups_changes_into_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+        assert_eq!(
+            unified_diff(old, new),
+            "@@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n@@ -10,3 +10,4 @@\n j\n k\n l\n+m\n"
+        );
+        assert_eq!(unified_diff(old, old), "");
+        assert_eq!(unified_diff("", "x\n"), "@@ -0,0 +1,1 @@\n+x\n");
+    }
+}
@@ -1,11 +1,15 @@
 #![cfg(test)]
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::TempDir;
 
-use crate::config::{ResolvedRepo, ResolvedTemplate};
-use crate::meta::{ForestMeta, ForestMode, RepoMeta};
+use crate::config::{
+    write_raw_config_atomic, MultiTemplateConfig, ResolvedConfig, ResolvedRepo, ResolvedTemplate,
+    TemplateConfig,
+};
+use crate::meta::{ForestMeta, ForestMode, RepoMeta, META_FILENAME};
 use crate::paths::{AbsolutePath, ForestName, RepoName};
 use chrono::{DateTime, Utc};
 
@@ -121,9 +125,12 @@ impl TestEnv {
             .iter()
             .map(|name| ResolvedRepo {
                 path: self.repo_path(name),
+                url: None,
                 name: RepoName::new(name.to_string()).unwrap(),
                 base_branch: "main".to_string(),
                 remote: "origin".to_string(),
+                post_create: None,
+                pre_remove: None,
             })
             .collect();
 
@@ -132,6 +139,9 @@ impl TestEnv {
             base_branch: "main".to_string(),
             feature_branch_template: "testuser/{name}".to_string(),
             disposable_root_entries: vec![],
+            post_create: None,
+            pre_remove: None,
+            env: Default::default(),
             repos,
         }
     }
@@ -150,7 +160,9 @@ pub fn make_meta(
         created_at,
         mode,
         disposable_root_entries: vec![],
+        env: Default::default(),
         repos,
+        pins: vec![],
     }
 }
 
@@ -162,6 +174,8 @@ pub fn make_repo(name: &str, branch: &str) -> RepoMeta {
         base_branch: "dev".to_string(),
         remote: Some("origin".to_string()),
         branch_created: true,
+        pre_remove: None,
+        base_commit: None,
     }
 }
 
@@ -197,3 +211,57 @@ pub fn setup_forest_with_git_repos(base: &Path) -> (PathBuf, ForestMeta) {
 
     (forest_dir, meta)
 }
+
+/// Creates `repos` (each with a bare `origin`) and a forest named `name` over
+/// them with `cmd_new`, without fetching or hooks. Returns the forest dir and
+/// its metadata as written.
+pub fn new_forest(
+    env: &TestEnv,
+    name: &str,
+    mode: ForestMode,
+    repos: &[&str],
+    repo_branches: &[(&str, &str)],
+) -> (PathBuf, ForestMeta) {
+    for repo in repos {
+        env.create_repo_with_remote(repo);
+    }
+    let inputs = crate::commands::NewInputs {
+        name: name.to_string(),
+        mode,
+        branch_override: None,
+        repo_branches: repo_branches
+            .iter()
+            .map(|(repo, branch)| (repo.to_string(), branch.to_string()))
+            .collect(),
+        pull_requests: vec![],
+        no_fetch: true,
+        dry_run: false,
+        jobs: 1,
+        no_hooks: true,
+        parallel_hooks: false,
+    };
+    let forest_dir = crate::commands::cmd_new(inputs, &env.default_template(repos))
+        .unwrap()
+        .forest_dir
+        .into_inner();
+    let meta = ForestMeta::read(&forest_dir.join(META_FILENAME)).unwrap();
+    (forest_dir, meta)
+}
+
+/// Write `config` back out as a raw config, for tests that start from a
+/// resolved one.
+pub fn write_config_atomic(path: &Path, config: &ResolvedConfig) -> anyhow::Result<()> {
+    let raw = MultiTemplateConfig {
+        default_template: config.default_template.clone(),
+        project: BTreeMap::new(),
+        trusted_projects: BTreeMap::new(),
+        version_check: config.version_check.clone(),
+        template: config
+            .templates
+            .iter()
+            .map(|(name, tmpl)| (name.clone(), TemplateConfig::from(tmpl)))
+            .collect(),
+    };
+
+    write_raw_config_atomic(path, &raw)
+}
@@ -185,6 +185,61 @@ fn init_json_output() {
         .stdout(predicates::str::contains("\"worktree_base\""));
 }
 
+#[test]
+fn init_custom_name_remote_and_detected_base_branch() {
+    let tmp = tempfile::tempdir().unwrap();
+    let fake_home = tmp.path().join("home");
+    std::fs::create_dir_all(&fake_home).unwrap();
+    let repo = create_repo_with_remote(tmp.path(), "foo-api");
+    run_git(&repo, &["remote", "set-head", "origin", "--auto"]);
+
+    let repo_arg = format!("{}:api", repo.display());
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "init",
+            "--feature-branch-template",
+            "testuser/{name}",
+            "--repo",
+            &repo_arg,
+            "--repo-remote",
+            "api=origin",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["repos"][0]["name"], "api");
+    assert_eq!(json["repos"][0]["remote"], "origin");
+    assert_eq!(json["repos"][0]["base_branch"], "main");
+
+    bin_cmd()
+        .args([
+            "init",
+            "--template",
+            "other",
+            "--feature-branch-template",
+            "testuser/{name}",
+            "--repo",
+            repo.to_str().unwrap(),
+            "--repo-remote",
+            "api=origin",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "unknown repo(s) in --repo-remote: api",
+        ));
+}
+
 #[test]
 fn subcommand_new_requires_mode() {
     bin_cmd()
@@ -364,6 +419,76 @@ fn run_git(dir: &std::path::Path, args: &[&str]) {
     );
 }
 
+#[test]
+fn init_and_new_clone_url_repos_into_the_data_dir() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src").join("foo-api");
+    create_test_git_repo(&src);
+    let bare = tmp.path().join("bare").join("foo-api.git");
+    run_git(
+        tmp.path(),
+        &[
+            "clone",
+            "--bare",
+            src.to_str().unwrap(),
+            bare.to_str().unwrap(),
+        ],
+    );
+    let url = format!("file://{}", bare.display());
+
+    let fake_home = tmp.path().join("home");
+    let data_home = tmp.path().join("data");
+    let worktree_base = tmp.path().join("worktrees");
+    let clone_path = data_home
+        .join(bin_name())
+        .join("repos")
+        .join(bare.strip_prefix("/").unwrap().with_extension(""));
+    let run = |args: &[&str]| {
+        bin_cmd()
+            .args(args)
+            .env("HOME", &fake_home)
+            .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+            .env("XDG_DATA_HOME", &data_home)
+            .assert()
+    };
+
+    run(&[
+        "init",
+        "--feature-branch-template",
+        "testuser/{name}",
+        "--repo",
+        &url,
+        "--repo-remote",
+        "foo-api=upstream",
+        "--worktree-base",
+        worktree_base.to_str().unwrap(),
+    ])
+    .success()
+    .stdout(predicates::str::contains(format!("cloned from {}", url)));
+    assert!(clone_path.join(".git").is_dir());
+    let config = std::fs::read_to_string(expected_config_path(&fake_home)).unwrap();
+    assert!(config.contains(&format!("url = \"{}\"", url)), "{config}");
+    assert!(!config.contains("path = "), "{config}");
+
+    // A missing clone is made again on first use
+    std::fs::remove_dir_all(&clone_path).unwrap();
+    run(&["new", "cloned", "--mode", "feature", "--dry-run"])
+        .success()
+        .stdout(predicates::str::contains(format!("would clone {}", url)));
+    assert!(!clone_path.exists());
+
+    let output = run(&["new", "cloned", "--mode", "feature", "--no-fetch", "--json"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["repos"][0]["clone"]["url"], url.as_str());
+    assert!(clone_path.join(".git").is_dir());
+    assert!(worktree_base.join("cloned").join("foo-api").exists());
+    run_git(&clone_path, &["remote", "get-url", "upstream"]);
+}
+
 #[test]
 fn new_feature_mode_creates_forest() {
     let (tmp, fake_home, worktree_base) = setup_new_env();
@@ -900,6 +1025,1076 @@ fn json_rm_reports_detached_head_safety_outcomes() {
     drop(tmp);
 }
 
+#[test]
+fn exec_parallel_json_reports_each_repo() {
+    let (tmp, fake_home, _worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "exec-par", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "exec",
+            "exec-par",
+            "--parallel",
+            "--",
+            "git",
+            "rev-parse",
+            "--abbrev-ref",
+            "HEAD",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let repos = json["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["name"], "foo-api");
+    assert_eq!(repos[1]["name"], "foo-web");
+    for repo in repos {
+        assert_eq!(repo["exit_code"], 0);
+        assert_eq!(repo["stdout"], "testuser/exec-par\n");
+        assert!(repo["duration_ms"].is_u64());
+    }
+
+    bin_cmd()
+        .args([
+            "exec",
+            "exec-par",
+            "--parallel",
+            "2",
+            "--",
+            "git",
+            "branch",
+            "--show-current",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("foo-api | testuser/exec-par"))
+        .stdout(predicates::str::contains("foo-web | testuser/exec-par"));
+
+    drop(tmp);
+}
+
+#[test]
+fn repo_filters_narrow_exec_status_and_rm() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "filtered", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let forest_dir = worktree_base.join("filtered");
+    std::fs::write(forest_dir.join("foo-web").join("wip.txt"), "wip").unwrap();
+
+    let output = bin_cmd()
+        .args(["--json", "exec", "filtered", "--dirty", "--", "pwd"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let repos = json["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0]["name"], "foo-web");
+
+    let output = bin_cmd()
+        .args(["--json", "status", "filtered", "--exclude-repo", "foo-web"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let repos = json["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0]["name"], "foo-api");
+
+    bin_cmd()
+        .args(["rm", "filtered", "--repo", "foo-api"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Forest kept (repo filter)."));
+    assert!(!forest_dir.join("foo-api").exists());
+    assert!(forest_dir.join("foo-web").exists());
+
+    bin_cmd()
+        .args(["status", "filtered", "--repo", "foo-api"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("repo foo-api is not in forest"));
+
+    drop(tmp);
+}
+
+#[test]
+fn sync_dry_run_json_reports_plan_without_changes() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "sync-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let forest_dir = worktree_base.join("sync-e2e");
+    std::process::Command::new("git")
+        .args(["checkout", "--detach", "HEAD"])
+        .current_dir(forest_dir.join("foo-web"))
+        .output()
+        .unwrap();
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "sync",
+            "sync-e2e",
+            "--strategy",
+            "merge",
+            "--no-fetch",
+            "--dry-run",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["strategy"], "merge");
+    assert_eq!(json["dry_run"], true);
+    let repos = json["repos"].as_array().unwrap();
+    assert_eq!(repos[0]["name"], "foo-api");
+    assert_eq!(repos[0]["outcome"]["type"], "up_to_date");
+    assert_eq!(repos[1]["name"], "foo-web");
+    assert_eq!(repos[1]["outcome"]["type"], "skipped");
+
+    drop(tmp);
+}
+
+#[test]
+fn diff_and_log_json_report_changes_against_base() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "changes-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let api = worktree_base.join("changes-e2e").join("foo-api");
+    std::fs::write(api.join("change.txt"), "change\n").unwrap();
+    for args in [
+        &["add", "change.txt"][..],
+        &[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@test.com",
+            "commit",
+            "-m",
+            "Add change",
+        ],
+    ] {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&api)
+            .output()
+            .unwrap();
+    }
+
+    let output = bin_cmd()
+        .args(["--json", "diff", "changes-e2e", "--name-only"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["view"], "name_only");
+    assert_eq!(json["repos"][0]["base_ref"], "origin/main");
+    assert_eq!(json["repos"][0]["files"][0]["path"], "change.txt");
+    assert_eq!(json["repos"][0]["files"][0]["status"], "A");
+    assert_eq!(json["repos"][1]["files"].as_array().unwrap().len(), 0);
+
+    let output = bin_cmd()
+        .args(["--json", "log", "changes-e2e"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let commits = json["repos"][0]["commits"].as_array().unwrap();
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0]["subject"], "Add change");
+    assert_eq!(commits[0]["author"], "Test");
+
+    bin_cmd()
+        .args(["diff", "changes-e2e", "--stat", "--name-only"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure();
+
+    drop(tmp);
+}
+
+#[test]
+fn commit_dry_run_json_reports_repos_with_staged_changes() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "commit-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let api = worktree_base.join("commit-e2e").join("foo-api");
+    std::fs::write(api.join("change.txt"), "change").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "change.txt"])
+        .current_dir(&api)
+        .output()
+        .unwrap();
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "commit",
+            "commit-e2e",
+            "-m",
+            "Shared change",
+            "--forest-trailer",
+            "--dry-run",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["message"], "Shared change\n\nForest: commit-e2e");
+    let repos = json["repos"].as_array().unwrap();
+    assert_eq!(repos[0]["outcome"]["status"], "success");
+    assert_eq!(repos[1]["outcome"]["status"], "skipped");
+
+    drop(tmp);
+}
+
+#[test]
+fn push_dry_run_json_skips_repos_without_commits() {
+    let (tmp, fake_home, _worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "push-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let output = bin_cmd()
+        .args(["--json", "push", "push-e2e", "--dry-run"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["dry_run"], true);
+    for repo in json["repos"].as_array().unwrap() {
+        assert_eq!(repo["branch"], "testuser/push-e2e");
+        assert_eq!(repo["outcome"]["status"], "skipped");
+        assert_eq!(repo["outcome"]["reason"], "no commits beyond base branch");
+    }
+
+    drop(tmp);
+}
+
+#[test]
+fn export_then_new_from_manifest_recreates_forest() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let manifest = tmp.path().join("handoff.toml");
+
+    bin_cmd()
+        .args(["new", "handoff", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    bin_cmd()
+        .args(["export", "handoff", "--output", manifest.to_str().unwrap()])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Exported forest \"handoff\" (2 repos)",
+        ));
+    bin_cmd()
+        .args(["rm", "handoff"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "new",
+            "--from-manifest",
+            manifest.to_str().unwrap(),
+            "--no-fetch",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["forest_name"], "handoff");
+    assert_eq!(json["mode"], "feature");
+    assert_eq!(json["repos"][0]["branch"], "testuser/handoff");
+    assert!(worktree_base.join("handoff").join("foo-web").is_dir());
+
+    bin_cmd()
+        .args([
+            "new",
+            "--from-manifest",
+            manifest.to_str().unwrap(),
+            "--mode",
+            "review",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+
+    drop(tmp);
+}
+
+#[test]
+fn pin_then_checkout_pin_dry_run_reports_pinned_commits() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "pin-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    bin_cmd()
+        .args(["pin", "known-good", "pin-e2e"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Pinned checkpoint \"known-good\" in forest \"pin-e2e\"",
+        ));
+
+    let meta = std::fs::read_to_string(worktree_base.join("pin-e2e/.forest-meta.toml")).unwrap();
+    assert!(meta.contains("base_commit = "), "meta: {}", meta);
+    assert!(meta.contains("[[pins]]"), "meta: {}", meta);
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "checkout-pin",
+            "known-good",
+            "pin-e2e",
+            "--dry-run",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["checkpoint"], "known-good");
+    assert_eq!(json["on_branch"], false);
+    for repo in json["repos"].as_array().unwrap() {
+        assert_eq!(repo["outcome"]["status"], "success");
+        assert_eq!(repo["commit"].as_str().unwrap().len(), 40);
+    }
+
+    bin_cmd()
+        .args(["checkout-pin", "missing", "pin-e2e"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "available checkpoints: known-good",
+        ));
+
+    drop(tmp);
+}
+
+#[test]
+fn new_review_pr_checks_out_pull_request_head_from_remote() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let src = tmp.path().join("src").join("foo-api");
+    run_git(&src, &["checkout", "-b", "pr-work"]);
+    run_git(&src, &["commit", "--allow-empty", "-m", "PR change"]);
+    run_git(&src, &["push", "origin", "HEAD:refs/pull/12/head"]);
+    run_git(&src, &["checkout", "main"]);
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "new",
+            "review-12",
+            "--mode",
+            "review",
+            "--pr",
+            "foo-api=12",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["repos"][0]["checkout_kind"], "pull_request");
+    assert_eq!(json["repos"][0]["pull_request"], 12);
+    assert_eq!(json["repos"][1]["checkout_kind"], "new_branch");
+
+    let log = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%s"])
+        .current_dir(worktree_base.join("review-12").join("foo-api"))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "PR change");
+
+    bin_cmd()
+        .args(["new", "bad-pr", "--mode", "review", "--pr", "foo-api=abc"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid --pr format"));
+
+    drop(tmp);
+}
+
+#[test]
+fn rename_moves_forest_and_renames_template_branches() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "java-48", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    bin_cmd()
+        .args([
+            "rename",
+            "java-48",
+            "java-84",
+            "--rename-branches",
+            "--dry-run",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "foo-api → testuser/java-84 (was testuser/java-48)",
+        ));
+    assert!(worktree_base.join("java-48").exists());
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "rename",
+            "java-48",
+            "java-84",
+            "--rename-branches",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["new_name"], "java-84");
+    assert!(!worktree_base.join("java-48").exists());
+
+    let output = bin_cmd()
+        .args(["--json", "status", "java-84"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("testuser/java-84"), "status: {}", stdout);
+    assert!(
+        !stdout.contains("\"branch_drift\": true"),
+        "status: {}",
+        stdout
+    );
+
+    drop(tmp);
+}
+
+#[test]
+fn doctor_reports_deleted_worktree_and_prunes_with_fix() {
+    let (_tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "java-50", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    std::fs::remove_dir_all(worktree_base.join("java-50").join("foo-api")).unwrap();
+
+    bin_cmd()
+        .args(["doctor"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "stale-registration java-50/foo-api",
+        ))
+        .stdout(predicates::str::contains("Run `git forest doctor --fix`"));
+
+    let output = bin_cmd()
+        .args(["--json", "doctor", "--fix"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["unresolved_count"], 0);
+    assert_eq!(json["findings"][0]["fix"]["action"], "prune");
+    assert_eq!(json["findings"][0]["fix"]["outcome"]["status"], "success");
+
+    bin_cmd()
+        .args(["doctor"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "missing-worktree java-50/foo-api",
+        ));
+}
+
+#[test]
+fn repair_drift_switches_worktree_back_to_recorded_branch() {
+    let (_tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "java-51", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let api = worktree_base.join("java-51").join("foo-api");
+    run_git(&api, &["checkout", "-b", "side-quest"]);
+
+    let output = bin_cmd()
+        .args(["--json", "repair-drift", "java-51"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["repos"][0]["name"], "foo-api");
+    assert_eq!(
+        json["repos"][0]["branch_state"]["actual_branch"],
+        "side-quest"
+    );
+    assert_eq!(json["repos"][0]["outcome"]["status"], "success");
+    assert_eq!(json["repos"][1]["outcome"]["status"], "skipped");
+
+    let head = std::process::Command::new("git")
+        .args(["symbolic-ref", "--short", "HEAD"])
+        .current_dir(&api)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&head.stdout).trim(),
+        "testuser/java-51"
+    );
+}
+
+#[test]
+fn adopt_moves_hand_made_worktree_into_new_forest() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let hand_made = tmp.path().join("hand-made-api");
+    run_git(
+        &tmp.path().join("src").join("foo-api"),
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "sue/legacy",
+            hand_made.to_str().unwrap(),
+        ],
+    );
+    let worktree_arg = format!("foo-api={}", hand_made.display());
+
+    bin_cmd()
+        .args(["adopt", "legacy", "--worktree", &worktree_arg, "--dry-run"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Dry run"))
+        .stdout(predicates::str::contains("foo-api: sue/legacy from"));
+    assert!(hand_made.exists());
+
+    let output = bin_cmd()
+        .args(["--json", "adopt", "legacy", "--worktree", &worktree_arg])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["repos"][0]["outcome"]["status"], "success");
+    assert!(!hand_made.exists());
+    let meta =
+        std::fs::read_to_string(worktree_base.join("legacy").join(".forest-meta.toml")).unwrap();
+    assert!(meta.contains("branch = \"sue/legacy\""), "meta: {}", meta);
+    assert!(meta.contains("branch_created = false"), "meta: {}", meta);
+
+    bin_cmd()
+        .args(["adopt", "again", "--worktree", "foo-web=/nonexistent"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("does not exist"));
+}
+
+#[test]
+fn config_subcommands_edit_template_in_place() {
+    let (tmp, fake_home, _worktree_base) = setup_new_env();
+    let repo_c = create_repo_with_remote(tmp.path(), "foo-docs");
+    let config_path = fake_home.join(".config/git-forest/config.toml");
+
+    bin_cmd()
+        .args([
+            "config",
+            "add-repo",
+            repo_c.to_str().unwrap(),
+            "--remote",
+            "upstream",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "added repo foo-docs to template \"default\"",
+        ));
+
+    bin_cmd()
+        .args([
+            "config",
+            "set",
+            "base_branch",
+            "release",
+            "--repo",
+            "foo-web",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    bin_cmd()
+        .args(["config", "remove-repo", "foo-api"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    assert!(!config.contains("name = \"foo-api\""), "config: {}", config);
+    assert!(
+        config.contains("remote = \"upstream\""),
+        "config: {}",
+        config
+    );
+    assert!(
+        config.contains("base_branch = \"release\""),
+        "config: {}",
+        config
+    );
+
+    bin_cmd()
+        .args(["config", "delete-template", "default"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("it is the only template"));
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), config);
+}
+
+#[test]
+fn template_extends_survives_edits_and_drives_new() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let config_path = fake_home.join(".config/git-forest/config.toml");
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("\n[template.api-only]\nextends = \"default\"\nbase_branch = \"dev\"\n");
+    std::fs::write(&config_path, config).unwrap();
+    let api = tmp.path().join("src").join("foo-api");
+    run_git(&api, &["branch", "dev"]);
+    run_git(&api, &["push", "origin", "dev"]);
+
+    let other = create_repo_with_remote(tmp.path(), "bar-api");
+    bin_cmd()
+        .args([
+            "init",
+            "--template",
+            "other",
+            "--feature-branch-template",
+            "testuser/{name}",
+            "--repo",
+            other.to_str().unwrap(),
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    bin_cmd()
+        .args(["config", "remove-repo", "foo-web", "--template", "api-only"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    assert!(
+        config.contains("extends = \"default\""),
+        "config: {}",
+        config
+    );
+    assert!(
+        config.contains("remove_repos = [\"foo-web\"]"),
+        "config: {}",
+        config
+    );
+
+    bin_cmd()
+        .args([
+            "new",
+            "trimmed",
+            "--mode",
+            "feature",
+            "--template",
+            "api-only",
+            "--no-fetch",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let forest_dir = worktree_base.join("trimmed");
+    assert!(forest_dir.join("foo-api").exists());
+    assert!(!forest_dir.join("foo-web").exists());
+    let meta = std::fs::read_to_string(forest_dir.join(".forest-meta.toml")).unwrap();
+    assert!(meta.contains("base_branch = \"dev\""), "meta: {}", meta);
+}
+
+#[test]
+fn project_config_is_discovered_and_shows_origins() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let project_dir = tmp.path().join("acme");
+    std::fs::create_dir_all(project_dir.join("docs")).unwrap();
+    std::fs::write(
+        project_dir.join(".git-forest.toml"),
+        "[template.acme]\nbase_branch = \"main\"\npost_create = \"touch hooked\"\n\n[[template.acme.repos]]\npath = \"../src/foo-api\"\n",
+    )
+    .unwrap();
+
+    let output = bin_cmd()
+        .args(["config", "show", "--origin"])
+        .current_dir(project_dir.join("docs"))
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[template.acme]"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("base_branch = \"main\"  # project config"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("[template.default]"), "stdout: {}", stdout);
+    assert!(stdout.contains("# not trusted"), "stdout: {}", stdout);
+
+    // Untrusted: the personal default applies and the project hook is ignored
+    let output = bin_cmd()
+        .args(["new", "untrusted", "--mode", "feature", "--no-fetch"])
+        .current_dir(&project_dir)
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("untrusted project config"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(worktree_base.join("untrusted").join("foo-web").exists());
+
+    bin_cmd()
+        .args(["config", "trust"])
+        .current_dir(project_dir.join("docs"))
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Trusted "));
+
+    bin_cmd()
+        .args(["new", "in-project", "--mode", "feature", "--no-fetch"])
+        .current_dir(&project_dir)
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+    let forest_dir = worktree_base.join("in-project");
+    assert!(forest_dir.join("foo-api").join("hooked").exists());
+    assert!(!forest_dir.join("foo-web").exists());
+    let meta = std::fs::read_to_string(forest_dir.join(".forest-meta.toml")).unwrap();
+    assert!(meta.contains("base_branch = \"main\""), "meta: {}", meta);
+}
+
+#[test]
+fn legacy_config_is_migrated_with_backup() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let config_path = fake_home.join(".config/git-forest/config.toml");
+    let legacy = format!(
+        "[general]\nworktree_base = {:?}\nbase_branch = \"main\"\nbranch_template = \"{{user}}/{{name}}\"\nusername = \"testuser\"\n\n[[repos]]\nname = \"foo-api\"\npath = {:?}\ntype = \"mutable\"\n",
+        worktree_base.to_str().unwrap(),
+        tmp.path().join("src").join("foo-api").to_str().unwrap(),
+    );
+    std::fs::write(&config_path, &legacy).unwrap();
+
+    let output = bin_cmd()
+        .args(["config", "migrate", "--dry-run"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("from config_version 1 to 2"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("-[general]"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("+feature_branch_template = \"testuser/{name}\""),
+        "stdout: {}",
+        stdout
+    );
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), legacy);
+
+    // Other commands read the old layout in memory and leave the file alone
+    let output = bin_cmd()
+        .args(["new", "migrated", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("git forest config migrate"),
+        "stderr: {}",
+        stderr
+    );
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), legacy);
+    let backup = fake_home.join(".config/git-forest/config.toml.v1.bak");
+    assert!(!backup.exists());
+    let meta =
+        std::fs::read_to_string(worktree_base.join("migrated").join(".forest-meta.toml")).unwrap();
+    assert!(
+        meta.contains("branch = \"testuser/migrated\""),
+        "meta: {}",
+        meta
+    );
+
+    let output = bin_cmd()
+        .args(["config", "migrate"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(std::fs::read_to_string(backup).unwrap(), legacy);
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    assert!(
+        config.starts_with("config_version = 2\n"),
+        "config: {}",
+        config
+    );
+
+    let output = bin_cmd()
+        .args(["config", "migrate"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("already current"), "stdout: {}", stdout);
+}
+
+// --- add-repo command integration tests ---
+
+#[test]
+fn add_repo_by_path_grows_forest() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+    let extra = create_repo_with_remote(tmp.path(), "foo-worker");
+
+    bin_cmd()
+        .args(["new", "grow-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "add-repo",
+            "grow-e2e",
+            "--repo",
+            extra.to_str().unwrap(),
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["repos"][0]["name"], "foo-worker");
+    assert_eq!(json["repos"][0]["branch"], "testuser/grow-e2e");
+
+    let forest_dir = worktree_base.join("grow-e2e");
+    assert!(forest_dir.join("foo-worker").exists());
+    let meta = std::fs::read_to_string(forest_dir.join(".forest-meta.toml")).unwrap();
+    assert!(meta.contains("name = \"foo-worker\""), "meta: {}", meta);
+
+    drop(tmp);
+}
+
+// --- drop-repo command integration tests ---
+
+#[test]
+fn drop_repo_removes_one_repo() {
+    let (tmp, fake_home, worktree_base) = setup_new_env();
+
+    bin_cmd()
+        .args(["new", "drop-e2e", "--mode", "feature", "--no-fetch"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success();
+
+    let forest_dir = worktree_base.join("drop-e2e");
+
+    let output = bin_cmd()
+        .args([
+            "--json",
+            "drop-repo",
+            "drop-e2e",
+            "--repo",
+            "foo-web",
+            "--dry-run",
+        ])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["repo"]["worktree_removed"]["status"], "success");
+    assert_eq!(json["repo"]["branch_deleted"]["status"], "success");
+    assert!(forest_dir.join("foo-web").exists());
+
+    bin_cmd()
+        .args(["drop-repo", "drop-e2e", "--repo", "foo-web"])
+        .env("HOME", fake_home.to_str().unwrap())
+        .env("XDG_CONFIG_HOME", fake_home.join(".config"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Dropped foo-web"));
+
+    assert!(!forest_dir.join("foo-web").exists());
+    assert!(forest_dir.join("foo-api").exists());
+    let meta = std::fs::read_to_string(forest_dir.join(".forest-meta.toml")).unwrap();
+    assert!(!meta.contains("foo-web"), "meta: {}", meta);
+
+    drop(tmp);
+}
+
 // --- rm command integration tests ---
 
 #[test]